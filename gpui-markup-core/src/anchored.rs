@@ -0,0 +1,295 @@
+//! `anchored` and `overlay` elements. `overlay` is the former name of gpui's
+//! `anchored`, and lowers to `gpui::anchored()`.
+
+use proc_macro_error2::abort;
+use syn::spanned::Spanned;
+use syn::{Expr, Ident, parse_quote_spanned};
+
+use crate::ast::Attribute;
+use crate::config::Config;
+use crate::suggest::closest_match;
+
+/// Fit modes accepted by `fit_mode:`: (keyword, `AnchoredFitMode` variant,
+/// method it lowers to). `switch_anchor` is the default.
+const FIT_MODES: &[(&str, &str, Option<&str>)] = &[
+    ("switch_anchor", "SwitchAnchor", None),
+    ("snap_to_window", "SnapToWindow", Some("snap_to_window")),
+    (
+        "snap_to_window_with_margin",
+        "SnapToWindowWithMargin",
+        Some("snap_to_window_with_margin"),
+    ),
+];
+
+/// `Anchored` builder methods: (name, whether it takes a value).
+const ANCHORED_METHODS: &[(&str, bool)] = &[
+    ("anchor", true),
+    ("position", true),
+    ("offset", true),
+    ("position_mode", true),
+    ("snap_to_window", false),
+    ("snap_to_window_with_margin", true),
+];
+
+/// Attributes shared by every element: `FluentBuilder` and `ParentElement`
+/// methods, and those lowered by the macro itself.
+const GENERIC_ATTRIBUTES: &[&str] = &[
+    "when",
+    "when_some",
+    "when_none",
+    "when_else",
+    "map",
+    "apply",
+    "bind",
+    "child",
+    "children",
+];
+
+/// Variants accepted by the enum-valued methods, checked when the value is a
+/// path into the enum: (method, enum, variants).
+const ANCHORED_ENUMS: &[(&str, &str, &[&str])] = &[
+    (
+        "anchor",
+        "Corner",
+        &["TopLeft", "TopRight", "BottomLeft", "BottomRight"],
+    ),
+    (
+        "position_mode",
+        "AnchoredPositionMode",
+        &["Window", "Local"],
+    ),
+];
+
+/// Check the attributes of an `anchored` (or `overlay`) element against the
+/// `Anchored` API, which would otherwise fail as an opaque missing method deep
+/// in the generated chain. Runs after `expand_fit_mode`.
+///
+/// Events are left to `check_event_support`.
+pub fn check_anchored_attributes(element: &Ident, attributes: &[Attribute]) {
+    for attr in attributes {
+        match attr {
+            Attribute::Flag(name) => check_method(element, name, false),
+            Attribute::KeyValue { key, value } => {
+                check_method(element, key, true);
+                check_variant(key, value);
+            }
+            Attribute::Platform { attributes, .. } => {
+                check_anchored_attributes(element, attributes);
+            }
+            Attribute::PseudoState { state, .. } => abort!(
+                state,
+                "`{}` is not styled and has no `@{}` state, wrap its content in a `div` to style it",
+                element,
+                state
+            ),
+            Attribute::Compound { key, .. } | Attribute::Prop { key, .. } => {
+                check_method(element, key, true);
+            }
+            Attribute::Event { .. } => {}
+        }
+    }
+}
+
+fn check_method(element: &Ident, name: &Ident, has_value: bool) {
+    if has_value && GENERIC_ATTRIBUTES.iter().any(|generic| name == generic) {
+        return;
+    }
+    match ANCHORED_METHODS.iter().find(|(method, _)| name == method) {
+        Some((_, true)) if !has_value => {
+            abort!(name, "`{}` takes a value, e.g. `{}: ..`", name, name)
+        }
+        Some((_, false)) if has_value => abort!(name, "`{}` is a flag and takes no value", name),
+        Some(_) => {}
+        None => {
+            let supported = ANCHORED_METHODS
+                .iter()
+                .map(|(method, _)| *method)
+                .chain(["fit_mode"]);
+            if let Some(suggestion) = closest_match(&name.to_string(), supported.clone()) {
+                abort!(
+                    name,
+                    "unknown `{}` attribute `{}`, did you mean `{}`?",
+                    element,
+                    name,
+                    suggestion
+                );
+            }
+            abort!(
+                name,
+                "unknown `{}` attribute `{}`, expected one of: {}",
+                element,
+                name,
+                supported.collect::<Vec<_>>().join(", ")
+            );
+        }
+    }
+}
+
+/// Report unknown variants in `anchor: Corner::..` and
+/// `position_mode: AnchoredPositionMode::..`.
+fn check_variant(key: &Ident, value: &Expr) {
+    let Some(&(_, enum_name, variants)) = ANCHORED_ENUMS.iter().find(|(method, ..)| key == method)
+    else {
+        return;
+    };
+    let Expr::Path(path) = value else {
+        return;
+    };
+    let segments = &path.path.segments;
+    if segments.len() < 2 || segments[segments.len() - 2].ident != enum_name {
+        return;
+    }
+    let variant = &segments[segments.len() - 1].ident;
+    if !variants.iter().any(|known| variant == known) {
+        abort!(
+            variant,
+            "unknown `{}` variant `{}`, expected one of: {}",
+            enum_name,
+            variant,
+            variants.join(", ")
+        );
+    }
+}
+
+/// Rewrite `fit_mode: ..` into the method setting it, since `Anchored` has no
+/// `fit_mode` setter.
+///
+/// Accepts a keyword (`snap_to_window`) or an `AnchoredFitMode` variant
+/// (`AnchoredFitMode::SnapToWindow`), with the margin as an argument:
+/// `snap_to_window_with_margin(px(8.0))`.
+pub fn expand_fit_mode(attributes: &mut Vec<Attribute>) {
+    let mut expanded = Vec::with_capacity(attributes.len());
+    for attr in attributes.drain(..) {
+        match attr {
+            Attribute::KeyValue { key, value } if key == "fit_mode" => {
+                expanded.extend(fit_mode(&value));
+            }
+            attr => expanded.push(attr),
+        }
+    }
+    *attributes = expanded;
+}
+
+fn fit_mode(value: &Expr) -> Option<Attribute> {
+    let (path, arguments) = match value {
+        Expr::Path(path) if path.qself.is_none() => (&path.path, None),
+        Expr::Call(call) => match call.func.as_ref() {
+            Expr::Path(path) if path.qself.is_none() => (&path.path, Some(&call.args)),
+            _ => unknown_fit_mode(value),
+        },
+        _ => unknown_fit_mode(value),
+    };
+    let Some(name) = path.segments.last().map(|segment| &segment.ident) else {
+        unknown_fit_mode(value)
+    };
+    let Some(&(keyword, _, method)) = FIT_MODES
+        .iter()
+        .find(|(keyword, variant, _)| name == keyword || name == variant)
+    else {
+        if let Some(suggestion) = closest_match(
+            &name.to_string(),
+            FIT_MODES.iter().map(|(keyword, ..)| *keyword),
+        ) {
+            abort!(
+                name,
+                "unknown `fit_mode` `{}`, did you mean `{}`?",
+                name,
+                suggestion
+            );
+        }
+        unknown_fit_mode(value)
+    };
+
+    let method = method.map(|method| Ident::new(method, name.span()));
+    match (method, arguments) {
+        (None, None) => None,
+        (Some(method), None) if keyword == "snap_to_window" => Some(Attribute::Flag(method)),
+        (Some(method), Some(arguments)) if arguments.len() == 1 => {
+            let margin = &arguments[0];
+            // The variant holds `Edges<Pixels>` while the method takes
+            // `impl Into<Edges<Pixels>>`: keep the field type, so a margin
+            // written for the variant, like `px(8.0).into()`, still infers
+            let value = if name == keyword {
+                margin.clone()
+            } else {
+                let gpui = Config::current().gpui();
+                parse_quote_spanned! {margin.span()=>
+                    ::core::convert::identity::<#gpui::Edges<#gpui::Pixels>>(#margin)
+                }
+            };
+            Some(Attribute::KeyValue { key: method, value })
+        }
+        _ => abort!(
+            value,
+            "`{}` takes {}",
+            keyword,
+            if keyword == "snap_to_window_with_margin" {
+                "the margin as an argument, e.g. `snap_to_window_with_margin(px(8.0))`"
+            } else {
+                "no arguments"
+            }
+        ),
+    }
+}
+
+fn unknown_fit_mode(value: &Expr) -> ! {
+    abort!(
+        value,
+        "unknown `fit_mode`, expected one of: switch_anchor, snap_to_window, snap_to_window_with_margin(edges)"
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use proc_macro2::Span;
+    use syn::parse_quote;
+
+    use super::*;
+
+    fn expand(value: Expr) -> Vec<Attribute> {
+        let mut attributes = vec![Attribute::KeyValue {
+            key: Ident::new("fit_mode", Span::call_site()),
+            value,
+        }];
+        expand_fit_mode(&mut attributes);
+        attributes
+    }
+
+    #[test]
+    fn test_keywords() {
+        assert!(expand(parse_quote!(switch_anchor)).is_empty());
+        assert!(matches!(
+            &expand(parse_quote!(snap_to_window))[..],
+            [Attribute::Flag(flag)] if flag == "snap_to_window"
+        ));
+    }
+
+    #[test]
+    fn test_anchored_attributes() {
+        let element = Ident::new("anchored", Span::call_site());
+        let attributes = vec![
+            Attribute::KeyValue {
+                key: Ident::new("anchor", Span::call_site()),
+                value: parse_quote!(gpui::Corner::BottomRight),
+            },
+            Attribute::Flag(Ident::new("snap_to_window", Span::call_site())),
+            Attribute::KeyValue {
+                key: Ident::new("when", Span::call_site()),
+                value: parse_quote!((open, |this| this)),
+            },
+        ];
+        check_anchored_attributes(&element, &attributes);
+    }
+
+    #[test]
+    fn test_variants() {
+        assert!(matches!(
+            &expand(parse_quote!(AnchoredFitMode::SnapToWindow))[..],
+            [Attribute::Flag(flag)] if flag == "snap_to_window"
+        ));
+        assert!(matches!(
+            &expand(parse_quote!(AnchoredFitMode::SnapToWindowWithMargin(px(8.0))))[..],
+            [Attribute::KeyValue { key, .. }] if key == "snap_to_window_with_margin"
+        ));
+    }
+}
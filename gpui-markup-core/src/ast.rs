@@ -0,0 +1,222 @@
+//! AST definitions for gpui-markup DSL.
+
+use proc_macro2::TokenStream;
+use syn::{Expr, Ident, Path, Type};
+
+use crate::lint::Lints;
+use crate::options::Options;
+use crate::registry::ElementDef;
+
+/// Root node of the markup DSL.
+#[derive(Debug)]
+pub struct Markup {
+    pub element: Element,
+    /// Code generation options from `#![options(..)]`
+    pub options: Options,
+    /// Lint levels from `#![allow(..)]`, `#![warn(..)]` and `#![deny(..)]`
+    pub lints: Lints,
+    /// Warnings reported while parsing
+    pub warnings: TokenStream,
+}
+
+/// A list of children, built into a `Vec<AnyElement>` by `ui_vec!`.
+#[derive(Debug)]
+pub struct MarkupVec {
+    pub children: Vec<Child>,
+}
+
+/// A reusable list of style attributes, built into a `StyleRefinement` by
+/// `style!`.
+#[derive(Debug)]
+pub struct StyleMixin {
+    pub attributes: Vec<Attribute>,
+}
+
+/// An element in the markup tree.
+#[derive(Debug)]
+pub enum Element {
+    /// Native elements: `div`, `svg`, `anchored`, `overlay`, `surface`,
+    /// `canvas`, `list`, `uniform_list`, `empty`
+    Native(NativeElement),
+    /// Component elements: `Header`, `Button`, etc. (calls `::new()`
+    /// implicitly)
+    Component(ComponentElement),
+    /// `deferred { child }`
+    Deferred(DeferredElement),
+    /// Expression as element: `(expr) [attrs] { children }`
+    Expression(ExprElement),
+    /// Inline styled text: `span @[font_weight: BOLD] { "Enter" }`
+    Span(SpanElement),
+    /// Clickable text: `rich_text @[id: "log"] { .. }`, whose `span` children
+    /// take `on_click`
+    RichText(RichTextElement),
+}
+
+#[derive(Debug)]
+pub struct NativeElement {
+    pub name: Ident,
+    pub attributes: Vec<Attribute>,
+    pub children: Vec<Child>,
+    /// Declaration of a custom element from `#![elements(..)]`
+    pub definition: Option<ElementDef>,
+}
+
+#[derive(Debug)]
+pub struct ComponentElement {
+    /// Component type, optionally path-qualified: `Button`, `ui::Button`
+    pub name: Path,
+    /// Constructor arguments: `Button("Save")` calls `Button::new("Save")`
+    pub arguments: Vec<Expr>,
+    pub attributes: Vec<Attribute>,
+    pub children: Vec<Child>,
+}
+
+#[derive(Debug)]
+pub struct DeferredElement {
+    pub name: Ident,
+    pub attributes: Vec<Attribute>,
+    pub child: Box<Child>,
+}
+
+/// A styled run of text, merged with the surrounding text children into a
+/// single `StyledText`.
+#[derive(Debug)]
+pub struct SpanElement {
+    pub name: Ident,
+    pub attributes: Vec<Attribute>,
+    pub children: Vec<Child>,
+}
+
+/// Text and `span` children rendered as an `InteractiveText`, whose spans may
+/// handle clicks.
+#[derive(Debug)]
+pub struct RichTextElement {
+    pub name: Ident,
+    /// The `id` attribute, passed to `InteractiveText::new`
+    pub id: Expr,
+    pub attributes: Vec<Attribute>,
+    pub children: Vec<Child>,
+}
+
+/// An expression used as an element.
+#[derive(Debug)]
+pub struct ExprElement {
+    pub expr: Expr,
+    pub attributes: Vec<Attribute>,
+    pub children: Vec<Child>,
+}
+
+/// An attribute on an element.
+#[derive(Debug)]
+pub enum Attribute {
+    /// Flag attribute: `flex`, `cursor_pointer`, etc.
+    Flag(Ident),
+    /// Key-value attribute: `w: px(200.0)`, `when: (cond, fn)`, etc.
+    KeyValue { key: Ident, value: Expr },
+    /// Pseudo-state block: `@hover[...]`, `@group_hover("row")[...]`, etc.,
+    /// or `@base[...]` forwarding styles to a component's base element
+    PseudoState {
+        state: Ident,
+        /// Group name for `group_*` states
+        group: Option<Expr>,
+        attributes: Vec<Self>,
+    },
+    /// Platform-conditional block: `macos: { pt: px(28.0) }`
+    Platform {
+        platform: Ident,
+        attributes: Vec<Self>,
+    },
+    /// Component prop: `:label: "Save"` or `:disabled`, always a plain
+    /// builder method call, with tuple values split into arguments
+    Prop { key: Ident, value: Option<Expr> },
+    /// Composite attribute with named arguments: `grid: (cols: 3, gap: 2)`
+    Compound { key: Ident, attributes: Vec<Self> },
+    /// Event listener: `on:click: |event, window, cx| ...`, with modifiers
+    /// like `on:click.double:`, an argument like `on:key("cmd-s"):` or a
+    /// payload type like `on:drop::<Tab>:`
+    Event {
+        event: Ident,
+        payload: Option<Box<Type>>,
+        argument: Option<Expr>,
+        modifiers: Vec<Ident>,
+        handler: Expr,
+    },
+}
+
+impl Element {
+    /// Attributes applied to this element.
+    #[must_use]
+    pub fn attributes(&self) -> &[Attribute] {
+        match self {
+            Self::Native(el) => &el.attributes,
+            Self::Component(el) => &el.attributes,
+            Self::Deferred(el) => &el.attributes,
+            Self::Expression(el) => &el.attributes,
+            Self::Span(el) => &el.attributes,
+            Self::RichText(el) => &el.attributes,
+        }
+    }
+
+    /// The variable named by a `bind:` attribute on this element.
+    #[must_use]
+    pub fn bound_name(&self) -> Option<&Ident> {
+        self.attributes().iter().find_map(|attr| match attr {
+            Attribute::KeyValue {
+                key,
+                value: Expr::Path(path),
+            } if key == "bind" => path.path.get_ident(),
+            _ => None,
+        })
+    }
+
+    /// Children of this element.
+    #[must_use]
+    pub fn children(&self) -> &[Child] {
+        match self {
+            Self::Native(el) => &el.children,
+            Self::Component(el) => &el.children,
+            Self::Deferred(el) => std::slice::from_ref(el.child.as_ref()),
+            Self::Expression(el) => &el.children,
+            Self::Span(el) => &el.children,
+            Self::RichText(el) => &el.children,
+        }
+    }
+}
+
+/// A child of an element.
+#[derive(Debug)]
+pub enum Child {
+    /// A nested element (including all expressions)
+    Element(Element),
+    /// A spread expression: `..expr` where expr is iterable
+    Spread(Expr),
+    /// A method chain: `.method(args)` or `.a().b::<T>()`
+    MethodChain(TokenStream),
+    /// A child gated by `#[cfg(...)]` attributes
+    Cfg {
+        attrs: Vec<syn::Attribute>,
+        child: Box<Self>,
+    },
+}
+
+impl Child {
+    /// An expression child without attributes or children.
+    #[must_use]
+    pub const fn expression(expr: Expr) -> Self {
+        Self::Element(Element::Expression(ExprElement {
+            expr,
+            attributes: vec![],
+            children: vec![],
+        }))
+    }
+
+    /// The element of this child, looking through `#[cfg(...)]` gates.
+    #[must_use]
+    pub fn element(&self) -> Option<&Element> {
+        match self {
+            Self::Element(element) => Some(element),
+            Self::Cfg { child, .. } => child.element(),
+            Self::Spread(_) | Self::MethodChain(_) => None,
+        }
+    }
+}
@@ -0,0 +1,114 @@
+//! Custom drawing with `gpui::canvas`: `canvas { prepaint: .., paint: .. }`,
+//! with `prepaint: |bounds, window, cx| ..` and
+//! `paint: |bounds, state, window, cx| ..` callbacks.
+//!
+//! The callbacks are kept as the two children of the `canvas` element and
+//! passed to the constructor inline, so the closure parameter types are
+//! inferred from `gpui::canvas`.
+
+use proc_macro_error2::abort;
+use syn::parse::ParseStream;
+use syn::{Expr, Ident, Result, Token, braced, parse_quote};
+
+use crate::ast::Child;
+
+/// Callbacks accepted in a `canvas` body, with their number of parameters.
+const CALLBACKS: &[(&str, usize, &str)] = &[
+    ("prepaint", 3, "|bounds, window, cx|"),
+    ("paint", 4, "|bounds, state, window, cx|"),
+];
+
+/// Parse the body of a `canvas` into its `prepaint` and `paint` callbacks.
+///
+/// `prepaint` defaults to a no-op, leaving `paint` a `()` state.
+pub fn parse_canvas_callbacks(name: &Ident, input: ParseStream) -> Result<Vec<Child>> {
+    let content;
+    braced!(content in input);
+
+    let mut callbacks: [Option<Expr>; 2] = [None, None];
+    while !content.is_empty() {
+        let key: Ident = content.parse()?;
+        let Some(index) = CALLBACKS.iter().position(|(known, ..)| key == known) else {
+            abort!(
+                key,
+                "unknown `canvas` callback `{}`, expected `prepaint` or `paint`",
+                key
+            );
+        };
+        content.parse::<Token![:]>()?;
+        let value: Expr = content.parse()?;
+        check_arity(&value, CALLBACKS[index]);
+        if callbacks[index].replace(value).is_some() {
+            abort!(key, "duplicate `canvas` callback `{}`", key);
+        }
+        if !content.is_empty() {
+            content.parse::<Token![,]>()?;
+        }
+    }
+
+    let [prepaint, paint] = callbacks;
+    let Some(paint) = paint else {
+        abort!(
+            name,
+            "`canvas` requires a `paint` callback, e.g. `canvas { paint: |bounds, state, window, cx| .. }`"
+        );
+    };
+    let prepaint = prepaint.unwrap_or_else(|| parse_quote! { |_, _, _| {} });
+
+    Ok(vec![Child::expression(prepaint), Child::expression(paint)])
+}
+
+/// Report closures taking the wrong number of parameters.
+fn check_arity(value: &Expr, (name, arity, parameters): (&str, usize, &str)) {
+    if let Expr::Closure(closure) = value
+        && closure.inputs.len() != arity
+    {
+        abort!(
+            closure.or1_token,
+            "`{}` takes {} parameters: `{}`",
+            name,
+            arity,
+            parameters
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use quote::{ToTokens, quote};
+    use syn::parse::Parser;
+
+    use super::*;
+    use crate::ast::Element;
+
+    fn parse(tokens: proc_macro2::TokenStream) -> Vec<Child> {
+        let name = Ident::new("canvas", proc_macro2::Span::call_site());
+        (|input: ParseStream| parse_canvas_callbacks(&name, input))
+            .parse2(tokens)
+            .unwrap()
+    }
+
+    fn callback(child: &Child) -> String {
+        match child.element() {
+            Some(Element::Expression(el)) => el.expr.to_token_stream().to_string(),
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn test_callbacks() {
+        let children = parse(quote! {{
+            paint: |bounds, state, window, cx| window.paint_quad(state),
+            prepaint: |bounds, window, cx| bounds,
+        }});
+        assert_eq!(children.len(), 2);
+        assert!(callback(&children[0]).contains("bounds , window , cx"));
+        assert!(callback(&children[1]).contains("paint_quad"));
+    }
+
+    #[test]
+    fn test_default_prepaint() {
+        let children = parse(quote! {{ paint: draw }});
+        assert_eq!(callback(&children[0]), quote! { |_, _, _| {} }.to_string());
+    }
+}
@@ -0,0 +1,52 @@
+//! `#![options(checked)]`: expression children asserted to be elements where
+//! they are written.
+//!
+//! ```ignore
+//! ui! {
+//!     #![options(checked)]
+//!     div { &self.title, count }
+//! }
+//! ```
+//!
+//! A child that isn't an element is reported by the compiler as a missing
+//! `IntoElement` impl at the `child` call of the generated code. With the
+//! option, each expression child is passed to a function requiring an
+//! element, spanned at the child, whose error also says which values make
+//! children. The function converts the child into an `AnyElement`, as
+//! `child` would, so a child that isn't an element is reported once.
+//!
+//! The function is the value of the block declaring it and is called outside
+//! of it: items aren't hidden by hygiene, so the child expression must not
+//! see them.
+
+use proc_macro2::{Span, TokenStream};
+use quote::quote;
+use syn::spanned::Spanned;
+use syn::{Expr, Ident};
+
+use crate::config::Config;
+
+/// `expr` passed through a function asserting it is an element.
+pub fn child_check(expr: &Expr) -> TokenStream {
+    let gpui = Config::current().gpui();
+    let into_element = Config::current().item("IntoElement");
+    let check = Ident::new("__Child", Span::mixed_site());
+    let function = Ident::new("__into_child", Span::mixed_site());
+    let call = Ident::new("__into_child", Span::mixed_site().located_at(expr.span()));
+    quote! {
+        ({
+            #[diagnostic::on_unimplemented(
+                message = "`{Self}` can't be a child element",
+                label = "not an element",
+                note = "children implement `IntoElement`, like elements and text: `&'static str`, `String` or `SharedString`",
+                note = "show numbers and other `Display` values with `text: value`"
+            )]
+            trait #check: #into_element {}
+            impl<T: #into_element> #check for T {}
+            fn #function<E: #check>(child: E) -> #gpui::AnyElement {
+                #into_element::into_any_element(child)
+            }
+            #call
+        })(#expr)
+    }
+}
@@ -0,0 +1,112 @@
+//! `ui_fn!`: markup wrapped in a `move` closure.
+//!
+//! ```ignore
+//! ui_fn!(|item: &FileEntry| div { item.name.clone() })
+//! // -> move |item: &FileEntry| gpui::ParentElement::child(div(), item.name.clone())
+//! ```
+
+use proc_macro2::TokenStream;
+use quote::{ToTokens, quote};
+use syn::parse::{Parse, ParseStream};
+use syn::punctuated::Punctuated;
+use syn::{Pat, PatType, Result, Token};
+
+use crate::ast::Markup;
+
+/// A parsed `ui_fn!` invocation: closure parameters and the markup body.
+#[derive(Debug)]
+pub struct MarkupFn {
+    pub inputs: Punctuated<Pat, Token![,]>,
+    pub markup: Markup,
+}
+
+impl Parse for MarkupFn {
+    fn parse(input: ParseStream) -> Result<Self> {
+        Ok(Self {
+            inputs: parse_closure_inputs(input)?,
+            markup: input.parse()?,
+        })
+    }
+}
+
+/// Parse closure parameters: `||` or `|a, b: &T|`.
+pub fn parse_closure_inputs(input: ParseStream) -> Result<Punctuated<Pat, Token![,]>> {
+    let mut inputs = Punctuated::new();
+    if input.parse::<Option<Token![||]>>()?.is_none() {
+        input.parse::<Token![|]>()?;
+        while !input.peek(Token![|]) {
+            inputs.push_value(parse_input(input)?);
+            if input.peek(Token![|]) {
+                break;
+            }
+            inputs.push_punct(input.parse()?);
+        }
+        input.parse::<Token![|]>()?;
+    }
+    Ok(inputs)
+}
+
+/// A closure parameter, optionally typed: `item` or `item: &FileEntry`.
+fn parse_input(input: ParseStream) -> Result<Pat> {
+    let pat = Pat::parse_single(input)?;
+    if !input.peek(Token![:]) {
+        return Ok(pat);
+    }
+    Ok(Pat::Type(PatType {
+        attrs: vec![],
+        pat: Box::new(pat),
+        colon_token: input.parse()?,
+        ty: input.parse()?,
+    }))
+}
+
+impl ToTokens for MarkupFn {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let Self { inputs, markup } = self;
+        if inputs.is_empty() {
+            tokens.extend(quote! { move || #markup });
+        } else {
+            tokens.extend(quote! { move |#inputs| #markup });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use syn::parse2;
+
+    use super::*;
+
+    fn expand(tokens: TokenStream) -> String {
+        parse2::<MarkupFn>(tokens)
+            .unwrap()
+            .to_token_stream()
+            .to_string()
+    }
+
+    #[test]
+    fn test_typed_input() {
+        assert_eq!(
+            expand(quote! { |item: &FileEntry| div { item.name.clone() } }),
+            quote! { move |item: &FileEntry| gpui::ParentElement::child(div(), item.name.clone()) }
+                .to_string()
+        );
+    }
+
+    #[test]
+    fn test_inputs() {
+        assert_eq!(
+            expand(quote! { |(ix, item), _cx| div @[id: ix] {} }),
+            quote! { move |(ix, item), _cx| div().id(ix) }.to_string()
+        );
+        assert_eq!(
+            expand(quote! { || div {} }),
+            quote! { move || div() }.to_string()
+        );
+    }
+
+    #[test]
+    fn test_missing_inputs() {
+        assert!(parse2::<MarkupFn>(quote! { div {} }).is_err());
+    }
+}
@@ -0,0 +1,1697 @@
+//! Code generation for gpui-markup DSL.
+
+use proc_macro2::{Delimiter, Group, Ident, Span, TokenStream};
+use quote::{ToTokens, format_ident, quote, quote_spanned};
+use syn::Expr;
+use syn::spanned::Spanned;
+
+use crate::ast::{
+    Attribute, Child, ComponentElement, DeferredElement, Element, ExprElement, Markup, MarkupVec,
+    NativeElement, RichTextElement,
+};
+use crate::child::child_check;
+use crate::config::Config;
+use crate::drag::drag_calls;
+use crate::event::event_call;
+use crate::hoist::hoisted;
+use crate::lazy::lazy;
+use crate::lint::{AttributeLint, Level, with_lints};
+use crate::memo::{memo, memo_style};
+use crate::methods::check_unknown_attributes;
+use crate::mixin::use_call;
+use crate::options::{current_options, with_options};
+use crate::portal::{portal, portal_layer};
+use crate::preset::flex_helper;
+use crate::shorthand::{expand_compound, expand_shorthand};
+use crate::span::{TextRun, group_text_runs, interactive_text, styled_text};
+use crate::split::{split_runs, with_size_cache};
+use crate::styled::styled_check;
+use crate::theme::{expand_theme_tokens, respan};
+
+impl ToTokens for Markup {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let (element, warnings) = with_lints(self.lints, || {
+            with_options(self.options, || {
+                with_size_cache(|| self.element.to_token_stream())
+            })
+        });
+        let mut prelude = Config::current().dependency().unwrap_or_default();
+        prelude.extend(self.warnings.clone());
+        prelude.extend(warnings);
+        if prelude.is_empty() {
+            tokens.extend(element);
+        } else {
+            tokens.extend(quote! {{ #prelude #element }});
+        }
+    }
+}
+
+impl ToTokens for MarkupVec {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let gpui = Config::current().gpui();
+        let parent_element = Config::current().item("ParentElement");
+        // A local parent collecting children, so they go through the same
+        // text grouping, spreads and `#[cfg]` gates as element children
+        // The parent type is the value of the block declaring it, so the
+        // children, appended outside of it, don't see the declared items
+        let elements = Ident::new("__Elements", Span::mixed_site());
+        let dependency = Config::current().dependency();
+        let parent = quote! {
+            ({
+                #dependency
+                struct #elements(::std::vec::Vec<#gpui::AnyElement>);
+
+                impl #parent_element for #elements {
+                    fn extend(&mut self, elements: impl ::std::iter::IntoIterator<Item = #gpui::AnyElement>) {
+                        ::std::iter::Extend::extend(&mut self.0, elements);
+                    }
+                }
+
+                #elements
+            })(::std::vec::Vec::new())
+        };
+        let children = with_size_cache(|| append_children(parent, &self.children, &parent_element));
+        tokens.extend(quote! { #children.0 });
+    }
+}
+
+impl ToTokens for Element {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        match self {
+            Self::Native(el) => el.to_tokens(tokens),
+            Self::Component(el) => el.to_tokens(tokens),
+            Self::Deferred(el) => el.to_tokens(tokens),
+            Self::Expression(expr) => expr.to_tokens(tokens),
+            Self::Span(_) => tokens.extend(styled_text([self])),
+            Self::RichText(el) => el.to_tokens(tokens),
+        }
+    }
+}
+
+/// Native elements whose children are constructor arguments: the frame
+/// source of a `surface`, the callbacks of a `canvas`, and the arguments and
+/// renderer of a `list` or `uniform_list`.
+const ARGUMENT_ELEMENTS: &[&str] = &["surface", "canvas", "list", "uniform_list"];
+
+impl ToTokens for NativeElement {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let name = &self.name;
+        if ARGUMENT_ELEMENTS.iter().any(|element| name == element) {
+            let arguments = self.children.iter().filter_map(Child::element);
+            generate_native_element(
+                &quote! { #name(#(#arguments),*) },
+                &self.attributes,
+                &[],
+                tokens,
+            );
+            return;
+        }
+        if name == "portal" {
+            let [priority, window, children @ ..] = self.children.as_slice() else {
+                unreachable!("portal priority and window are inserted by the parser")
+            };
+            let mut layer = TokenStream::new();
+            generate_native_element(
+                &portal_layer(window),
+                &self.attributes,
+                children,
+                &mut layer,
+            );
+            tokens.extend(portal(priority, &layer));
+            return;
+        }
+        if name == "lazy" {
+            tokens.extend(lazy(&self.children[0]));
+            return;
+        }
+        if name == "memo" {
+            let mut style = TokenStream::new();
+            generate_native_element(&memo_style(), &self.attributes, &[], &mut style);
+            tokens.extend(memo(name, &self.children, &style));
+            return;
+        }
+        if self.definition.is_none() && AttributeLint::Unknown.level() != Level::Allow {
+            check_unknown_attributes(name, &self.attributes);
+        }
+        if let Some(definition) = &self.definition {
+            let constructor = &definition.constructor;
+            generate_native_element(
+                &quote_spanned! {name.span()=> #constructor() },
+                &self.attributes,
+                &self.children,
+                tokens,
+            );
+            return;
+        }
+        let gpui = Config::current().gpui();
+        let styled = Config::current().item("Styled");
+        let constructor = match name.to_string().as_str() {
+            // gpui has no `overlay` function anymore, it was renamed to `anchored`
+            "overlay" => quote_spanned! {name.span()=> #gpui::anchored() },
+            "empty" => quote_spanned! {name.span()=> #gpui::Empty },
+            "h_flex" => Config::current()
+                .h_flex()
+                .or_else(|| flex_helper("h_flex"))
+                .map_or_else(
+                    || quote_spanned! {name.span()=> #styled::flex_row(#styled::flex(#gpui::div())) },
+                    |helper| quote_spanned! {name.span()=> #helper() },
+                ),
+            "v_flex" => Config::current()
+                .v_flex()
+                .or_else(|| flex_helper("v_flex"))
+                .map_or_else(
+                    || quote_spanned! {name.span()=> #styled::flex_col(#styled::flex(#gpui::div())) },
+                    |helper| quote_spanned! {name.span()=> #helper() },
+                ),
+            "stack" => quote_spanned! {name.span()=> #styled::relative(#gpui::div()) },
+            "spacer" => quote_spanned! {name.span()=> #styled::flex_grow(#gpui::div()) },
+            "divider" => {
+                let border = expand_theme_tokens(quote_spanned! {name.span()=> $border });
+                quote_spanned! {name.span()=> #styled::bg(#styled::flex_none(#gpui::div()), #border) }
+            }
+            _ => quote! { #name() },
+        };
+        generate_native_element(&constructor, &self.attributes, &self.children, tokens);
+    }
+}
+
+impl ToTokens for ComponentElement {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let name = &self.name;
+        let arguments = &self.arguments;
+        generate_element(
+            &styled_check(quote! { #name::new(#(#arguments),*) }, &self.attributes),
+            &self.attributes,
+            &self.children,
+            tokens,
+        );
+    }
+}
+
+impl ToTokens for DeferredElement {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let name = &self.name;
+        let child_tokens = match self.child.as_ref() {
+            Child::Element(element) => quote! { #element },
+            _ => unreachable!("deferred only accepts Element children"),
+        };
+        let into_element = Config::current().item("IntoElement");
+        generate_element(
+            &quote! { #name(#into_element::into_any_element(#child_tokens)) },
+            &self.attributes,
+            &[],
+            tokens,
+        );
+    }
+}
+
+impl ToTokens for RichTextElement {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        generate_element(&interactive_text(self), &self.attributes, &[], tokens);
+    }
+}
+
+impl ToTokens for ExprElement {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let expr = element_expr(&self.expr);
+        let parent_element = Config::current().item("ParentElement");
+        let (base, parent_element) = if takes_children(self) {
+            // The check and the `child` calls on its result share one span,
+            // so rustc reports an element failing the check once, as
+            // pointed there, rather than again for every child
+            let span = Span::mixed_site().located_at(self.expr.span());
+            (parent_check(&expr, span), respan(parent_element, span))
+        } else {
+            (expr, parent_element)
+        };
+        generate(
+            &styled_check(base, &self.attributes),
+            &self.attributes,
+            &self.children,
+            tokens,
+            false,
+            &parent_element,
+        );
+    }
+}
+
+/// Whether the children of an expression element are added with
+/// `ParentElement`. Method chains are called on the element as written, and
+/// `map` or `apply` may turn it into another type before its children.
+fn takes_children(element: &ExprElement) -> bool {
+    element
+        .children
+        .iter()
+        .any(|child| !matches!(child, Child::MethodChain(_)))
+        && !element.attributes.iter().any(
+            |attr| matches!(attr, Attribute::KeyValue { key, .. } if key == "map" || key == "apply"),
+        )
+}
+
+/// The expression of an expression element. The parentheses around `(if ..)`
+/// or `(div())` are kept, as methods may be called on it, but spanned as
+/// generated code so rustc doesn't report them as unnecessary.
+fn element_expr(expr: &Expr) -> TokenStream {
+    let Expr::Paren(paren) = expr else {
+        return expr.to_token_stream();
+    };
+    let attrs = &paren.attrs;
+    let mut group = Group::new(Delimiter::Parenthesis, paren.expr.to_token_stream());
+    group.set_span(Span::mixed_site().located_at(paren.paren_token.span.join()));
+    quote! { #(#attrs)* #group }
+}
+
+/// `expr` passed through a function requiring `ParentElement`, so an element
+/// that can't take children is reported at the expression, by name, rather
+/// than as an unsatisfied bound deep in the generated `child` calls.
+fn parent_check(expr: &TokenStream, span: Span) -> TokenStream {
+    let parent_element = Config::current().item("ParentElement");
+    let check = Ident::new("__ParentElement", Span::mixed_site());
+    let function = Ident::new("__parent", Span::mixed_site());
+    let call = Ident::new("__parent", span);
+    let helper = quote! {
+        {
+            #[diagnostic::on_unimplemented(
+                message = "`{Self}` can't take children",
+                label = "given children in `ui!`, but doesn't implement `ParentElement`",
+                note = "only elements implementing `ParentElement` take children, wrap it in a `div` to lay it out with others"
+            )]
+            trait #check: #parent_element {}
+            impl<T: #parent_element> #check for T {}
+            fn #function<T: #check>(element: T) -> T {
+                element
+            }
+            #call
+        }
+    };
+    let mut helper = Group::new(Delimiter::Parenthesis, helper);
+    helper.set_span(span);
+    let mut argument = Group::new(Delimiter::Parenthesis, expr.clone());
+    argument.set_span(span);
+    quote! { #helper #argument }
+}
+
+pub fn generate_element(
+    base: &TokenStream,
+    attributes: &[Attribute],
+    children: &[Child],
+    tokens: &mut TokenStream,
+) {
+    let parent_element = Config::current().item("ParentElement");
+    generate(base, attributes, children, tokens, false, &parent_element);
+}
+
+/// Like [`generate_element`], for a native element or style mixin, whose
+/// attributes are the crate's own `Styled` and interactive methods: shorthands
+/// like `size: ..` or `scroll: ..` are expanded. The attributes of components
+/// and expression elements may be methods of their own with the same names,
+/// so they are called as written.
+pub fn generate_native_element(
+    base: &TokenStream,
+    attributes: &[Attribute],
+    children: &[Child],
+    tokens: &mut TokenStream,
+) {
+    let parent_element = Config::current().item("ParentElement");
+    generate(base, attributes, children, tokens, true, &parent_element);
+}
+
+/// Attribute values are passed inline to the call they lower to, so they are
+/// evaluated exactly once, in source order along the chain, and their type can
+/// be inferred from the parameter (`Default::default()`, `.into()` or a
+/// closure).
+fn generate(
+    base: &TokenStream,
+    attributes: &[Attribute],
+    children: &[Child],
+    tokens: &mut TokenStream,
+    shorthands: bool,
+    parent_element: &TokenStream,
+) {
+    let calls = attribute_calls(attributes, shorthands);
+
+    if current_options().linear && !(calls.is_empty() && children.is_empty()) {
+        let element = Ident::new("__element", Span::mixed_site());
+        let calls = calls
+            .iter()
+            .map(|call| chain(&element, std::slice::from_ref(call)));
+        // A statement per attribute and child, see `options`
+        let (child_statements, appended) = prepare_children(children);
+        let steps = appended
+            .iter()
+            .map(|item| linear_step(&element, item, parent_element));
+        tokens.extend(quote! {
+            {
+                let #element = #base;
+                #(let #element = #calls;)*
+                #(#child_statements)*
+                #(#steps)*
+                #element
+            }
+        });
+        return;
+    }
+    tokens.extend(append_children(
+        chain(base, &calls),
+        children,
+        parent_element,
+    ));
+}
+
+/// An attribute lowered to a call on the element built so far.
+pub enum Call {
+    /// A method-call suffix, like `.flex()` or `.w(value)`
+    Method(TokenStream),
+    /// `FluentBuilder::map(element, function)`, called by path so the trait
+    /// need not be in scope, spanned at the attribute
+    Map(Span, TokenStream),
+    /// `function(element, arguments)`, for a generated helper taking the
+    /// element first, spanned at the attribute
+    Function(Span, TokenStream, TokenStream),
+}
+
+impl From<TokenStream> for Call {
+    fn from(suffix: TokenStream) -> Self {
+        Self::Method(suffix)
+    }
+}
+
+/// `receiver` with `calls` applied in order.
+pub fn chain(receiver: impl ToTokens, calls: &[Call]) -> TokenStream {
+    calls
+        .iter()
+        .fold(receiver.into_token_stream(), |element, call| match call {
+            Call::Method(suffix) => quote! { #element #suffix },
+            Call::Map(span, function) => {
+                let fluent_builder = Config::current().item("FluentBuilder");
+                quote_spanned! {*span=> #fluent_builder::map(#element, #function) }
+            }
+            Call::Function(span, function, arguments) => {
+                quote_spanned! {*span=> #function(#element, #arguments) }
+            }
+        })
+}
+
+/// `.method(arguments)`, spanned at `method`: errors on the call itself, like
+/// a missing argument, point at the attribute rather than at the macro.
+pub fn method_call(method: &Ident, arguments: impl ToTokens) -> TokenStream {
+    quote_spanned! {method.span()=> .#method(#arguments) }
+}
+
+/// `.key(value)`, splitting tuple values into separate arguments.
+pub fn key_value_call(key: &Ident, value: &Expr) -> TokenStream {
+    if let Expr::Tuple(tuple) = value {
+        method_call(key, &tuple.elems)
+    } else {
+        method_call(key, value)
+    }
+}
+
+/// Lower `apply: f` (or `apply: (f, g)`) into `FluentBuilder::map(.., f)`
+/// calls, passing the element through each transform in turn.
+fn apply_calls(key: &Ident, value: &Expr) -> Vec<Call> {
+    let transforms: Vec<_> = match value {
+        Expr::Tuple(tuple) => tuple.elems.iter().collect(),
+        value => vec![value],
+    };
+    transforms
+        .into_iter()
+        .map(|f| Call::Map(key.span(), f.to_token_stream()))
+        .collect()
+}
+
+/// Lower attributes into calls (`.flex()`, `.w(value)`, ...), expanding
+/// shorthands if `shorthands` is set, see [`generate_native_element`]
+pub fn attribute_calls(attributes: &[Attribute], shorthands: bool) -> Vec<Call> {
+    let mut calls = Vec::new();
+    for attr in attributes {
+        match attr {
+            Attribute::KeyValue { key, value } if key == "use" => calls.push(use_call(key, value)),
+            Attribute::KeyValue { key, value } if key == "apply" => {
+                calls.extend(apply_calls(key, value));
+            }
+            // Handled by the parent, see `append_children`
+            Attribute::KeyValue { key, .. } if key == "bind" => {}
+            Attribute::KeyValue { key, value } if key == "drag" => {
+                calls.extend(drag_calls(key, value));
+            }
+            Attribute::Compound { key, attributes } => {
+                calls.extend(expand_compound(key, attributes, shorthands));
+            }
+            attr => calls.push(Call::Method(method_suffix(attr, shorthands))),
+        }
+    }
+    calls
+}
+
+/// Lower an attribute into a method-call suffix.
+fn method_suffix(attr: &Attribute, shorthands: bool) -> TokenStream {
+    match attr {
+        Attribute::Flag(name) => method_call(name, quote! {}),
+        Attribute::KeyValue { key, value } if shorthands => {
+            expand_shorthand(key, value).unwrap_or_else(|| key_value_call(key, value))
+        }
+        // Props skip the shorthands, but split tuples like any attribute
+        Attribute::KeyValue { key, value }
+        | Attribute::Prop {
+            key,
+            value: Some(value),
+        } => key_value_call(key, value),
+        Attribute::PseudoState {
+            state,
+            group,
+            attributes,
+        } => {
+            let group = group.as_ref().map(|group| quote! { #group, });
+            // Mixed-site span keeps the closure parameter from shadowing user variables
+            let style = Ident::new("style", Span::mixed_site());
+            // The block refines a `StyleRefinement`, whatever the element
+            let calls = attribute_calls(attributes, true);
+            let method = if state == "base" {
+                Ident::new("map_base", state.span())
+            } else {
+                state.clone()
+            };
+            let body = chain(&style, &calls);
+            method_call(&method, quote! { #group |#style| #body })
+        }
+        Attribute::Prop { key, value: None } => method_call(key, quote! {}),
+        Attribute::Event {
+            event,
+            payload,
+            argument,
+            modifiers,
+            handler,
+        } => event_call(
+            event,
+            payload.as_deref(),
+            argument.as_ref(),
+            modifiers,
+            handler,
+        ),
+        Attribute::Platform {
+            platform,
+            attributes,
+        } => {
+            // Values are evaluated inside the closure, so only on the matching
+            // platform
+            let calls = attribute_calls(attributes, shorthands);
+            let cfg = platform_cfg(platform);
+            let element = Ident::new("element", Span::mixed_site());
+            let when = Ident::new("when", platform.span());
+            let body = chain(&element, &calls);
+            method_call(&when, quote! { cfg!(#cfg), |#element| #body })
+        }
+        Attribute::Compound { .. } => {
+            unreachable!("compound attributes are expanded by `attribute_calls`")
+        }
+    }
+}
+
+/// `cfg` predicate for a platform block key.
+fn platform_cfg(platform: &Ident) -> TokenStream {
+    if platform == "unix" {
+        quote! { unix }
+    } else {
+        let os = platform.to_string();
+        quote! { target_os = #os }
+    }
+}
+
+/// Runs of at least this many element children are added with a single
+/// `ParentElement::extend` call: nesting a `child` call per child is slow to
+/// type-check and buries errors in deep backtraces for wide trees.
+const EXTEND_THRESHOLD: usize = 4;
+
+/// A child to append: an element, or a child needing its own call.
+enum Appended<'a> {
+    Element(TokenStream),
+    Other(&'a Child),
+}
+
+fn append_children(
+    output: TokenStream,
+    children: &[Child],
+    parent_element: &TokenStream,
+) -> TokenStream {
+    let parent = Ident::new("__parent", Span::mixed_site());
+    let (statements, appended) = prepare_children(children);
+    if statements.is_empty() {
+        return append_all(output, appended, parent_element);
+    }
+    let chain = append_all(parent.to_token_stream(), appended, parent_element);
+    quote! {
+        {
+            let #parent = #output;
+            #(#statements)*
+            #chain
+        }
+    }
+}
+
+/// Statements building element children up front, if any, and the children
+/// to append.
+///
+/// With a `bind:` element, element children are built before any is added,
+/// in source order, so later siblings can borrow it before it is moved into
+/// the parent. Huge children are built the same way, see `split`.
+fn prepare_children(children: &[Child]) -> (Vec<TokenStream>, Vec<Appended<'_>>) {
+    let runs = group_text_runs(children);
+    let is_bound = |run: &TextRun| matches!(run, TextRun::Child(Child::Element(element)) if element.bound_name().is_some());
+    let split = split_runs(&runs);
+    let bound = runs.iter().any(is_bound) || split.contains(&true);
+
+    let into_element = Config::current().item("IntoElement");
+    let gpui = Config::current().gpui();
+    let mut statements = vec![];
+    let appended = runs
+        .into_iter()
+        .enumerate()
+        .map(|(index, run)| match run {
+            TextRun::Child(Child::Element(element)) if bound => {
+                let name = element.bound_name().cloned().unwrap_or_else(|| {
+                    format_ident!("__child_{}", index, span = Span::mixed_site())
+                });
+                let element = element_child(element);
+                statements.push(if split[index] {
+                    quote! { let #name: #gpui::AnyElement = #into_element::into_any_element(#element); }
+                } else {
+                    quote! { let #name = #element; }
+                });
+                Appended::Element(name.into_token_stream())
+            }
+            TextRun::Child(Child::Element(element)) => Appended::Element(element_child(element)),
+            TextRun::Child(child) => Appended::Other(child),
+            TextRun::Styled(children) => {
+                Appended::Element(styled_text(children.iter().filter_map(Child::element)))
+            }
+        })
+        .collect();
+    (statements, appended)
+}
+
+/// Append `appended` to the parent built by `output`.
+///
+/// The output is passed along by value, and method chain children extend it
+/// in place rather than copying it into a new `quote!`, so a long run of
+/// `.method()` children stays linear to expand.
+fn append_all(
+    output: TokenStream,
+    appended: Vec<Appended>,
+    parent_element: &TokenStream,
+) -> TokenStream {
+    let mut acc = output;
+    let mut elements = vec![];
+    for item in appended.into_iter().map(Some).chain([None]) {
+        if let Some(Appended::Element(element)) = item {
+            elements.push(element);
+            continue;
+        }
+        if elements.len() >= EXTEND_THRESHOLD {
+            acc = extend_children(&acc, &elements, parent_element);
+        } else {
+            for element in &elements {
+                let element = child_element(element);
+                acc = quote! { #parent_element::child(#acc, #element) };
+            }
+        }
+        elements.clear();
+        if let Some(Appended::Other(child)) = item {
+            acc = append_child(acc, child, parent_element);
+        }
+    }
+    acc
+}
+
+/// A statement appending a child to `parent` with `#![options(linear)]`.
+fn linear_step(parent: &Ident, item: &Appended, parent_element: &TokenStream) -> TokenStream {
+    match item {
+        Appended::Element(element) => {
+            let element = child_element(element);
+            quote! { let #parent = #parent_element::child(#parent, #element); }
+        }
+        Appended::Other(Child::Cfg { attrs, child }) => {
+            let step = linear_step(parent, &Appended::Other(child), parent_element);
+            quote! { #(#attrs)* #step }
+        }
+        Appended::Other(Child::Element(element)) => linear_step(
+            parent,
+            &Appended::Element(element_child(element)),
+            parent_element,
+        ),
+        Appended::Other(child) => {
+            let appended = append_child(parent.to_token_stream(), child, parent_element);
+            quote! { let #parent = #appended; }
+        }
+    }
+}
+
+/// The tokens building an element child, hoisted into a function if it is
+/// static. Expression children are asserted to be elements with
+/// `#![options(checked)]`.
+fn element_child(element: &Element) -> TokenStream {
+    match element {
+        Element::Expression(el)
+            if current_options().checked
+                && el.attributes.is_empty()
+                && el.children.is_empty()
+                && !matches!(el.expr, Expr::Lit(_)) =>
+        {
+            child_check(&el.expr)
+        }
+        _ => hoisted(element).unwrap_or_else(|| element.to_token_stream()),
+    }
+}
+
+/// An element child, converted into an `AnyElement` with
+/// `#![options(boxed_children)]`.
+fn child_element(element: &TokenStream) -> TokenStream {
+    if !current_options().boxed_children {
+        return element.clone();
+    }
+    let into_element = Config::current().item("IntoElement");
+    quote! { #into_element::into_any_element(#element) }
+}
+
+fn extend_children(
+    acc: &TokenStream,
+    elements: &[TokenStream],
+    parent_element: &TokenStream,
+) -> TokenStream {
+    let into_element = Config::current().item("IntoElement");
+    let parent = Ident::new("__parent", Span::mixed_site());
+    quote! {
+        {
+            let mut #parent = #acc;
+            #parent_element::extend(
+                &mut #parent,
+                [#(#into_element::into_any_element(#elements)),*],
+            );
+            #parent
+        }
+    }
+}
+
+/// The elements of a spread child `..expr`, asserted where it is written to
+/// iterate over elements: a wrong item type is otherwise reported at the
+/// `children` call of the generated code. The items are passed on unchanged.
+pub fn spread_children(expr: &Expr) -> TokenStream {
+    let into_element = Config::current().item("IntoElement");
+    let helper = Ident::new("__assert_children", Span::mixed_site());
+    let call = Ident::new(
+        "__assert_children",
+        Span::mixed_site().located_at(expr.span()),
+    );
+    quote! {
+        ({
+            fn #helper<I: ::std::iter::IntoIterator>(children: I) -> I
+            where
+                I::Item: #into_element,
+            {
+                children
+            }
+            #call
+        })(#expr)
+    }
+}
+
+fn append_child(mut acc: TokenStream, child: &Child, parent_element: &TokenStream) -> TokenStream {
+    match child {
+        Child::Element(element) => {
+            let element = child_element(&element_child(element));
+            quote! { #parent_element::child(#acc, #element) }
+        }
+        Child::Spread(expr) => {
+            let children = spread_children(expr);
+            quote! { #parent_element::children(#acc, #children) }
+        }
+        Child::MethodChain(tokens) => {
+            acc.extend(quote! { .#tokens });
+            acc
+        }
+        Child::Cfg { attrs, child } => {
+            // Rebind the parent so the gated statement can be compiled out
+            let parent = Ident::new("__parent", Span::mixed_site());
+            let gated = append_child(parent.to_token_stream(), child, parent_element);
+            quote! {
+                {
+                    let #parent = #acc;
+                    #(#attrs)*
+                    let #parent = #gated;
+                    #parent
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use insta::assert_snapshot;
+
+    use super::*;
+    use crate::ast::{Markup, StyleMixin};
+
+    fn generate(input: proc_macro2::TokenStream) -> String {
+        let markup: Markup = syn::parse2(input).unwrap();
+        let output = quote! { fn __wrapper() { #markup } };
+        let syntax_tree = syn::parse_file(&output.to_string()).unwrap();
+        prettyplease::unparse(&syntax_tree)
+    }
+
+    #[test]
+    fn test_simple_div() {
+        assert_snapshot!(generate(quote::quote! { div {} }));
+    }
+
+    #[test]
+    fn test_div_with_flag_attribute() {
+        assert_snapshot!(generate(quote::quote! { div @[flex] {} }));
+    }
+
+    #[test]
+    fn test_div_with_multiple_flags() {
+        assert_snapshot!(generate(quote::quote! { div @[flex, flex_col] {} }));
+    }
+
+    #[test]
+    fn test_div_with_key_value_attribute() {
+        assert_snapshot!(generate(quote::quote! { div @[w: px(200.0)] {} }));
+    }
+
+    #[test]
+    fn test_div_with_mixed_attributes() {
+        assert_snapshot!(generate(
+            quote::quote! { div @[flex, w: px(200.0), bg: theme.secondary] {} }
+        ));
+    }
+
+    #[test]
+    fn test_div_with_single_child() {
+        assert_snapshot!(generate(quote::quote! { div { "Hello" } }));
+    }
+
+    #[test]
+    fn test_div_with_multiple_children() {
+        assert_snapshot!(generate(quote::quote! {
+            div {
+                "First",
+                "Second",
+            }
+        }));
+    }
+
+    #[test]
+    fn test_div_with_element_children() {
+        assert_snapshot!(generate(quote::quote! {
+            div {
+                div { "First" },
+                div { "Second" },
+            }
+        }));
+    }
+
+    #[test]
+    fn test_expression_element() {
+        assert_snapshot!(generate(quote::quote! { Container::new(title) {} }));
+    }
+
+    #[test]
+    fn test_expression_element_with_attributes() {
+        assert_snapshot!(generate(quote::quote! { Container::new(title) @[flex] {} }));
+    }
+
+    #[test]
+    fn test_expression_element_with_children() {
+        assert_snapshot!(generate(
+            quote::quote! { Container::new(title) { "Content" } }
+        ));
+    }
+
+    #[test]
+    fn test_nested_elements() {
+        assert_snapshot!(generate(quote::quote! {
+            div @[flex] {
+                div { "Inner" },
+            }
+        }));
+    }
+
+    #[test]
+    fn test_multi_value_attribute() {
+        assert_snapshot!(generate(
+            quote::quote! { div @[when: (is_visible, |d| d.flex())] {} }
+        ));
+    }
+
+    #[test]
+    fn test_complex_nested() {
+        assert_snapshot!(generate(quote::quote! {
+            div @[flex, flex_col] {
+                div @[text_size: px(16.0)] {
+                    "Hello World",
+                },
+                div @[bg: theme.secondary] {
+                    (Header::new()),
+                },
+            }
+        }));
+    }
+
+    #[test]
+    fn test_pseudo_states() {
+        assert_snapshot!(generate(quote::quote! {
+            div @[bg: white()] @hover[bg: red(), cursor_pointer] @active[opacity: 0.5] {}
+        }));
+    }
+
+    #[test]
+    fn test_base_attributes() {
+        assert_snapshot!(generate(quote::quote! {
+            Button @[:label: "Save", flex] @base[mt: 2, w: px(120.0)] {}
+        }));
+    }
+
+    #[test]
+    fn test_markup_vec() {
+        let markup: MarkupVec = syn::parse2(quote::quote! {
+            div @[flex] { "a" },
+            "Label",
+            ..items,
+        })
+        .unwrap();
+        let output = quote! { fn __wrapper() { #markup } };
+        let syntax_tree = syn::parse_file(&output.to_string()).unwrap();
+        assert_snapshot!(prettyplease::unparse(&syntax_tree));
+    }
+
+    #[test]
+    fn test_style_mixins() {
+        let mixin: StyleMixin =
+            syn::parse2(quote::quote! { rounded_md, border_1, p: px(12.0) }).unwrap();
+        let output = quote! { fn __wrapper() { let card = #mixin; } };
+        let syntax_tree = syn::parse_file(&output.to_string()).unwrap();
+        assert_snapshot!("style_mixin", prettyplease::unparse(&syntax_tree));
+
+        assert_snapshot!(generate(quote::quote! {
+            div @[flex, use: card, use: (elevated, theme.panel())] {}
+        }));
+    }
+
+    #[test]
+    fn test_bind() {
+        assert_snapshot!(generate(quote::quote! {
+            div {
+                "Title",
+                div @[bind: header, flex] { "Header" },
+                Label::new(header_height(&header)) {},
+                .when(compact, |el| el.p_1()),
+            }
+        }));
+    }
+
+    #[test]
+    fn test_style_attribute() {
+        assert_snapshot!(generate(quote::quote! {
+            div @[style: settings.panel_style(), flex] {}
+        }));
+    }
+
+    #[test]
+    fn test_apply() {
+        assert_snapshot!(generate(quote::quote! {
+            div @[apply: with_extras, flex, apply: (rounded, |el| el.mt_2())] {}
+        }));
+    }
+
+    #[test]
+    fn test_group_hover() {
+        assert_snapshot!(generate(quote::quote! {
+            div @[group: "row"] {
+                div @group_hover("row")[bg: red(), visible] {},
+            }
+        }));
+    }
+
+    #[test]
+    fn test_platform_attributes() {
+        assert_snapshot!(generate(quote::quote! {
+            div @[flex, macos: { pt: px(28.0), h: titlebar_height() }, windows: { pt: px(8.0) }] {}
+        }));
+    }
+
+    #[test]
+    fn test_cfg_children() {
+        assert_snapshot!(generate(quote::quote! {
+            div {
+                "Always",
+                #[cfg(debug_assertions)]
+                div { "Debug overlay" },
+                #[cfg(feature = "extras")]
+                #[cfg(not(test))]
+                ..extras,
+            }
+        }));
+    }
+
+    #[test]
+    fn test_unit_literals() {
+        assert_snapshot!(generate(quote::quote! {
+            div @[w: 200px, h: 50%, text_size: 1.5rem] @hover[mt: -4px] {}
+        }));
+    }
+
+    #[test]
+    fn test_size_shorthand() {
+        assert_snapshot!(generate(quote::quote! {
+            div @[size: 24px] {
+                div @[size: (px(200.0), height())] {},
+                div @[size: icon_size()] {},
+            }
+        }));
+    }
+
+    #[test]
+    fn test_font_shorthand() {
+        assert_snapshot!(generate(quote::quote! {
+            div @[font: ("Zed Mono", 13px, FontWeight::MEDIUM)] {
+                div @[font: (family(),)] {},
+                div @[font: (_, 12px)] {},
+                div @[font: theme.font()] {},
+            }
+        }));
+    }
+
+    #[test]
+    fn test_events() {
+        assert_snapshot!(generate(quote::quote! {
+            div @[
+                id: "button",
+                on:click: |event, window, cx| clicked(event, window, cx),
+                on:mouse_down: handler,
+                on:scroll_wheel: cx.listener(Self::scroll),
+            ] {}
+        }));
+    }
+
+    #[test]
+    fn test_event_modifiers() {
+        assert_snapshot!(generate(quote::quote! {
+            div @[
+                id: "row",
+                on:click.double: |_, _, cx| rename(cx),
+                on:click.right: open_menu,
+                on:mouse_down.middle: close,
+                on:mouse_up.left.double: select_word(),
+            ] {}
+        }));
+    }
+
+    #[test]
+    fn test_capture_modifier() {
+        assert_snapshot!(generate(quote::quote! {
+            div @[
+                on:mouse_down.capture: dismiss,
+                on:mouse_up.capture.right: |_, _, _| {},
+                on:key_down.capture: |event, _, cx| intercept(event, cx),
+            ] {}
+        }));
+    }
+
+    #[test]
+    fn test_key_bindings() {
+        assert_snapshot!(generate(quote::quote! {
+            div @[
+                key_context: "Editor",
+                on:key("cmd-s"): save,
+                on:key("escape").capture: |_, window, cx| dismiss(window, cx),
+            ] {}
+        }));
+    }
+
+    #[test]
+    fn test_actions() {
+        assert_snapshot!(generate(quote::quote! {
+            div @[on:action(SaveFile): save, on:action(editor::Cancel).capture: cancel] {
+                div @[id: "save", action: SaveFile] {},
+            }
+        }));
+    }
+
+    #[test]
+    fn test_drag_and_drop() {
+        assert_snapshot!(generate(quote::quote! {
+            div @[
+                id: "tab",
+                drag: (tab.clone(), |tab| tab_preview(tab)),
+                on:drop::<Tab>: move_tab,
+                drag_over::<Tab>: |style, _, _, _| style.bg(blue()),
+            ] {}
+        }));
+    }
+
+    #[test]
+    fn test_focus() {
+        assert_snapshot!(generate(quote::quote! {
+            div @[focus: self.focus_handle, focus_trap] {
+                Button @[focus: handle] {},
+            }
+        }));
+    }
+
+    #[test]
+    fn test_component_arguments() {
+        assert_snapshot!(generate(quote::quote! {
+            div {
+                Button("Save") @[disabled: busy] {},
+                Tab(index, label.clone()) { "x" },
+            }
+        }));
+    }
+
+    #[test]
+    fn test_path_components() {
+        assert_snapshot!(generate(quote::quote! {
+            ui::Stack {
+                ui::button::Button("Save") @[disabled: busy] {},
+                ::widgets::Footer {},
+            }
+        }));
+    }
+
+    #[test]
+    fn test_generic_components() {
+        assert_snapshot!(generate(quote::quote! {
+            List::<FileEntry> {
+                picker::Picker::<FileFinderDelegate>(delegate) {},
+            }
+        }));
+    }
+
+    #[test]
+    fn test_component_props() {
+        assert_snapshot!(generate(quote::quote! {
+            Button @[:label: "Save", :size: (width, height), :disabled, size: 4, flex] {}
+        }));
+    }
+
+    #[test]
+    fn test_component_shorthands() {
+        assert_snapshot!(generate(quote::quote! {
+            div {
+                Icon @[size: IconSize::Small] @hover[size: px(20.0)] {},
+                (avatar) @[size: px(24.0)] {},
+            }
+        }));
+    }
+
+    #[test]
+    fn test_component_scroll_and_font() {
+        assert_snapshot!(generate(quote::quote! {
+            div {
+                ScrollArea @[scroll: handle] {},
+                Label @[font: (family, FontWeight::BOLD)] {},
+            }
+        }));
+    }
+
+    #[test]
+    fn test_listener_header() {
+        assert_snapshot!(generate(quote::quote! {
+            #![listener(cx)]
+            div @[id: "save", on:click: self.save, on:hover: |_, _, _| {}] {
+                Button @[on:click: self.cancel] {},
+            }
+        }));
+    }
+
+    #[test]
+    fn test_theme_tokens() {
+        assert_snapshot!(generate(quote::quote! {
+            div @[bg: $surface, border: (1, $border)] @hover[text_color: $accent] {}
+        }));
+    }
+
+    #[test]
+    fn test_edge_shorthands() {
+        assert_snapshot!(generate(quote::quote! {
+            div @[p: 8px, m: (4px, 8px)] {
+                div @[p: (1px, 2px, 3px), m: (top(), 0px, 4px, 8px)] {},
+            }
+        }));
+    }
+
+    #[test]
+    fn test_border_shorthand() {
+        assert_snapshot!(generate(quote::quote! {
+            div @[border: (1., theme.border), border_t: (2, red())] {
+                div @[border_b: (width, theme.border)] {},
+                div @[border: px(1.0)] {},
+            }
+        }));
+    }
+
+    #[test]
+    fn test_spacing_scale() {
+        assert_snapshot!(generate(quote::quote! {
+            div @[gap: 2, p: 1.5, m: (0, 4), px: spacing()] {}
+        }));
+    }
+
+    #[test]
+    fn test_grid_shorthand() {
+        assert_snapshot!(generate(quote::quote! {
+            div @[grid: (cols: 3, gap: 2, rows: count())] {
+                div @[grid_cols: 2] {},
+            }
+        }));
+    }
+
+    #[test]
+    fn test_position_shorthand() {
+        assert_snapshot!(generate(quote::quote! {
+            div @[relative: (size: 8px)] {
+                div @[absolute: (top: 0, right: offset()), inset: 2] {},
+            }
+        }));
+    }
+
+    #[test]
+    fn test_attribute_aliases() {
+        assert_snapshot!(generate(quote::quote! {
+            div @[width: 200px, padding: (1, 2), color: white()] @hover[background: black()] {
+                Icon @[color: Color::Muted] {},
+            }
+        }));
+    }
+
+    #[test]
+    fn test_text_children() {
+        assert_snapshot!(generate(quote::quote! {
+            div {
+                "Hello, {name}!", "{{literal}}", "{}", 42, text: count + 1, text(total),
+                "Joined " "with {name}",
+                r"
+                    Dedented
+                      copy
+                ",
+            }
+        }));
+    }
+
+    #[test]
+    fn test_escaped_braces() {
+        assert_snapshot!(generate(quote::quote! {
+            div { "{{x}}", "{{x}} is {name}" }
+        }));
+    }
+
+    #[test]
+    fn test_span() {
+        assert_snapshot!(generate(quote::quote! {
+            div {
+                "Press ",
+                span @[font_weight: FontWeight::BOLD, italic] { "Enter" },
+                " to {action}",
+                div {},
+                span @[color: red()] { text: count },
+            }
+        }));
+    }
+
+    #[test]
+    fn test_rich_text() {
+        assert_snapshot!(generate(quote::quote! {
+            rich_text @[id: "log", tooltip: tooltip] {
+                "See ",
+                span @[color: blue(), on_click: |_, cx| cx.open_url(url)] { "the docs" },
+                " or ",
+                span @[underline, on_click: retry] { "retry" },
+            }
+        }));
+    }
+
+    #[test]
+    fn test_link() {
+        assert_snapshot!(generate(quote::quote! {
+            div {
+                link("Documentation", "https://docs.rs/gpui") @[text_color: blue()] {},
+                link("Issue #{number}", issue_url(number)),
+            }
+        }));
+    }
+
+    #[cfg(feature = "markdown")]
+    #[test]
+    fn test_markdown() {
+        assert_snapshot!(generate(quote::quote! {
+            div {
+                markdown: "
+                    # Title
+
+                    Some **bold** text.
+
+                    - one
+                ",
+            }
+        }));
+    }
+
+    #[cfg(feature = "markdown")]
+    #[test]
+    fn test_dynamic_markdown() {
+        assert_snapshot!(generate(quote::quote! {
+            div {
+                markdown: self.changelog,
+            }
+        }));
+    }
+
+    #[test]
+    fn test_inline_attribute_values() {
+        assert_snapshot!(generate(quote::quote! {
+            Header @[
+                w: next(),
+                opacity: Default::default(),
+                when: (next() > 1, |d| d.flex()),
+                on_click: cx.listener(|this, _, _, _| this.clicked()),
+            ] @hover[bg: next()] {
+                div @[h: next()] {},
+            }
+        }));
+    }
+
+    #[test]
+    fn test_svg() {
+        assert_snapshot!(generate(
+            quote::quote! { svg @[path: icon_path, size: px(24.0)] {} }
+        ));
+    }
+
+    #[test]
+    fn test_anchored() {
+        assert_snapshot!(generate(quote::quote! {
+            anchored @[position: Point::default()] {
+                div { "Tooltip" },
+            }
+        }));
+    }
+
+    #[test]
+    fn test_surface() {
+        assert_snapshot!(generate(quote::quote! {
+            surface @[size_full, object_fit: ObjectFit::Cover] { self.frame.clone() }
+        }));
+    }
+
+    #[test]
+    fn test_canvas() {
+        assert_snapshot!(generate(quote::quote! {
+            canvas @[size_full] {
+                prepaint: |bounds, _window, _cx| bounds.size,
+                paint: |bounds, size, window, _cx| window.paint_quad(fill(bounds, red())),
+            }
+        }));
+    }
+
+    #[test]
+    fn test_list() {
+        assert_snapshot!(generate(quote::quote! {
+            list(self.list_state) @[size_full, sizing_behavior: ListSizingBehavior::Infer] {
+                |ix, _window, _cx| ui! { div { "Item {ix}" } }
+            }
+        }));
+        assert_snapshot!(generate(quote::quote! {
+            list @[state: self.list_state, flex_grow] { render_message }
+        }));
+    }
+
+    #[test]
+    fn test_uniform_list() {
+        assert_snapshot!(generate(quote::quote! {
+            uniform_list("files", self.files.len()) @[size_full, track_scroll: handle]
+                |range, _window, _cx| {
+                    ..files[range].iter().map(|file| ui! { div { file.name.clone() } }),
+                }
+        }));
+    }
+
+    #[test]
+    fn test_overlay() {
+        assert_snapshot!(generate(quote::quote! {
+            overlay @[position: point, anchor: Corner::TopRight, fit_mode: snap_to_window] {
+                div { "Menu" },
+            }
+        }));
+        assert_snapshot!(generate(quote::quote! {
+            anchored @[fit_mode: AnchoredFitMode::SnapToWindowWithMargin(px(8.0))] {}
+        }));
+    }
+
+    #[test]
+    fn test_empty() {
+        assert_snapshot!(generate(quote::quote! {
+            div {
+                (if loading { spinner().into_any_element() } else { ui! { empty {} }.into_any_element() }),
+                empty {},
+            }
+        }));
+    }
+
+    #[test]
+    fn test_stateful_div() {
+        assert_snapshot!(generate(quote::quote! {
+            div {
+                div("toolbar") @[flex, on_click: handler] { "Tools" },
+                div id(("row", ix)) @[hover: bg(red)] {},
+            }
+        }));
+    }
+
+    #[test]
+    fn test_keyed_ids() {
+        assert_snapshot!(generate(quote::quote! {
+            div {
+                div @[key: row.id, flex, on:click: handler] {},
+                div @[apply: |d| d.id("row"), on:click: handler] {},
+                div @[macos: { id: "row" }, on:click: handler] {},
+            }
+        }));
+    }
+
+    #[test]
+    #[cfg(not(any(feature = "gpui-component", feature = "zed-ui")))]
+    fn test_flex_elements() {
+        assert_snapshot!(generate(quote::quote! {
+            v_flex @[gap: px(8.0)] {
+                h_flex @[items_center] { "Title" },
+                h_flex @[id: "actions", on:click: handler] {},
+            }
+        }));
+    }
+
+    #[test]
+    #[cfg(not(any(feature = "gpui-component", feature = "zed-ui")))]
+    fn test_layout_elements() {
+        assert_snapshot!(generate(quote::quote! {
+            v_flex {
+                stack @[size_full] {
+                    img(src),
+                    div @[bottom_0] { "Caption" },
+                    ..badges,
+                },
+                divider {},
+                h_flex { "Left", spacer {}, divider @[vertical, my: 1] {}, "Right" },
+            }
+        }));
+    }
+
+    #[test]
+    fn test_portal() {
+        assert_snapshot!(generate(quote::quote! {
+            div {
+                portal(window) @[priority: 10, flex, items_center, justify_center, bg: overlay] {
+                    modal(title),
+                },
+                portal(_window) { toast(message) },
+            }
+        }));
+    }
+
+    #[test]
+    fn test_custom_elements() {
+        assert_snapshot!(generate(quote::quote! {
+            #![elements(icon = my_ui::icon -> my_ui::Icon, row = my_ui::h_flex -> gpui::Div)]
+            row @[gap_2, width: px(200.0)] {
+                icon @[size: px(16.0)] {},
+                "Saved",
+            }
+        }));
+    }
+
+    #[test]
+    #[cfg(feature = "gpui-component")]
+    fn test_gpui_component_preset() {
+        assert_snapshot!(generate(quote::quote! {
+            v_flex @[gap_2] {
+                h_flex { Button("save") @[:label: "Save", :primary, :small] {} },
+            }
+        }));
+    }
+
+    #[test]
+    #[cfg(feature = "zed-ui")]
+    fn test_zed_ui_preset() {
+        assert_snapshot!(generate(quote::quote! {
+            IconButton("close", IconName::Close) @[:icon_size: IconSize::Small, :on_click: close, mt_1] {}
+        }));
+    }
+
+    #[test]
+    fn test_extend_children() {
+        assert_snapshot!(generate(quote::quote! {
+            div {
+                "Title", header, div { "Body" }, Footer {},
+                ..rows,
+                "Total", total,
+            }
+        }));
+    }
+
+    #[test]
+    fn test_static_styled_text() {
+        assert_snapshot!(generate(quote::quote! {
+            div { "Press ", span @[font_weight: FontWeight::BOLD] { "Enter" }, " to continue" }
+        }));
+    }
+
+    #[test]
+    fn test_constant_conditions() {
+        assert_snapshot!(generate(quote::quote! {
+            div @[
+                when: (true, |this| this.flex()),
+                when: (false, |this| this.hidden()),
+                when_else: (false, |this| this.p_1(), |this| this.p_2()),
+                when: (compact, |this| this.gap_1()),
+            ] {}
+        }));
+    }
+
+    #[test]
+    fn test_static_subtree() {
+        assert_snapshot!(generate(quote::quote! {
+            div {
+                div @[flex, gap: 2, p: 16px] {
+                    div @[text_color: gpui::rgb(0x888888)] { "No results" },
+                    "Try another search",
+                },
+                div @[flex] { "Query:", query, "Results", "Total" },
+            }
+        }));
+    }
+
+    #[test]
+    fn test_linear() {
+        assert_snapshot!(generate(quote::quote! {
+            #![options(linear)]
+            div @[flex, w: width] {
+                "Title",
+                Body {},
+                #[cfg(debug_assertions)]
+                div { "Debug" },
+                ..rows,
+            }
+        }));
+    }
+
+    #[test]
+    fn test_boxed_children() {
+        assert_snapshot!(generate(quote::quote! {
+            #![options(boxed_children)]
+            div {
+                "Title",
+                Body {},
+                #[cfg(debug_assertions)]
+                div { "Debug" },
+            }
+        }));
+    }
+
+    #[test]
+    #[cfg(not(feature = "zed-ui"))]
+    fn test_tooltips() {
+        assert_snapshot!(generate(quote::quote! {
+            div("copy") @[tooltip: "Copy path"] {
+                div("save") @[tooltip: ui! { div @[p_2] { "Save", kbd } }] {},
+                div("custom") @[tooltip: build_tooltip] {},
+            }
+        }));
+    }
+
+    #[test]
+    fn test_memo() {
+        assert_snapshot!(generate(quote::quote! {
+            div {
+                memo(version) @[h: px(320.)] {
+                    Chart(points.clone()) {}
+                },
+                memo(row.version, ("row", row.id)) {
+                    div { (row.label.clone()) }
+                },
+            }
+        }));
+    }
+
+    #[test]
+    fn test_lazy() {
+        assert_snapshot!(generate(quote::quote! {
+            div {
+                lazy { Details(item.clone()) {} },
+            }
+        }));
+    }
+
+    #[test]
+    fn test_checked() {
+        assert_snapshot!(generate(quote::quote! {
+            #![options(checked)]
+            div @[flex] {
+                Badge::new("3") @[size: Size::Small, bg: red(), p_1] {},
+                (label) @[text_color: muted, apply: f, rounded_md] {},
+            }
+        }));
+    }
+
+    #[test]
+    fn test_checked_children() {
+        assert_snapshot!(generate(quote::quote! {
+            #![options(checked)]
+            div {
+                "Title",
+                label,
+                div { count },
+            }
+        }));
+    }
+
+    #[test]
+    fn test_warned_lints() {
+        assert_snapshot!(generate(quote::quote! {
+            #![warn(conflicting_attribute, unknown_attribute)]
+            div @[flex, block, widht: px(10.0)] {}
+        }));
+    }
+
+    #[test]
+    fn test_keyword_lint() {
+        assert_snapshot!(generate(quote::quote! {
+            div @[rounded: m, shadow: s] {}
+        }));
+    }
+
+    #[test]
+    fn test_deferred() {
+        assert_snapshot!(generate(quote::quote! {
+            deferred {
+                div { "Deferred content" },
+            }
+        }));
+    }
+
+    #[test]
+    fn test_deferred_priority() {
+        assert_snapshot!(generate(quote::quote! {
+            deferred @[priority: 10, when: (pinned, |this| this.priority(20))] {
+                div { "Deferred content" },
+            }
+        }));
+    }
+
+    #[test]
+    fn test_spread_children() {
+        assert_snapshot!(generate(quote::quote! {
+            div {
+                ..items,
+            }
+        }));
+    }
+
+    #[test]
+    fn test_spread_with_siblings() {
+        assert_snapshot!(generate(quote::quote! {
+            div {
+                "Header",
+                ..items,
+                "Footer",
+            }
+        }));
+    }
+
+    #[test]
+    fn test_method_call() {
+        assert_snapshot!(generate(quote::quote! {
+            div {
+                "static",
+                .when(cond, |d| d.child("dynamic")),
+            }
+        }));
+    }
+
+    #[test]
+    fn test_method_call_no_args() {
+        assert_snapshot!(generate(quote::quote! {
+            div {
+                .flex(),
+            }
+        }));
+    }
+
+    #[test]
+    fn test_method_chain() {
+        assert_snapshot!(generate(quote::quote! {
+            div {
+                .flex().flex_col().gap_2(),
+            }
+        }));
+    }
+
+    #[test]
+    fn test_method_with_generics() {
+        assert_snapshot!(generate(quote::quote! {
+            div {
+                .map::<Div, _>(|d| d),
+            }
+        }));
+    }
+
+    #[test]
+    fn test_component() {
+        assert_snapshot!(generate(quote::quote! { Header {} }));
+    }
+
+    #[test]
+    fn test_component_with_attrs() {
+        assert_snapshot!(generate(
+            quote::quote! { Header @[flex, style: Primary] {} }
+        ));
+    }
+
+    #[test]
+    fn test_component_with_children() {
+        assert_snapshot!(generate(quote::quote! {
+            Container {
+                "Content",
+            }
+        }));
+    }
+
+    #[test]
+    fn test_component_nested() {
+        assert_snapshot!(generate(quote::quote! {
+            div {
+                Header @[flex] {},
+                Footer {},
+            }
+        }));
+    }
+}
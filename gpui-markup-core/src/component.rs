@@ -0,0 +1,204 @@
+//! `#[component]`: write a component as a function.
+//!
+//! The function becomes a `RenderOnce` struct of the same name in
+//! `PascalCase`, with one field per parameter and the builder of
+//! `#[derive(Props)]`:
+//!
+//! ```ignore
+//! #[component]
+//! fn badge(label: SharedString, #[prop(default = 1)] count: usize) -> impl IntoElement {
+//!     ui! { div { label, count.to_string() } }
+//! }
+//!
+//! ui! { Badge @[:label: "Inbox", :count: 3] {} }
+//! ```
+//!
+//! Parameters named `window` and `cx` taking a reference are bound to the
+//! arguments of `render` rather than becoming props. Only the doc comments and
+//! `#[cfg(...)]` attributes of the function and its parameters are kept on
+//! the struct and its fields.
+
+use proc_macro2::{Span, TokenStream};
+use quote::quote;
+use syn::spanned::Spanned;
+use syn::{Error, FnArg, Ident, ItemFn, Pat, Result, ReturnType, Type};
+
+use crate::config::Config;
+use crate::props::{Prop, builder};
+
+/// Render arguments a parameter may bind instead of becoming a prop.
+const RENDER_ARGUMENTS: &[&str] = &["window", "cx"];
+
+/// The prop of a function parameter, or `None` for a render argument.
+fn parameter_prop(arg: &FnArg) -> Result<Option<Prop>> {
+    let FnArg::Typed(arg) = arg else {
+        return Err(Error::new(arg.span(), "components cannot take `self`"));
+    };
+    let Pat::Ident(pat) = &*arg.pat else {
+        return Err(Error::new(
+            arg.pat.span(),
+            "expected a parameter name, patterns are not supported in components",
+        ));
+    };
+
+    if let Type::Reference(_) = &*arg.ty {
+        if RENDER_ARGUMENTS.iter().any(|name| pat.ident == name) {
+            return Ok(None);
+        }
+        return Err(Error::new(
+            arg.ty.span(),
+            "component props must be owned, only `window` and `cx` can be references",
+        ));
+    }
+
+    Prop::new(pat.ident.clone(), (*arg.ty).clone(), &arg.attrs).map(Some)
+}
+
+/// Convert a function name to the struct name: `user_card` -> `UserCard`.
+fn pascal_case(ident: &Ident) -> Ident {
+    let name = ident.to_string();
+    let name: String = name
+        .trim_start_matches("r#")
+        .split('_')
+        .map(|word| {
+            let mut chars = word.chars();
+            chars.next().map_or_else(String::new, |first| {
+                first.to_uppercase().chain(chars).collect()
+            })
+        })
+        .collect();
+    Ident::new(&name, ident.span())
+}
+
+/// Expand `#[component]` on `item` into a struct, its constructor and
+/// builder methods, and `IntoElement` and `RenderOnce` implementations
+/// running the body.
+///
+/// # Errors
+///
+/// Returns an error if `args` is not empty, or `item` cannot be turned into a
+/// component (`async` or `self` functions, pattern or borrowed parameters,
+/// malformed `#[prop(...)]` attributes, no return type).
+pub fn expand_component(args: &TokenStream, item: &ItemFn) -> Result<TokenStream> {
+    if !args.is_empty() {
+        return Err(Error::new(args.span(), "`#[component]` takes no arguments"));
+    }
+    let sig = &item.sig;
+    if let Some(asyncness) = &sig.asyncness {
+        return Err(Error::new(asyncness.span(), "components cannot be `async`"));
+    }
+    let ReturnType::Type(_, output) = &sig.output else {
+        return Err(Error::new(
+            sig.paren_token.span.close(),
+            "components must return an element, e.g. `-> impl IntoElement`",
+        ));
+    };
+
+    let props = sig
+        .inputs
+        .iter()
+        .map(parameter_prop)
+        .filter_map(Result::transpose)
+        .collect::<Result<Vec<_>>>()?;
+
+    let gpui = Config::current().gpui();
+    let into_element = Config::current().item("IntoElement");
+    let attrs = item
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("doc") || attr.path().is_ident("cfg"));
+    let cfgs: Vec<_> = item
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("cfg"))
+        .collect();
+    let vis = &item.vis;
+    let name = pascal_case(&sig.ident);
+    let generics = &sig.generics;
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let body = &item.block;
+
+    let fields = props.iter().map(
+        |Prop {
+             name, ty, attrs, ..
+         }| {
+            quote! { #(#attrs)* #name: #ty }
+        },
+    );
+    let builder = builder(&name, generics, vis, &props);
+    let names = props.iter().map(|prop| {
+        let (name, cfgs) = (&prop.name, prop.cfgs());
+        quote! { #(#cfgs)* #name }
+    });
+    let window = Ident::new("window", Span::call_site());
+    let cx = Ident::new("cx", Span::call_site());
+
+    Ok(quote! {
+        #(#attrs)*
+        #vis struct #name #generics #where_clause {
+            #(#fields,)*
+        }
+
+        #(#cfgs)*
+        #builder
+
+        #(#cfgs)*
+        impl #impl_generics #into_element for #name #ty_generics #where_clause {
+            type Element = #gpui::Component<Self>;
+
+            fn into_element(self) -> Self::Element {
+                #gpui::Component::new(self)
+            }
+        }
+
+        #(#cfgs)*
+        impl #impl_generics #gpui::RenderOnce for #name #ty_generics #where_clause {
+            #[allow(unused_variables)]
+            fn render(
+                self,
+                #window: &mut #gpui::Window,
+                #cx: &mut #gpui::App,
+            ) -> #output {
+                let Self { #(#names,)* } = self;
+                #body
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use syn::parse_quote;
+
+    use super::*;
+
+    fn expand(item: &ItemFn) -> Result<String> {
+        expand_component(&TokenStream::new(), item).map(|output| output.to_string())
+    }
+
+    #[test]
+    fn test_pascal_case() {
+        let ident: Ident = parse_quote!(user_card);
+        assert_eq!(pascal_case(&ident), "UserCard");
+    }
+
+    #[test]
+    fn test_borrowed_prop() {
+        let item: ItemFn = parse_quote! {
+            fn label(text: &str) -> impl IntoElement {
+                div()
+            }
+        };
+        assert!(expand(&item).is_err());
+    }
+
+    #[test]
+    fn test_unknown_prop_option() {
+        let item: ItemFn = parse_quote! {
+            fn label(#[prop(optional)] text: SharedString) -> impl IntoElement {
+                div()
+            }
+        };
+        assert!(expand(&item).is_err());
+    }
+}
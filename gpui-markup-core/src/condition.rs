@@ -0,0 +1,107 @@
+//! Folding of `when:` and `when_else:` attributes whose condition is a
+//! literal `true` or `false`, as produced by macros and generated markup.
+//!
+//! A constant condition picks its branch during expansion: the taken
+//! transform is applied like `apply: f`, and `when: (false, f)` is dropped,
+//! so no runtime `when` call or dead closure ends up in the binary.
+
+use syn::{Expr, ExprLit, Ident, Lit};
+
+use crate::ast::Attribute;
+
+/// Replace `when` and `when_else` attributes with a literal condition by the
+/// branch they take.
+pub fn fold_constant_conditions(attributes: &mut Vec<Attribute>) {
+    attributes.retain_mut(|attr| match attr {
+        Attribute::KeyValue { key, value } if key == "when" || key == "when_else" => {
+            let Some((condition, then, otherwise)) = constant_branches(key, value) else {
+                return true;
+            };
+            let Some(transform) = (if condition { Some(then) } else { otherwise }) else {
+                return false;
+            };
+            *attr = Attribute::KeyValue {
+                key: Ident::new("apply", key.span()),
+                value: transform,
+            };
+            true
+        }
+        Attribute::Platform { attributes, .. } | Attribute::PseudoState { attributes, .. } => {
+            fold_constant_conditions(attributes);
+            true
+        }
+        _ => true,
+    });
+}
+
+/// The literal condition and the branches of a `when` or `when_else`.
+fn constant_branches(key: &Ident, value: &Expr) -> Option<(bool, Expr, Option<Expr>)> {
+    let Expr::Tuple(tuple) = value else {
+        return None;
+    };
+    let mut elems = tuple.elems.iter();
+    let condition = literal_bool(elems.next()?)?;
+    let then = elems.next()?.clone();
+    let otherwise = elems.next().cloned();
+    if elems.next().is_some() || (key == "when_else") != otherwise.is_some() {
+        // Left for the compiler to report
+        return None;
+    }
+    Some((condition, then, otherwise))
+}
+
+fn literal_bool(expr: &Expr) -> Option<bool> {
+    match expr {
+        Expr::Lit(ExprLit {
+            lit: Lit::Bool(lit),
+            ..
+        }) => Some(lit.value),
+        Expr::Paren(paren) => literal_bool(&paren.expr),
+        Expr::Group(group) => literal_bool(&group.expr),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proc_macro2::Span;
+    use quote::ToTokens;
+    use syn::parse_quote;
+
+    use super::*;
+
+    fn fold(attributes: Vec<Attribute>) -> Vec<String> {
+        let mut attributes = attributes;
+        fold_constant_conditions(&mut attributes);
+        attributes
+            .iter()
+            .map(|attr| match attr {
+                Attribute::KeyValue { key, value } => {
+                    format!("{key}: {}", value.to_token_stream())
+                }
+                _ => unreachable!(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_fold_constant_conditions() {
+        let when = |value: Expr| Attribute::KeyValue {
+            key: Ident::new("when", Span::call_site()),
+            value,
+        };
+        let when_else = |value: Expr| Attribute::KeyValue {
+            key: Ident::new("when_else", Span::call_site()),
+            value,
+        };
+        assert_eq!(
+            fold(vec![
+                when(parse_quote!((true, f))),
+                when(parse_quote!((false, g))),
+                when_else(parse_quote!((false, f, g))),
+                when(parse_quote!((enabled, f))),
+            ]),
+            ["apply: f", "apply: g", "when: (enabled , f)"]
+        );
+    }
+}
@@ -0,0 +1,393 @@
+//! Crate-wide configuration from `gpui-markup.toml`.
+//!
+//! The file is looked up from `CARGO_MANIFEST_DIR` upwards, so a single file
+//! at the workspace root applies to every crate in it:
+//!
+//! ```toml
+//! # Path to the gpui crate used in generated code
+//! gpui = "::gpui"
+//! # Localization function called by `t: "key"` children
+//! i18n = "crate::i18n::t"
+//! # Expression resolving `$token` attribute values
+//! theme = "cx.theme()"
+//! # Give elements using stateful attributes (`on:click`, ...) an id
+//! auto_id = true
+//! # Helpers constructing `h_flex` and `v_flex` elements
+//! h_flex = "ui::h_flex"
+//! v_flex = "ui::v_flex"
+//! # Function building a tooltip from the text of `tooltip: "..."`
+//! tooltip = "ui::Tooltip::text"
+//! # Build child subtrees in their own statement above this many elements
+//! split_threshold = 256
+//! # Turn on `#![options(checked)]` in every invocation
+//! strict = true
+//!
+//! # Paths of gpui traits and element types that moved in a fork
+//! [paths]
+//! ParentElement = "my_fork::elements::ParentElement"
+//! Div = "my_fork::elements::Div"
+//!
+//! # Custom native elements, like `#![elements(icon = my_ui::icon -> my_ui::Icon)]`
+//! [elements]
+//! icon = "my_ui::icon -> my_ui::Icon"
+//!
+//! # Lint levels, overridden by `#![allow(..)]`, `#![warn(..)]` and `#![deny(..)]`
+//! [lints]
+//! unknown_attribute = "deny"
+//! unknown_keyword = "allow"
+//! ```
+
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+use std::sync::{LazyLock, Mutex};
+
+use proc_macro_error2::abort_call_site;
+use proc_macro2::{Ident, Span, TokenStream};
+use quote::{ToTokens, quote};
+use serde::Deserialize;
+
+use crate::lint::{Level, Lints};
+use crate::registry::ElementDef;
+
+/// Name of the configuration file.
+pub const FILE_NAME: &str = "gpui-markup.toml";
+
+/// gpui items exported from `gpui::prelude` but not from the crate root.
+const PRELUDE_ITEMS: &[&str] = &["FluentBuilder"];
+
+/// Settings shared by every `ui!` invocation in a crate.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    /// Path to the gpui crate, e.g. `"::gpui"` or `"my_fork::gpui"`.
+    gpui: Option<String>,
+    /// Path to the localization function, e.g. `"crate::i18n::t"`.
+    i18n: Option<String>,
+    /// Expression resolving `$token` attribute values, e.g. `"cx.theme()"`.
+    theme: Option<String>,
+    /// Insert an id derived from the source location into native elements
+    /// that need one.
+    auto_id: bool,
+    /// Function constructing `h_flex` elements, e.g. `"ui::h_flex"`.
+    h_flex: Option<String>,
+    /// Function constructing `v_flex` elements, e.g. `"ui::v_flex"`.
+    v_flex: Option<String>,
+    /// Function building a tooltip from its text, e.g. `"ui::Tooltip::text"`.
+    tooltip: Option<String>,
+    /// Number of elements above which child subtrees are built in their own
+    /// statement.
+    split_threshold: Option<usize>,
+    /// Assert that elements given style attributes are `Styled` and that
+    /// expression children are elements in every invocation.
+    strict: bool,
+    /// Paths of gpui items by name, overriding the default path under `gpui`,
+    /// e.g. `ParentElement = "my_fork::ParentElement"`.
+    paths: HashMap<String, String>,
+    /// Custom native elements by tag,
+    /// e.g. `icon = "my_ui::icon -> my_ui::Icon"`.
+    elements: BTreeMap<String, String>,
+    /// Lint levels by lint name, e.g. `unknown_attribute = "deny"`.
+    lints: HashMap<String, Level>,
+    /// The file this configuration was loaded from.
+    #[serde(skip)]
+    path: Option<PathBuf>,
+}
+
+impl Config {
+    /// Configuration for the crate currently being compiled.
+    ///
+    /// Loaded once per manifest directory and cached for the lifetime of the
+    /// process (the proc-macro server of an IDE may serve many crates).
+    pub fn current() -> &'static Self {
+        static CACHE: LazyLock<Mutex<HashMap<PathBuf, &'static Config>>> =
+            LazyLock::new(Mutex::default);
+
+        let manifest_dir = std::env::var_os("CARGO_MANIFEST_DIR")
+            .map(PathBuf::from)
+            .unwrap_or_default();
+        let mut cache = CACHE
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+
+        cache
+            .entry(manifest_dir)
+            .or_insert_with_key(|dir| Box::leak(Box::new(Self::discover(dir))))
+    }
+
+    /// Find and load the nearest configuration file above `dir`.
+    fn discover(dir: &Path) -> Self {
+        dir.ancestors()
+            .map(|dir| dir.join(FILE_NAME))
+            .find(|path| path.is_file())
+            .map_or_else(Self::default, |path| Self::load(&path))
+    }
+
+    fn load(path: &Path) -> Self {
+        let source = std::fs::read_to_string(path)
+            .unwrap_or_else(|err| abort_call_site!("failed to read `{}`: {}", path.display(), err));
+        let mut config: Self = toml::from_str(&source)
+            .unwrap_or_else(|err| abort_call_site!("invalid `{}`: {}", path.display(), err));
+        config.path = Some(path.to_owned());
+        config
+    }
+
+    /// Path to the gpui crate (`gpui` by default).
+    pub fn gpui(&self) -> TokenStream {
+        let Some(gpui) = &self.gpui else {
+            return quote! { gpui };
+        };
+
+        syn::parse_str::<syn::Path>(gpui).map_or_else(
+            |_| abort_call_site!("invalid `gpui` path in `{}`: `{}`", FILE_NAME, gpui),
+            ToTokens::into_token_stream,
+        )
+    }
+
+    /// Path to the gpui item `name`, e.g. a trait (`ParentElement`) or an
+    /// element type (`Div`): the path configured in `[paths]`, or
+    /// `gpui::name` (`gpui::prelude::name` for the items gpui only exports
+    /// from its prelude).
+    pub fn item(&self, name: &str) -> TokenStream {
+        if let Some(path) = self.paths.get(name) {
+            return parse_path(&format!("paths.{name}"), path);
+        }
+        let gpui = self.gpui();
+        let prelude = PRELUDE_ITEMS.contains(&name).then(|| quote! { prelude:: });
+        let name = Ident::new(name, Span::call_site());
+        quote! { #gpui::#prelude #name }
+    }
+
+    /// Path to the localization function, if configured.
+    pub fn i18n(&self) -> Option<TokenStream> {
+        self.i18n.as_deref().map(|path| parse_path("i18n", path))
+    }
+
+    /// Path to the function constructing `h_flex` elements, if configured.
+    pub fn h_flex(&self) -> Option<TokenStream> {
+        self.h_flex
+            .as_deref()
+            .map(|path| parse_path("h_flex", path))
+    }
+
+    /// Path to the function constructing `v_flex` elements, if configured.
+    pub fn v_flex(&self) -> Option<TokenStream> {
+        self.v_flex
+            .as_deref()
+            .map(|path| parse_path("v_flex", path))
+    }
+
+    /// Path to the function building a tooltip from the text of
+    /// `tooltip: "..."`, if configured.
+    pub fn tooltip(&self) -> Option<TokenStream> {
+        self.tooltip
+            .as_deref()
+            .map(|path| parse_path("tooltip", path))
+    }
+
+    /// Expression resolving `$token` attribute values (`cx.theme()` by
+    /// default).
+    pub fn theme(&self) -> TokenStream {
+        let Some(theme) = &self.theme else {
+            return quote! { cx.theme() };
+        };
+
+        syn::parse_str::<syn::Expr>(theme).map_or_else(
+            |_| abort_call_site!("invalid `theme` expression in `{}`: `{}`", FILE_NAME, theme),
+            ToTokens::into_token_stream,
+        )
+    }
+
+    /// Whether native elements using stateful attributes without an `id` get
+    /// one inserted (`false` by default).
+    pub const fn auto_id(&self) -> bool {
+        self.auto_id
+    }
+
+    /// Number of elements an expression may build before child subtrees are
+    /// split into their own statement (256 by default).
+    pub fn split_threshold(&self) -> usize {
+        self.split_threshold.unwrap_or(256)
+    }
+
+    /// Whether `#![options(checked)]` is on in every invocation (`false` by
+    /// default).
+    pub const fn strict(&self) -> bool {
+        self.strict
+    }
+
+    /// Custom native elements declared for every invocation, in tag order.
+    pub fn elements(&self) -> Vec<ElementDef> {
+        self.elements
+            .iter()
+            .map(|(tag, definition)| {
+                syn::parse_str(&format!("{tag} = {definition}")).unwrap_or_else(|_| {
+                    abort_call_site!(
+                        "invalid `elements.{}` in `{}`: expected `\"constructor -> Type\"`, got `{}`",
+                        tag,
+                        FILE_NAME,
+                        definition
+                    )
+                })
+            })
+            .collect()
+    }
+
+    /// Lint levels set for every invocation.
+    pub fn lints(&self) -> Lints {
+        let mut lints = Lints::default();
+        for (name, level) in &self.lints {
+            let Ok(name) = syn::parse_str::<Ident>(name) else {
+                abort_call_site!("invalid lint name `{}` in `{}`", name, FILE_NAME);
+            };
+            lints.set(&name, *level);
+        }
+        lints
+    }
+
+    /// Tokens that make the compiler track the configuration file, so edits
+    /// to it trigger a rebuild.
+    pub fn dependency(&self) -> Option<TokenStream> {
+        let path = self.path.as_ref()?.to_str()?;
+        Some(quote! { const _: &[u8] = include_bytes!(#path); })
+    }
+}
+
+fn parse_path(key: &str, path: &str) -> TokenStream {
+    syn::parse_str::<syn::Path>(path).map_or_else(
+        |_| abort_call_site!("invalid `{}` path in `{}`: `{}`", key, FILE_NAME, path),
+        ToTokens::into_token_stream,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lint::AttributeLint;
+
+    #[test]
+    fn test_parse_config() {
+        let config: Config = toml::from_str(r#"gpui = "my_fork::gpui""#).unwrap();
+        assert_eq!(config.gpui().to_string(), "my_fork :: gpui");
+    }
+
+    #[test]
+    fn test_parse_i18n() {
+        let config: Config = toml::from_str(r#"i18n = "crate::i18n::t""#).unwrap();
+        assert_eq!(config.i18n().unwrap().to_string(), "crate :: i18n :: t");
+    }
+
+    #[test]
+    fn test_parse_theme() {
+        let config: Config = toml::from_str(r#"theme = "theme::active(cx)""#).unwrap();
+        assert_eq!(config.theme().to_string(), "theme :: active (cx)");
+    }
+
+    #[test]
+    fn test_parse_flex_helpers() {
+        let config: Config = toml::from_str(r#"h_flex = "ui::h_flex""#).unwrap();
+        assert_eq!(config.h_flex().unwrap().to_string(), "ui :: h_flex");
+        assert!(config.v_flex().is_none());
+    }
+
+    #[test]
+    fn test_parse_tooltip() {
+        let config: Config = toml::from_str(r#"tooltip = "ui::Tooltip::text""#).unwrap();
+        assert_eq!(
+            config.tooltip().unwrap().to_string(),
+            "ui :: Tooltip :: text"
+        );
+    }
+
+    #[test]
+    fn test_parse_split_threshold() {
+        let config: Config = toml::from_str("split_threshold = 64").unwrap();
+        assert_eq!(config.split_threshold(), 64);
+    }
+
+    #[test]
+    fn test_parse_paths() {
+        let config: Config = toml::from_str(
+            r#"
+            gpui = "my_fork::gpui"
+            [paths]
+            ParentElement = "my_fork::elements::ParentElement"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(
+            config.item("ParentElement").to_string(),
+            "my_fork :: elements :: ParentElement"
+        );
+        assert_eq!(
+            config.item("Styled").to_string(),
+            "my_fork :: gpui :: Styled"
+        );
+        assert_eq!(
+            config.item("FluentBuilder").to_string(),
+            "my_fork :: gpui :: prelude :: FluentBuilder"
+        );
+    }
+
+    #[test]
+    fn test_parse_auto_id() {
+        let config: Config = toml::from_str("auto_id = true").unwrap();
+        assert!(config.auto_id());
+    }
+
+    #[test]
+    fn test_parse_strict() {
+        let config: Config = toml::from_str("strict = true").unwrap();
+        assert!(config.strict());
+    }
+
+    #[test]
+    fn test_parse_elements() {
+        let config: Config = toml::from_str(
+            r#"
+            [elements]
+            row = "my_ui::h_flex -> gpui::Div"
+            icon = "my_ui::icon -> my_ui::Icon"
+            "#,
+        )
+        .unwrap();
+        let elements = config.elements();
+        assert_eq!(elements[0].tag, "icon");
+        assert_eq!(
+            elements[1].constructor.to_token_stream().to_string(),
+            "my_ui :: h_flex"
+        );
+    }
+
+    #[test]
+    fn test_parse_lints() {
+        let config: Config = toml::from_str(
+            r#"
+            [lints]
+            unknown_keyword = "deny"
+            "#,
+        )
+        .unwrap();
+        let lints = config.lints();
+        assert_eq!(lints.get(AttributeLint::Keyword), Some(Level::Deny));
+        assert_eq!(lints.get(AttributeLint::Unknown), None);
+        assert!(toml::from_str::<Config>("[lints]\nunknown_keyword = \"loud\"").is_err());
+    }
+
+    #[test]
+    fn test_default_config() {
+        let config = Config::default();
+        assert_eq!(config.gpui().to_string(), "gpui");
+        assert!(config.i18n().is_none());
+        assert_eq!(config.theme().to_string(), "cx . theme ()");
+        assert!(!config.auto_id());
+        assert_eq!(config.split_threshold(), 256);
+        assert!(!config.strict());
+        assert!(config.elements().is_empty());
+        assert!(config.dependency().is_none());
+    }
+
+    #[test]
+    fn test_unknown_key() {
+        assert!(toml::from_str::<Config>("unknown = 1").is_err());
+    }
+}
@@ -0,0 +1,281 @@
+//! `define_component!`: declare a component extending a native element.
+//!
+//! ```ignore
+//! define_component! {
+//!     pub struct Badge extends div {
+//!         label: SharedString,
+//!     }
+//!
+//!     render(self) {
+//!         ui! { self.base @[px_1] { self.label } }
+//!     }
+//! }
+//! ```
+//!
+//! The struct gets a `base` field holding the extended element, the builder
+//! of `#[derive(Props)]`, `Styled`, `InteractiveElement` and `map_base`
+//! delegating to `base`, and `ParentElement` for elements taking children.
+
+use proc_macro2::{Span, TokenStream};
+use quote::quote;
+use syn::parse::{Parse, ParseStream};
+use syn::{
+    Attribute, Block, DeriveInput, FieldsNamed, Generics, Ident, Result, Token, Visibility,
+    parenthesized, parse_quote,
+};
+
+use crate::config::Config;
+use crate::delegate::{
+    derive_base_component, derive_interactive_component, derive_styled_component,
+};
+use crate::props::derive_props;
+
+/// Native elements a component can extend: (tag, element type, accepts
+/// children).
+const BASE_ELEMENTS: &[(&str, &str, bool)] = &[("div", "Div", true), ("svg", "Svg", false)];
+
+/// A parsed `define_component!` invocation.
+pub struct ComponentDecl {
+    attrs: Vec<Attribute>,
+    vis: Visibility,
+    name: Ident,
+    generics: Generics,
+    /// The extended native element, e.g. `div`
+    base: Ident,
+    fields: FieldsNamed,
+    /// Parameter names for the window and app context of `render`
+    window: Option<Ident>,
+    cx: Option<Ident>,
+    body: Block,
+}
+
+impl Parse for ComponentDecl {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let attrs = input.call(Attribute::parse_outer)?;
+        let vis = input.parse()?;
+        input.parse::<Token![struct]>()?;
+        let name = input.parse()?;
+        let mut generics: Generics = input.parse()?;
+
+        let extends: Ident = input.parse()?;
+        if extends != "extends" {
+            return Err(syn::Error::new(
+                extends.span(),
+                "expected `extends`, e.g. `struct Badge extends div { .. }`",
+            ));
+        }
+        let base: Ident = input.parse()?;
+        if !BASE_ELEMENTS.iter().any(|(tag, ..)| base == tag) {
+            return Err(syn::Error::new(
+                base.span(),
+                format!(
+                    "cannot extend `{}`, expected one of: {}",
+                    base,
+                    BASE_ELEMENTS
+                        .iter()
+                        .map(|(tag, ..)| *tag)
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ),
+            ));
+        }
+        generics.where_clause = input.parse()?;
+        let fields = input.parse()?;
+
+        let render: Ident = input.parse()?;
+        if render != "render" {
+            return Err(syn::Error::new(
+                render.span(),
+                "expected `render(self) { .. }`",
+            ));
+        }
+        let content;
+        parenthesized!(content in input);
+        let (window, cx) = parse_render_arguments(&content)?;
+
+        Ok(Self {
+            attrs,
+            vis,
+            name,
+            generics,
+            base,
+            fields,
+            window,
+            cx,
+            body: input.parse()?,
+        })
+    }
+}
+
+/// Parse the parameters of a `render` body: `self`, optionally followed by
+/// names for the window and the context.
+pub fn parse_render_arguments(input: ParseStream) -> Result<(Option<Ident>, Option<Ident>)> {
+    input.parse::<Token![self]>()?;
+    let mut arguments = vec![];
+    while input.peek(Token![,]) {
+        input.parse::<Token![,]>()?;
+        if input.is_empty() || input.peek(Token![|]) {
+            break;
+        }
+        arguments.push(input.parse::<Ident>()?);
+    }
+    if arguments.len() > 2 {
+        return Err(syn::Error::new(
+            arguments[2].span(),
+            "`render` takes at most `self`, `window` and `cx`",
+        ));
+    }
+    let mut arguments = arguments.into_iter();
+    Ok((arguments.next(), arguments.next()))
+}
+
+/// The name of a `render` parameter, `_default` when not named.
+pub fn render_argument(name: Option<&Ident>, default: &str) -> Ident {
+    name.cloned()
+        .unwrap_or_else(|| Ident::new(&format!("_{default}"), Span::call_site()))
+}
+
+/// Expand a `define_component!` declaration into the struct and its
+/// implementations.
+///
+/// # Errors
+///
+/// Returns an error if a `#[prop(...)]` attribute is malformed.
+pub fn expand_declaration(decl: &ComponentDecl) -> Result<TokenStream> {
+    let gpui = Config::current().gpui();
+    let parent_element = Config::current().item("ParentElement");
+    let into_element = Config::current().item("IntoElement");
+    let ComponentDecl {
+        attrs,
+        vis,
+        name,
+        generics,
+        base,
+        fields,
+        body,
+        ..
+    } = decl;
+    let Some(&(_, ty, has_children)) = BASE_ELEMENTS.iter().find(|(tag, ..)| base == tag) else {
+        unreachable!("checked while parsing");
+    };
+    let ty = Config::current().item(ty);
+
+    let fields = fields.named.iter();
+    let mut input: DeriveInput = parse_quote! {
+        #vis struct #name {
+            #[prop(skip, default = #gpui::#base())]
+            base: #ty,
+            #(#fields,)*
+        }
+    };
+    input.generics = generics.clone();
+    let props = derive_props(&input)?;
+    let styled = derive_styled_component(&input)?;
+    let interactive = derive_interactive_component(&input)?;
+    let map_base = derive_base_component(&input)?;
+
+    // Helper attributes only mean something to the derives above
+    let mut item = input;
+    item.attrs.clone_from(attrs);
+    if let syn::Data::Struct(data) = &mut item.data {
+        for field in &mut data.fields {
+            field.attrs.retain(|attr| !attr.path().is_ident("prop"));
+        }
+    }
+
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let parent = has_children.then(|| {
+        quote! {
+            impl #impl_generics #parent_element for #name #ty_generics #where_clause {
+                fn extend(&mut self, elements: impl ::std::iter::IntoIterator<Item = #gpui::AnyElement>) {
+                    #parent_element::extend(&mut self.base, elements);
+                }
+            }
+        }
+    });
+    let window = render_argument(decl.window.as_ref(), "window");
+    let cx = render_argument(decl.cx.as_ref(), "cx");
+
+    Ok(quote! {
+        #item
+
+        #props
+        #styled
+        #interactive
+        #map_base
+        #parent
+
+        impl #impl_generics #into_element for #name #ty_generics #where_clause {
+            type Element = #gpui::Component<Self>;
+
+            fn into_element(self) -> Self::Element {
+                #gpui::Component::new(self)
+            }
+        }
+
+        impl #impl_generics #gpui::RenderOnce for #name #ty_generics #where_clause {
+            fn render(
+                self,
+                #window: &mut #gpui::Window,
+                #cx: &mut #gpui::App,
+            ) -> impl #into_element #body
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use quote::quote;
+    use syn::parse2;
+
+    use super::*;
+
+    fn expand(tokens: TokenStream) -> Result<String> {
+        expand_declaration(&parse2(tokens)?).map(|output| output.to_string())
+    }
+
+    #[test]
+    fn test_declaration() {
+        let output = expand(quote! {
+            pub struct Badge extends div {
+                #[prop(default = 1)]
+                count: usize,
+            }
+
+            render(self, window, cx) {
+                self.base
+            }
+        })
+        .unwrap();
+        assert!(
+            output.contains(
+                &quote! { pub struct Badge { base: gpui::Div, count: usize, } }.to_string()
+            )
+        );
+        assert!(output.contains(&quote! { Self { base: gpui::div(), count: 1, } }.to_string()));
+        assert!(output.contains(&quote! { impl gpui::ParentElement for Badge }.to_string()));
+        assert!(output.contains(
+            &quote! { fn render(self, window: &mut gpui::Window, cx: &mut gpui::App,) }.to_string()
+        ));
+    }
+
+    #[test]
+    fn test_svg_has_no_children() {
+        let output = expand(quote! {
+            struct Icon extends svg {}
+            render(self) { self.base }
+        })
+        .unwrap();
+        assert!(!output.contains("ParentElement"));
+        assert!(output.contains(&quote! { _window: &mut gpui::Window }.to_string()));
+    }
+
+    #[test]
+    fn test_unknown_base() {
+        let input = quote! {
+            struct Badge extends span {}
+            render(self) { self.base }
+        };
+        assert!(parse2::<ComponentDecl>(input).is_err());
+    }
+}
@@ -0,0 +1,61 @@
+//! Attributes of `deferred` elements: `deferred @[priority: 10] { child }`.
+
+use proc_macro_error2::abort;
+use syn::Ident;
+
+use crate::ast::Attribute;
+use crate::suggest::closest_match;
+
+/// Attributes a `deferred` element accepts: its `Deferred` methods, the
+/// builder helpers and `bind:`. All take a value.
+const DEFERRED_ATTRIBUTES: &[&str] = &[
+    "priority",
+    "with_priority",
+    "when",
+    "when_some",
+    "when_none",
+    "when_else",
+    "map",
+    "apply",
+    "bind",
+];
+
+/// Check the attributes of a `deferred` element, which is not styled and
+/// only configures the painting order of its child.
+pub fn check_deferred_attributes(element: &Ident, attributes: &[Attribute]) {
+    for attr in attributes {
+        let name = match attr {
+            Attribute::KeyValue { key, .. } => {
+                if DEFERRED_ATTRIBUTES.iter().any(|known| key == known) {
+                    continue;
+                }
+                key
+            }
+            Attribute::Flag(name) if DEFERRED_ATTRIBUTES.iter().any(|known| name == known) => {
+                abort!(name, "`{}` takes a value, e.g. `{}: 1`", name, name)
+            }
+            Attribute::Flag(name)
+            | Attribute::Compound { key: name, .. }
+            | Attribute::Prop { key: name, .. }
+            | Attribute::Platform { platform: name, .. }
+            | Attribute::PseudoState { state: name, .. }
+            | Attribute::Event { event: name, .. } => name,
+        };
+        if let Some(suggestion) =
+            closest_match(&name.to_string(), DEFERRED_ATTRIBUTES.iter().copied())
+        {
+            abort!(
+                name,
+                "unknown `{}` attribute `{}`, did you mean `{}`?",
+                element,
+                name,
+                suggestion
+            );
+        }
+        abort!(
+            name,
+            "`{}` only takes `priority` and builder attributes like `when`, style its child instead",
+            element
+        );
+    }
+}
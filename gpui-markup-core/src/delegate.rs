@@ -0,0 +1,264 @@
+//! Derives implementing gpui element traits by delegating to a field.
+//!
+//! ```ignore
+//! #[derive(IntoElement, ParentComponent, StyledComponent, InteractiveComponent)]
+//! #[interactive(stateful)]
+//! struct Card {
+//!     base: Stateful<Div>,
+//!     children: SmallVec<[AnyElement; 2]>,
+//! }
+//! ```
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Data, DeriveInput, Error, Fields, Member, Result, Type};
+
+use crate::config::Config;
+
+/// The field a trait is delegated to: the one marked `#[attr]`, or the one
+/// named `default` without a marker.
+fn delegate_field<'a>(
+    input: &'a DeriveInput,
+    derive: &str,
+    attr: &str,
+    default: &str,
+) -> Result<(Member, &'a Type)> {
+    let Data::Struct(data) = &input.data else {
+        return Err(Error::new(
+            input.ident.span(),
+            format!("`{derive}` can only be derived for structs"),
+        ));
+    };
+
+    let mut marked = data
+        .fields
+        .iter()
+        .enumerate()
+        .filter(|(_, field)| field.attrs.iter().any(|a| a.path().is_ident(attr)));
+    if let Some((index, field)) = marked.next() {
+        if let Some((_, other)) = marked.next() {
+            let ident = other.ident.as_ref().map_or(&input.ident, |ident| ident);
+            return Err(Error::new(
+                ident.span(),
+                format!("only one field can be marked `#[{attr}]`"),
+            ));
+        }
+        let member = field
+            .ident
+            .clone()
+            .map_or_else(|| Member::from(index), Member::Named);
+        return Ok((member, &field.ty));
+    }
+
+    if let Fields::Named(fields) = &data.fields
+        && let Some(field) = fields
+            .named
+            .iter()
+            .find(|field| field.ident.as_ref().is_some_and(|ident| ident == default))
+        && let Some(ident) = &field.ident
+    {
+        return Ok((Member::Named(ident.clone()), &field.ty));
+    }
+
+    Err(Error::new(
+        input.ident.span(),
+        format!("`{derive}` needs a field named `{default}` or marked `#[{attr}]`"),
+    ))
+}
+
+/// Expand `#[derive(ParentComponent)]` into a `ParentElement` implementation
+/// extending the `children` field (or the one marked `#[children]`).
+///
+/// # Errors
+///
+/// Returns an error if `input` is not a struct or has no children field.
+pub fn derive_parent_component(input: &DeriveInput) -> Result<TokenStream> {
+    let (field, _) = delegate_field(input, "ParentComponent", "children", "children")?;
+    let gpui = Config::current().gpui();
+    let parent_element = Config::current().item("ParentElement");
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics #parent_element for #name #ty_generics #where_clause {
+            fn extend(&mut self, elements: impl ::std::iter::IntoIterator<Item = #gpui::AnyElement>) {
+                ::std::iter::Extend::extend(&mut self.#field, elements);
+            }
+        }
+    })
+}
+
+/// Expand `#[derive(StyledComponent)]` into a `Styled` implementation
+/// returning the style of the `base` field (or the one marked `#[base]`).
+///
+/// # Errors
+///
+/// Returns an error if `input` is not a struct or has no base field.
+pub fn derive_styled_component(input: &DeriveInput) -> Result<TokenStream> {
+    let (field, _) = delegate_field(input, "StyledComponent", "base", "base")?;
+    let gpui = Config::current().gpui();
+    let styled = Config::current().item("Styled");
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics #styled for #name #ty_generics #where_clause {
+            fn style(&mut self) -> &mut #gpui::StyleRefinement {
+                #styled::style(&mut self.#field)
+            }
+        }
+    })
+}
+
+/// Whether `#[interactive(stateful)]` is set.
+fn is_stateful(input: &DeriveInput) -> Result<bool> {
+    let mut stateful = false;
+    for attr in input
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("interactive"))
+    {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("stateful") {
+                stateful = true;
+                Ok(())
+            } else {
+                Err(meta.error("unknown `interactive` option, expected `stateful`"))
+            }
+        })?;
+    }
+    Ok(stateful)
+}
+
+/// Expand `#[derive(InteractiveComponent)]` into an `InteractiveElement`
+/// implementation returning the interactivity of the `base` field.
+///
+/// `#[interactive(stateful)]` adds `StatefulInteractiveElement`.
+///
+/// # Errors
+///
+/// Returns an error if `input` is not a struct, has no base field, or the
+/// `#[interactive(...)]` attribute is malformed.
+pub fn derive_interactive_component(input: &DeriveInput) -> Result<TokenStream> {
+    let (field, _) = delegate_field(input, "InteractiveComponent", "base", "base")?;
+    let gpui = Config::current().gpui();
+    let stateful_interactive_element = Config::current().item("StatefulInteractiveElement");
+    let interactive_element = Config::current().item("InteractiveElement");
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let stateful = is_stateful(input)?.then(|| {
+        quote! {
+            impl #impl_generics #stateful_interactive_element for #name #ty_generics #where_clause {}
+        }
+    });
+
+    Ok(quote! {
+        impl #impl_generics #interactive_element for #name #ty_generics #where_clause {
+            fn interactivity(&mut self) -> &mut #gpui::Interactivity {
+                #interactive_element::interactivity(&mut self.#field)
+            }
+        }
+
+        #stateful
+    })
+}
+
+/// Expand `#[derive(BaseComponent)]` into a `map_base` method transforming
+/// the `base` field (or the one marked `#[base]`), the target of `@base[...]`
+/// blocks.
+///
+/// # Errors
+///
+/// Returns an error if `input` is not a struct or has no base field.
+pub fn derive_base_component(input: &DeriveInput) -> Result<TokenStream> {
+    let (field, ty) = delegate_field(input, "BaseComponent", "base", "base")?;
+    let name = &input.ident;
+    let vis = &input.vis;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics #name #ty_generics #where_clause {
+            /// Transform the base element, used by `@base[...]` attribute blocks.
+            #[must_use]
+            #vis fn map_base(mut self, f: impl ::std::ops::FnOnce(#ty) -> #ty) -> Self {
+                self.#field = f(self.#field);
+                self
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use syn::parse_quote;
+
+    use super::*;
+
+    #[test]
+    fn test_children_field() {
+        let input: DeriveInput = parse_quote! {
+            struct Card<T> { title: T, children: Vec<AnyElement> }
+        };
+        let output = derive_parent_component(&input).unwrap().to_string();
+        assert!(output.contains(&quote! { impl<T> gpui::ParentElement for Card<T> }.to_string()));
+        assert!(output.contains(&quote! { &mut self.children }.to_string()));
+    }
+
+    #[test]
+    fn test_marked_field() {
+        let input: DeriveInput = parse_quote! {
+            struct Card(#[children] Vec<AnyElement>);
+        };
+        let output = derive_parent_component(&input).unwrap().to_string();
+        assert!(output.contains(&quote! { &mut self.0 }.to_string()));
+    }
+
+    #[test]
+    fn test_missing_field() {
+        let input: DeriveInput = parse_quote! {
+            struct Card { items: Vec<AnyElement> }
+        };
+        assert!(derive_parent_component(&input).is_err());
+    }
+
+    #[test]
+    fn test_styled_base() {
+        let input: DeriveInput = parse_quote! {
+            struct Header { #[base] root: Div }
+        };
+        let output = derive_styled_component(&input).unwrap().to_string();
+        assert!(output.contains(&quote! { gpui::Styled::style(&mut self.root) }.to_string()));
+    }
+
+    #[test]
+    fn test_duplicate_base() {
+        let input: DeriveInput = parse_quote! {
+            struct Header { #[base] a: Div, #[base] b: Div }
+        };
+        assert!(derive_styled_component(&input).is_err());
+    }
+
+    #[test]
+    fn test_interactive() {
+        let input: DeriveInput = parse_quote! {
+            struct Header { base: Div }
+        };
+        let output = derive_interactive_component(&input).unwrap().to_string();
+        assert!(output.contains(&quote! { &mut self.base }.to_string()));
+        assert!(!output.contains("StatefulInteractiveElement"));
+    }
+
+    #[test]
+    fn test_stateful() {
+        let input: DeriveInput = parse_quote! {
+            #[interactive(stateful)]
+            struct Header { base: Stateful<Div> }
+        };
+        let output = derive_interactive_component(&input).unwrap().to_string();
+        assert!(
+            output.contains(
+                &quote! { impl gpui::StatefulInteractiveElement for Header {} }.to_string()
+            )
+        );
+    }
+}
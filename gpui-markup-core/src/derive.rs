@@ -0,0 +1,106 @@
+//! `#[derive(Markup)]`: make a component usable as a `ui!` tag.
+//!
+//! Component tags call `::new()`. Types constructed some other way derive
+//! `Markup` to get a hidden `new()` calling their constructor:
+//!
+//! ```ignore
+//! #[derive(IntoElement, Markup)]
+//! #[markup(constructor = "builder")]
+//! struct Toolbar { .. }
+//! ```
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{DeriveInput, Ident, LitStr, Result};
+
+/// Options of the `#[markup(...)]` attribute.
+#[derive(Default)]
+struct Options {
+    /// Associated function constructing the component.
+    constructor: Option<Ident>,
+}
+
+impl Options {
+    fn parse(input: &DeriveInput) -> Result<Self> {
+        let mut options = Self::default();
+        for attr in input
+            .attrs
+            .iter()
+            .filter(|attr| attr.path().is_ident("markup"))
+        {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("constructor") {
+                    options.constructor = Some(meta.value()?.parse::<LitStr>()?.parse()?);
+                    Ok(())
+                } else {
+                    Err(meta.error("unknown `markup` option, expected `constructor`"))
+                }
+            })?;
+        }
+        Ok(options)
+    }
+}
+
+/// Expand `#[derive(Markup)]` into an inherent `new()` calling the
+/// configured constructor, or `Default::default()` without one.
+///
+/// # Errors
+///
+/// Returns an error if the `#[markup(...)]` attribute is malformed.
+pub fn derive_markup(input: &DeriveInput) -> Result<TokenStream> {
+    let options = Options::parse(input)?;
+    let name = &input.ident;
+    let vis = &input.vis;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let body = options.constructor.map_or_else(
+        || quote! { ::std::default::Default::default() },
+        |constructor| quote! { Self::#constructor() },
+    );
+
+    Ok(quote! {
+        impl #impl_generics #name #ty_generics #where_clause {
+            /// Construct the component for `ui!` tags.
+            #[doc(hidden)]
+            #[must_use]
+            #vis fn new() -> Self {
+                #body
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use syn::parse_quote;
+
+    use super::*;
+
+    #[test]
+    fn test_constructor() {
+        let input: DeriveInput = parse_quote! {
+            #[markup(constructor = "builder")]
+            pub struct Toolbar<T> { items: Vec<T> }
+        };
+        let output = derive_markup(&input).unwrap().to_string();
+        assert!(output.contains(&quote! { impl<T> Toolbar<T> }.to_string()));
+        assert!(output.contains(&quote! { pub fn new() -> Self { Self::builder() } }.to_string()));
+    }
+
+    #[test]
+    fn test_default_constructor() {
+        let input: DeriveInput = parse_quote! {
+            struct Spacer;
+        };
+        let output = derive_markup(&input).unwrap().to_string();
+        assert!(output.contains(&quote! { ::std::default::Default::default() }.to_string()));
+    }
+
+    #[test]
+    fn test_unknown_option() {
+        let input: DeriveInput = parse_quote! {
+            #[markup(builder = "new")]
+            struct Toolbar;
+        };
+        assert!(derive_markup(&input).is_err());
+    }
+}
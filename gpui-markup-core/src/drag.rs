@@ -0,0 +1,82 @@
+//! `drag: (payload, |payload| preview)`: start dragging `payload`, rendering
+//! `preview` under the cursor.
+
+use proc_macro_error2::abort;
+use proc_macro2::{Ident, Span};
+use quote::quote;
+use syn::Expr;
+
+use crate::codegen::{Call, method_call};
+use crate::config::Config;
+
+/// Lower `drag: (payload, preview)` into calls of a helper calling
+/// `.on_drag(payload, constructor)` on the element.
+///
+/// gpui wants the drag preview as an entity of a `Render` view, so the
+/// preview closure is wrapped in one. The payload is cloned into each preview,
+/// which lets `preview` take it by reference like the other drag listeners.
+///
+/// The helper is built from the element and payload, then given the preview:
+/// the values are evaluated in source order, outside the block declaring the
+/// helper's items, and the payload type is known when the preview closure is
+/// checked, which infers its parameter type.
+pub fn drag_calls(key: &Ident, value: &Expr) -> Vec<Call> {
+    let Expr::Tuple(tuple) = value else {
+        abort!(
+            value,
+            "`drag` expects a payload and a preview, e.g. `drag: (tab.clone(), |tab| tab_preview(tab))`"
+        );
+    };
+    if tuple.elems.len() != 2 {
+        abort!(
+            tuple,
+            "`drag` expects a payload and a preview, e.g. `drag: (tab.clone(), |tab| tab_preview(tab))`"
+        );
+    }
+
+    let gpui = Config::current().gpui();
+    let into_element = Config::current().item("IntoElement");
+    let stateful = Config::current().item("StatefulInteractiveElement");
+    let span = key.span();
+    let (payload, preview) = (&tuple.elems[0], &tuple.elems[1]);
+    let view = Ident::new("__DragPreview", Span::mixed_site().located_at(span));
+    let drag = Ident::new("__Drag", Span::mixed_site().located_at(span));
+    let method = Ident::new("preview", span);
+    let helper = quote! {
+        ({
+            struct #view(
+                ::std::boxed::Box<dyn Fn(&mut #gpui::Window, &mut #gpui::App) -> #gpui::AnyElement>,
+            );
+            impl #gpui::Render for #view {
+                fn render(
+                    &mut self,
+                    window: &mut #gpui::Window,
+                    cx: &mut #gpui::Context<Self>,
+                ) -> impl #into_element {
+                    (self.0)(window, &mut **cx)
+                }
+            }
+            struct #drag<El, T>(El, T);
+            impl<El: #stateful, T: Clone + 'static> #drag<El, T> {
+                fn #method<E: #into_element>(self, preview: impl Fn(&T) -> E + 'static) -> El {
+                    let Self(element, payload) = self;
+                    let preview = ::std::rc::Rc::new(preview);
+                    #stateful::on_drag(element, payload, move |payload: &T, _, _, cx: &mut #gpui::App| {
+                        let payload = payload.clone();
+                        let preview = preview.clone();
+                        #gpui::AppContext::new(cx, |_| {
+                            #view(::std::boxed::Box::new(move |_, _| {
+                                #into_element::into_any_element(preview(&payload))
+                            }))
+                        })
+                    })
+                }
+            }
+            #drag
+        })
+    };
+    vec![
+        Call::Function(span, helper, quote! { #payload }),
+        Call::Method(method_call(&method, preview)),
+    ]
+}
@@ -0,0 +1,620 @@
+//! Event attributes: `on:click: |event, window, cx| ...`.
+//!
+//! Each event lowers to the listener method of gpui's `InteractiveElement`
+//! or `StatefulInteractiveElement` trait. Mouse events take button and click
+//! count modifiers (`on:click.double:`, `on:mouse_down.right:`), and `.capture`
+//! selects the capture phase listener (`on:mouse_down.capture:`).
+//!
+//! Drag events name the payload type (`on:drop::<Tab>:`). Actions are wired
+//! with `on:action(SaveFile): handler`, and dispatched on click with
+//! `action: SaveFile`.
+
+use proc_macro_error2::abort;
+use proc_macro2::{Delimiter, Ident, Span, TokenStream, TokenTree};
+use quote::{ToTokens, format_ident, quote, quote_spanned};
+use syn::{Expr, ExprLit, Lit, Type, parse_quote_spanned};
+
+use crate::ast::Attribute;
+use crate::codegen::method_call;
+use crate::config::Config;
+use crate::suggest::closest_match;
+
+/// Events accepted as `on:name:` and the listener method they lower to.
+const EVENTS: &[(&str, &str)] = &[
+    ("click", "on_click"),
+    ("key", "on_key_down"),
+    ("action", "on_action"),
+    ("hover", "on_hover"),
+    ("mouse_down", "on_any_mouse_down"),
+    ("mouse_up", "on_any_mouse_up"),
+    ("mouse_down_out", "on_mouse_down_out"),
+    ("mouse_move", "on_mouse_move"),
+    ("scroll_wheel", "on_scroll_wheel"),
+    ("key_down", "on_key_down"),
+    ("key_up", "on_key_up"),
+    ("modifiers_changed", "on_modifiers_changed"),
+    ("drop", "on_drop"),
+    ("drag_move", "on_drag_move"),
+    ("drag_over", "drag_over"),
+];
+
+/// Events accepting modifiers (`on:click.right:`), with the type of their
+/// event argument.
+const MODIFIABLE_EVENTS: &[(&str, &str)] = &[
+    ("click", "ClickEvent"),
+    ("mouse_down", "MouseDownEvent"),
+    ("mouse_up", "MouseUpEvent"),
+];
+
+/// Events with a capture phase listener, taken with `.capture`.
+const CAPTURE_EVENTS: &[(&str, &str)] = &[
+    ("mouse_down", "capture_any_mouse_down"),
+    ("mouse_up", "capture_any_mouse_up"),
+    ("key", "capture_key_down"),
+    ("action", "capture_action"),
+    ("key_down", "capture_key_down"),
+    ("key_up", "capture_key_up"),
+];
+
+/// Mouse button modifiers and the `MouseButton` variant they filter on.
+const BUTTON_MODIFIERS: &[(&str, &str)] =
+    &[("left", "Left"), ("right", "Right"), ("middle", "Middle")];
+
+/// Click count modifiers and the count they filter on.
+const COUNT_MODIFIERS: &[(&str, usize)] = &[("double", 2), ("triple", 3)];
+
+/// Events taking an argument, with an example of it: `on:key("cmd-s"):`.
+const ARGUMENT_EVENTS: &[(&str, &str)] = &[("key", "\"cmd-s\""), ("action", "SaveFile")];
+
+/// Events taking the type of the dragged payload: `on:drop::<Tab>:`.
+const PAYLOAD_EVENTS: &[&str] = &["drop", "drag_move", "drag_over"];
+
+/// Keystroke modifiers understood by gpui's `Keystroke::parse`.
+const KEY_MODIFIERS: &[&str] = &[
+    "ctrl",
+    "alt",
+    "shift",
+    "fn",
+    "cmd",
+    "super",
+    "win",
+    "secondary",
+];
+
+/// Events that require a `StatefulInteractiveElement`.
+const STATEFUL_EVENTS: &[&str] = &["click", "hover"];
+
+/// Attributes that require a `StatefulInteractiveElement`.
+const STATEFUL_ATTRIBUTES: &[&str] = &[
+    "action",
+    "drag",
+    "on_click",
+    "on_hover",
+    "on_drag",
+    "scroll",
+    "tooltip",
+    "hoverable_tooltip",
+    "track_scroll",
+    "focusable",
+    "overflow_scroll",
+    "overflow_x_scroll",
+    "overflow_y_scroll",
+];
+
+/// Native elements that don't implement `InteractiveElement`.
+const NON_INTERACTIVE_ELEMENTS: &[&str] =
+    &["anchored", "overlay", "surface", "canvas", "list", "memo"];
+
+/// Native elements given an id by their constructor.
+const CONSTRUCTOR_ID_ELEMENTS: &[&str] = &["uniform_list"];
+
+/// Check that `event` is a known event.
+pub fn check_event(event: &Ident) {
+    let name = event.to_string();
+    if EVENTS.iter().any(|(known, _)| *known == name) {
+        return;
+    }
+
+    let candidates = EVENTS.iter().map(|(known, _)| *known);
+    if let Some(suggestion) = closest_match(&name, candidates.clone()) {
+        abort!(
+            event,
+            "unknown event `on:{}`, did you mean `on:{}`?",
+            name,
+            suggestion
+        );
+    }
+    abort!(
+        event,
+        "unknown event `on:{}`, expected one of: {}",
+        name,
+        candidates.collect::<Vec<_>>().join(", ")
+    );
+}
+
+/// Check that drag events name their payload type and other events don't.
+pub fn check_event_payload(event: &Ident, payload: Option<&Type>) {
+    let takes_payload = PAYLOAD_EVENTS.iter().any(|name| event == name);
+    match payload {
+        Some(payload) if !takes_payload => {
+            abort!(payload, "`on:{}` doesn't take a payload type", event);
+        }
+        None if takes_payload => abort!(
+            event,
+            "`{}` needs the type of the dragged payload, e.g. `on:{}::<Tab>: handler`",
+            event,
+            event
+        ),
+        _ => {}
+    }
+}
+
+/// Check that `on:key` has a valid keystroke argument and other events have
+/// none.
+pub fn check_event_argument(event: &Ident, argument: Option<&Expr>) {
+    let Some((_, example)) = ARGUMENT_EVENTS.iter().find(|(name, _)| event == name) else {
+        if let Some(argument) = argument {
+            abort!(argument, "`on:{}` doesn't take an argument", event);
+        }
+        return;
+    };
+    let Some(argument) = argument else {
+        abort!(
+            event,
+            "`on:{}` expects an argument, e.g. `on:{}({}): handler`",
+            event,
+            event,
+            example
+        );
+    };
+
+    if event == "key" {
+        check_keystroke(argument);
+    } else if !matches!(argument, Expr::Path(path) if path.qself.is_none()) {
+        abort!(
+            argument,
+            "`on:{}` expects an action type, e.g. `on:{}({}): handler`",
+            event,
+            event,
+            example
+        );
+    }
+}
+
+/// Check the modifiers of `on:event.modifier:`: each must be known to the
+/// event, with at most one button, one click count and one `.capture`.
+pub fn check_modifiers(event: &Ident, modifiers: &[Ident]) {
+    let mut button: Option<&Ident> = None;
+    let mut count: Option<&Ident> = None;
+    let mut capture: Option<&Ident> = None;
+    for modifier in modifiers {
+        let name = modifier.to_string();
+        let seen = if name == "capture" {
+            if !CAPTURE_EVENTS.iter().any(|(known, _)| event == known) {
+                abort!(
+                    modifier,
+                    "`on:{}` has no capture phase listener, `.capture` is supported on: {}",
+                    event,
+                    CAPTURE_EVENTS
+                        .iter()
+                        .map(|(name, _)| *name)
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                );
+            }
+            &mut capture
+        } else if !MODIFIABLE_EVENTS.iter().any(|(known, _)| event == known) {
+            abort!(modifier, "`on:{}` does not take `.{}`", event, modifier);
+        } else if BUTTON_MODIFIERS.iter().any(|(known, _)| *known == name) {
+            if name == "middle" && event == "click" {
+                abort!(
+                    modifier,
+                    "`.middle` is not supported on `on:click`, use `on:mouse_down.middle` or `on:mouse_up.middle`"
+                );
+            }
+            &mut button
+        } else if COUNT_MODIFIERS.iter().any(|(known, _)| *known == name) {
+            &mut count
+        } else {
+            unknown_modifier(modifier);
+        };
+
+        if let Some(other) = seen {
+            abort!(
+                modifier,
+                "conflicting modifiers `.{}` and `.{}`",
+                other,
+                modifier
+            );
+        }
+        *seen = Some(modifier);
+    }
+}
+
+fn unknown_modifier(modifier: &Ident) -> ! {
+    let candidates = BUTTON_MODIFIERS
+        .iter()
+        .map(|(name, _)| *name)
+        .chain(COUNT_MODIFIERS.iter().map(|(name, _)| *name))
+        .chain(["capture"]);
+    if let Some(suggestion) = closest_match(&modifier.to_string(), candidates.clone()) {
+        abort!(
+            modifier,
+            "unknown modifier `.{}`, did you mean `.{}`?",
+            modifier,
+            suggestion
+        );
+    }
+    abort!(
+        modifier,
+        "unknown modifier `.{}`, expected one of: {}",
+        modifier,
+        candidates.collect::<Vec<_>>().join(", ")
+    );
+}
+
+/// Report events on native elements that can't handle them.
+pub fn check_event_support(element: &Ident, attributes: &[Attribute]) {
+    if !NON_INTERACTIVE_ELEMENTS.contains(&element.to_string().as_str()) {
+        return;
+    }
+    if let Some(event) = attributes.iter().find_map(|attr| match attr {
+        Attribute::Event { event, .. } => Some(event),
+        _ => None,
+    }) {
+        abort!(
+            event,
+            "`{}` does not support events, wrap its content in a `div` to handle `on:{}`",
+            element,
+            event
+        );
+    }
+}
+
+/// Rewrite `action: value` on a native element into an `on:click` listener
+/// dispatching the action.
+///
+/// The value is evaluated once and cloned into a boxed action on each click.
+pub fn expand_actions(attributes: &mut [Attribute]) {
+    for attr in attributes {
+        match attr {
+            Attribute::KeyValue { key, value } if key == "action" => {
+                let gpui = Config::current().gpui();
+                let span = key.span();
+                let action = Ident::new("__action", Span::mixed_site());
+                let window = Ident::new("__window", Span::mixed_site());
+                let cx = Ident::new("__cx", Span::mixed_site());
+                *attr = Attribute::Event {
+                    event: Ident::new("click", span),
+                    payload: None,
+                    argument: None,
+                    modifiers: vec![],
+                    handler: parse_quote_spanned! {span=>
+                        {
+                            let #action = #value;
+                            move |_: &#gpui::ClickEvent, #window: &mut #gpui::Window, #cx: &mut #gpui::App| {
+                                #window.dispatch_action(#gpui::Action::boxed_clone(&#action), #cx);
+                            }
+                        }
+                    },
+                };
+            }
+            Attribute::Platform { attributes, .. } => expand_actions(attributes),
+            _ => {}
+        }
+    }
+}
+
+/// Make sure a native element using stateful attributes has an `id`.
+///
+/// Without one, gpui reports an unsatisfied `StatefulInteractiveElement`
+/// bound deep in the method chain. With `auto_id` enabled in
+/// `gpui-markup.toml`, an id derived from the source location is inserted
+/// instead of reporting an error. `key: k` mixes `k` into that id, for
+/// elements built in a loop, and gives the element one even without
+/// `auto_id`.
+pub fn ensure_stateful(element: &Ident, attributes: &mut Vec<Attribute>) {
+    let key = attributes
+        .iter()
+        .position(|attr| matches!(attr, Attribute::KeyValue { key, .. } if key == "key"))
+        .map(|index| attributes.remove(index));
+    let name = element.to_string();
+    if NON_INTERACTIVE_ELEMENTS.contains(&name.as_str())
+        || CONSTRUCTOR_ID_ELEMENTS.contains(&name.as_str())
+        || has_id(attributes)
+    {
+        if let Some(Attribute::KeyValue { key, .. }) = key {
+            abort!(
+                key,
+                "`key` is mixed into the id generated for `{}`, which already has one",
+                element
+            );
+        }
+        return;
+    }
+    if let Some(Attribute::KeyValue { key, value }) = key {
+        attributes.insert(0, auto_id(key.span(), Some(&value)));
+        return;
+    }
+    let Some((stateful, written)) = find_stateful(attributes) else {
+        return;
+    };
+
+    if Config::current().auto_id() {
+        attributes.insert(0, auto_id(stateful.span(), None));
+        return;
+    }
+
+    abort!(
+        stateful,
+        "`{}` requires a stateful element, add an `id`: `{} @[id: \"...\", {}: ...]`",
+        written,
+        element,
+        written
+    );
+}
+
+/// An `id` derived from the location of `span`, and the caller-supplied `key`
+/// telling apart elements built at the same location.
+fn auto_id(span: Span, key: Option<&Expr>) -> Attribute {
+    let gpui = Config::current().gpui();
+    let location = quote_spanned! {span=>
+        #gpui::ElementId::from(::core::panic::Location::caller())
+    };
+    let value = key.map_or_else(
+        || parse_quote_spanned!(span=> #location),
+        |key| {
+            parse_quote_spanned! {span=>
+                #gpui::ElementId::NamedChild(
+                    ::std::boxed::Box::new(#gpui::ElementId::from(#key)),
+                    #gpui::SharedString::new_static(concat!(file!(), ":", line!(), ":", column!())),
+                )
+            }
+        },
+    );
+    Attribute::KeyValue {
+        key: Ident::new("id", span),
+        value,
+    }
+}
+
+/// Whether `attributes` give the element an id: `id: ..`, also in platform
+/// blocks, or a `.id(..)` call in an `apply:` or `map:` transform.
+fn has_id(attributes: &[Attribute]) -> bool {
+    attributes.iter().any(|attr| match attr {
+        Attribute::KeyValue { key, .. } if key == "id" => true,
+        Attribute::KeyValue { key, value } if key == "apply" || key == "map" => {
+            calls_id(value.to_token_stream())
+        }
+        Attribute::Platform { attributes, .. } => has_id(attributes),
+        _ => false,
+    })
+}
+
+/// Whether `tokens` contain a `.id(..)` method call.
+fn calls_id(tokens: TokenStream) -> bool {
+    let tokens: Vec<_> = tokens.into_iter().collect();
+    tokens.iter().enumerate().any(|(index, token)| match token {
+        TokenTree::Group(group) => calls_id(group.stream()),
+        TokenTree::Punct(dot) if dot.as_char() == '.' => matches!(
+            &tokens[index + 1..],
+            [TokenTree::Ident(id), TokenTree::Group(args), ..]
+                if id == "id" && args.delimiter() == Delimiter::Parenthesis
+        ),
+        _ => false,
+    })
+}
+
+/// The first attribute requiring a stateful element, looking into platform
+/// blocks, and how it is written (`on:click` or `on_click`).
+fn find_stateful(attributes: &[Attribute]) -> Option<(&Ident, String)> {
+    attributes.iter().find_map(|attr| match attr {
+        Attribute::Event { event, .. } => STATEFUL_EVENTS
+            .contains(&event.to_string().as_str())
+            .then(|| (event, format!("on:{event}"))),
+        Attribute::Flag(key) | Attribute::KeyValue { key, .. } => STATEFUL_ATTRIBUTES
+            .contains(&key.to_string().as_str())
+            .then(|| (key, key.to_string())),
+        Attribute::Platform { attributes, .. } => find_stateful(attributes),
+        Attribute::PseudoState { .. } | Attribute::Compound { .. } | Attribute::Prop { .. } => None,
+    })
+}
+
+/// `.on_event(handler)` for an `on:event.modifiers: handler` attribute.
+///
+/// Drag events name their payload type: `.on_drop::<Tab>(handler)`.
+/// `.capture` picks the capture phase method. A button modifier on mouse
+/// events picks gpui's per-button method (`.on_mouse_down(MouseButton::Right,
+/// handler)`). Other modifiers and `on:key("...")` wrap the handler in a
+/// listener that only calls it for matching events. `on:action(Type)` names
+/// the action type: `.on_action::<Type>(handler)`.
+pub fn event_call(
+    event: &Ident,
+    payload: Option<&Type>,
+    argument: Option<&Expr>,
+    modifiers: &[Ident],
+    handler: &Expr,
+) -> TokenStream {
+    let (_, method) = EVENTS
+        .iter()
+        .find(|(name, _)| event == name)
+        .unwrap_or_else(|| unreachable!("events are checked while parsing"));
+    let handler = quote! { #handler };
+    if let Some(payload) = payload {
+        let method = Ident::new(method, event.span());
+        return quote_spanned! {method.span()=> .#method::<#payload>(#handler) };
+    }
+    if modifiers.is_empty() && argument.is_none() {
+        let method = Ident::new(method, event.span());
+        return method_call(&method, handler);
+    }
+
+    let gpui = Config::current().gpui();
+    let event_argument = Ident::new("__event", Span::mixed_site());
+    let is_click = event == "click";
+    let capture = CAPTURE_EVENTS
+        .iter()
+        .find(|(name, _)| event == name)
+        .filter(|_| modifiers.iter().any(|modifier| modifier == "capture"));
+    let mut method = Ident::new(
+        capture.map_or(*method, |(_, capture)| capture),
+        event.span(),
+    );
+
+    if event == "action" {
+        return quote_spanned! {method.span()=> .#method::<#argument>(#handler) };
+    }
+
+    if let Some(keystroke) = argument {
+        // Parsed once, on the first event rather than in every render. The
+        // keystroke was checked at compile time, so it always parses
+        let binding = Ident::new("__KEYSTROKE", Span::mixed_site());
+        let keystroke_type = quote! { ::core::option::Option<#gpui::KeybindingKeystroke> };
+        let setup = quote! {
+            static #binding: ::std::sync::LazyLock<#keystroke_type> = ::std::sync::LazyLock::new(|| {
+                ::core::result::Result::ok(#gpui::Keystroke::parse(#keystroke))
+                    .map(#gpui::KeybindingKeystroke::from_keystroke)
+            });
+        };
+        let condition = quote! {
+            #binding
+                .as_ref()
+                .is_some_and(|keystroke| #event_argument.keystroke.should_match(keystroke))
+        };
+        let event_type = Ident::new("KeyDownEvent", event.span());
+        let listener = filtered_listener(&event_type, &handler, &setup, &[condition]);
+        return method_call(&method, listener);
+    }
+
+    let mut button = None;
+    let mut conditions = vec![];
+    for modifier in modifiers {
+        if let Some((_, variant)) = BUTTON_MODIFIERS.iter().find(|(name, _)| modifier == name) {
+            if is_click {
+                conditions.push(if *variant == "Right" {
+                    quote! { #event_argument.is_right_click() }
+                } else {
+                    quote! { #event_argument.standard_click() }
+                });
+            } else if capture.is_some() {
+                // Capture listeners are not per button
+                let variant = Ident::new(variant, modifier.span());
+                conditions.push(quote! { #event_argument.button == #gpui::MouseButton::#variant });
+            } else {
+                let variant = Ident::new(variant, modifier.span());
+                method = format_ident!("on_{}", event, span = event.span());
+                button = Some(quote! { #gpui::MouseButton::#variant, });
+            }
+        } else if let Some((_, count)) = COUNT_MODIFIERS.iter().find(|(name, _)| modifier == name) {
+            conditions.push(if is_click {
+                quote! { #event_argument.click_count() == #count }
+            } else {
+                quote! { #event_argument.click_count == #count }
+            });
+        }
+    }
+
+    if conditions.is_empty() {
+        return method_call(&method, quote! { #button #handler });
+    }
+
+    let (_, event_type) = MODIFIABLE_EVENTS
+        .iter()
+        .find(|(name, _)| event == name)
+        .unwrap_or_else(|| unreachable!("modifiers are checked while parsing"));
+    let event_type = Ident::new(event_type, event.span());
+    let listener = filtered_listener(&event_type, &handler, &quote! {}, &conditions);
+    method_call(&method, quote! { #button #listener })
+}
+
+/// A listener calling `handler` only when all `conditions` hold for the
+/// event, after running `setup` on each event.
+fn filtered_listener(
+    event_type: &Ident,
+    handler: &TokenStream,
+    setup: &TokenStream,
+    conditions: &[TokenStream],
+) -> TokenStream {
+    let gpui = Config::current().gpui();
+    let event = Ident::new("__event", Span::mixed_site());
+    let listener = Ident::new("__listener", Span::mixed_site());
+    let window = Ident::new("__window", Span::mixed_site());
+    let cx = Ident::new("__cx", Span::mixed_site());
+    let into_listener = Ident::new("__into_listener", Span::mixed_site());
+    // Passing the handler through a generic function lets closure parameter
+    // types be inferred from the listener signature. The function is called
+    // outside the block declaring it and the setup runs in the listener, so
+    // the handler doesn't see the items they declare
+    quote! {
+        {
+            let #listener = ({
+                fn #into_listener(
+                    listener: impl Fn(&#gpui::#event_type, &mut #gpui::Window, &mut #gpui::App) + 'static,
+                ) -> impl Fn(&#gpui::#event_type, &mut #gpui::Window, &mut #gpui::App) + 'static {
+                    listener
+                }
+                #into_listener
+            })(#handler);
+            move |#event: &#gpui::#event_type, #window: &mut #gpui::Window, #cx: &mut #gpui::App| {
+                #setup
+                if #(#conditions)&&* {
+                    #listener(#event, #window, #cx);
+                }
+            }
+        }
+    }
+}
+
+/// Validate the keystroke of `on:key("...")` the way gpui's
+/// `Keystroke::parse` reads it: modifiers followed by a key, separated by `-`.
+fn check_keystroke(keystroke: &Expr) {
+    let Expr::Lit(ExprLit {
+        lit: Lit::Str(lit), ..
+    }) = keystroke
+    else {
+        abort!(
+            keystroke,
+            "`on:key` expects a keystroke string literal, e.g. `on:key(\"cmd-s\")`"
+        );
+    };
+
+    let source = lit.value();
+    if source.is_empty() {
+        abort!(lit, "invalid keystroke: expected a key, e.g. `\"cmd-s\"`");
+    }
+    let mut components = source.split('-').peekable();
+    while let Some(component) = components.next() {
+        if KEY_MODIFIERS
+            .iter()
+            .any(|modifier| modifier.eq_ignore_ascii_case(component))
+        {
+            continue;
+        }
+        let Some(next) = components.next() else {
+            return;
+        };
+        // `ctrl--` is the minus key, `a->b` a key with the character it types
+        let is_minus = next.is_empty() && components.peek().is_none() && source.ends_with('-');
+        if is_minus || (next.len() > 1 && next.starts_with('>') && components.peek().is_none()) {
+            return;
+        }
+        if let Some(suggestion) =
+            closest_match(&component.to_lowercase(), KEY_MODIFIERS.iter().copied())
+        {
+            abort!(
+                lit,
+                "invalid keystroke `{}`: unknown modifier `{}`, did you mean `{}`?",
+                source,
+                component,
+                suggestion
+            );
+        }
+        abort!(
+            lit,
+            "invalid keystroke `{}`: `{}` is not a modifier, expected modifiers ({}) followed by a key",
+            source,
+            component,
+            KEY_MODIFIERS.join(", ")
+        );
+    }
+}
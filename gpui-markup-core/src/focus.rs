@@ -0,0 +1,109 @@
+//! Focus attributes: `focus: handle` and the `focus_trap` flag.
+
+use proc_macro_error2::abort;
+use proc_macro2::{Ident, Span};
+use syn::spanned::Spanned;
+use syn::{Expr, parse_quote_spanned};
+
+use crate::ast::Attribute;
+use crate::config::Config;
+
+/// Rewrite `focus: handle` into `track_focus: &handle`, and `focus_trap`
+/// into a tab group whose Tab and Shift-Tab keep focus inside it.
+///
+/// Only applied to native elements: components may define a `focus` method
+/// of their own.
+pub fn expand_focus(attributes: &mut Vec<Attribute>) {
+    let mut handle = None;
+    for attr in attributes.iter_mut() {
+        if let Attribute::KeyValue { key, value } = attr
+            && key == "focus"
+        {
+            *key = Ident::new("track_focus", key.span());
+            handle = Some(value.clone());
+            *value = parse_quote_spanned!(value.span()=> &#value);
+        }
+    }
+
+    let Some((index, flag)) = attributes
+        .iter()
+        .enumerate()
+        .find_map(|(index, attr)| match attr {
+            Attribute::Flag(flag) if flag == "focus_trap" => Some((index, flag.clone())),
+            _ => None,
+        })
+    else {
+        return;
+    };
+    let Some(handle) = handle else {
+        abort!(
+            flag,
+            "`focus_trap` needs the focus handle of the trapped subtree, add `focus: ...`"
+        );
+    };
+
+    let span = flag.span();
+    attributes[index] = Attribute::KeyValue {
+        key: Ident::new("capture_key_down", span),
+        value: focus_trap(&handle, span),
+    };
+    attributes.insert(index, Attribute::Flag(Ident::new("tab_group", span)));
+}
+
+/// A key listener moving focus to the next or previous tab stop on Tab and
+/// Shift-Tab, wrapping around within the subtree of `handle`.
+fn focus_trap(handle: &Expr, span: Span) -> Expr {
+    let gpui = Config::current().gpui();
+    let helper = Ident::new("__focus_trap", Span::mixed_site().located_at(span));
+    // Called outside the block declaring it, so `handle` doesn't see it
+    parse_quote_spanned! {span=>
+        ({
+            fn #helper(
+                trap: &#gpui::FocusHandle,
+            ) -> impl Fn(&#gpui::KeyDownEvent, &mut #gpui::Window, &mut #gpui::App) + 'static {
+                let trap = trap.clone();
+                move |event, window, cx| {
+                    let keystroke = &event.keystroke;
+                    let modifiers = &keystroke.modifiers;
+                    if keystroke.key != "tab" || modifiers.control || modifiers.alt || modifiers.platform {
+                        return;
+                    }
+                    cx.stop_propagation();
+                    let escaped = |window: &mut #gpui::Window, cx: &mut #gpui::App| {
+                        !trap.contains_focused(window, cx) || trap.is_focused(window)
+                    };
+                    if !modifiers.shift {
+                        window.focus_next();
+                        if escaped(window, cx) {
+                            window.focus(&trap);
+                            window.focus_next();
+                        }
+                        return;
+                    }
+
+                    window.focus_prev();
+                    if escaped(window, cx) {
+                        // Walk forward from the trap to its last tab stop
+                        window.focus(&trap);
+                        let mut stops = ::std::vec::Vec::new();
+                        loop {
+                            window.focus_next();
+                            match window.focused(cx) {
+                                ::std::option::Option::Some(focused)
+                                    if !escaped(window, cx) && !stops.contains(&focused) =>
+                                {
+                                    stops.push(focused);
+                                }
+                                _ => break,
+                            }
+                        }
+                        if let ::std::option::Option::Some(last) = stops.last() {
+                            window.focus(last);
+                        }
+                    }
+                }
+            }
+            #helper
+        })(&#handle)
+    }
+}
@@ -0,0 +1,196 @@
+//! Invocation headers: `#![name(args)]` inner attributes before the root
+//! element, configuring a single `ui!` invocation.
+//!
+//! ```ignore
+//! ui! {
+//!     #![listener(cx)]
+//!     div @[id: "save", on:click: self.save] {}
+//! }
+//! ```
+//!
+//! `#![elements(..)]` declares custom native elements, see `registry`,
+//! `#![options(..)]` sets code generation options, see `options`, and
+//! `#![allow(..)]`, `#![warn(..)]` and `#![deny(..)]` set lint levels, see
+//! `lint`.
+
+use proc_macro_error2::abort;
+use syn::parse::ParseStream;
+use syn::punctuated::Punctuated;
+use syn::spanned::Spanned;
+use syn::{Expr, Ident, Member, Result, Token, parse_quote_spanned};
+
+use crate::ast::{Attribute, Child, Element};
+use crate::lint::{Level, Lints};
+use crate::options::Options;
+use crate::registry::ElementDef;
+use crate::suggest::closest_match;
+
+/// Headers accepted as `#![name(...)]`.
+const HEADERS: &[&str] = &["listener", "elements", "options", "allow", "warn", "deny"];
+
+/// Settings declared by the headers of an invocation.
+#[derive(Default)]
+pub struct Header {
+    /// Context wrapping `self.method` event handlers in `cx.listener(..)`
+    listener: Option<Expr>,
+    /// Custom native elements
+    elements: Vec<ElementDef>,
+    /// Code generation options
+    options: Options,
+    /// Lint levels
+    lints: Lints,
+}
+
+impl Header {
+    /// Parse the `#![...]` attributes at the start of an invocation.
+    pub fn parse(input: ParseStream) -> Result<Self> {
+        let mut header = Self::default();
+
+        for attr in input.call(syn::Attribute::parse_inner)? {
+            let name = attr.path().require_ident()?.to_string();
+            if name == "listener" {
+                header.listener = Some(attr.parse_args()?);
+            } else if name == "elements" {
+                header.elements.extend(
+                    attr.parse_args_with(Punctuated::<ElementDef, Token![,]>::parse_terminated)?,
+                );
+            } else if name == "options" {
+                header.options = attr.parse_args()?;
+            } else if let Some(level) = Level::from_header(&name) {
+                for lint in
+                    attr.parse_args_with(Punctuated::<Ident, Token![,]>::parse_terminated)?
+                {
+                    header.lints.set(&lint, level);
+                }
+            } else {
+                unknown_header(attr.path(), &name);
+            }
+        }
+
+        Ok(header)
+    }
+
+    /// Custom native elements declared by `#![elements(..)]`.
+    pub fn elements(&self) -> &[ElementDef] {
+        &self.elements
+    }
+
+    /// Code generation options set by `#![options(..)]`.
+    pub const fn options(&self) -> Options {
+        self.options
+    }
+
+    /// Lint levels set by `#![allow(..)]`, `#![warn(..)]` and `#![deny(..)]`.
+    pub const fn lints(&self) -> Lints {
+        self.lints
+    }
+
+    /// Apply the headers to the parsed tree.
+    pub fn apply(&self, element: &mut Element) {
+        if let Some(cx) = &self.listener {
+            wrap_listeners(element, cx);
+        }
+    }
+}
+
+fn unknown_header(path: &syn::Path, name: &str) -> ! {
+    if let Some(suggestion) = closest_match(name, HEADERS.iter().copied()) {
+        abort!(
+            path,
+            "unknown header `#![{}]`, did you mean `#![{}]`?",
+            name,
+            suggestion
+        );
+    }
+    abort!(
+        path,
+        "unknown header `#![{}]`, expected one of: {}",
+        name,
+        HEADERS.join(", ")
+    );
+}
+
+/// Wrap `on:event: self.method` handlers as `cx.listener(Self::method)`
+/// throughout the tree.
+fn wrap_listeners(element: &mut Element, cx: &Expr) {
+    let (attributes, children) = match element {
+        Element::Native(el) => (&mut el.attributes, &mut el.children),
+        Element::Component(el) => (&mut el.attributes, &mut el.children),
+        Element::Expression(el) => (&mut el.attributes, &mut el.children),
+        Element::Deferred(el) => {
+            wrap_child_listeners(&mut el.child, cx);
+            return;
+        }
+        // Span handlers receive the window and app only, not the event
+        Element::Span(_) | Element::RichText(_) => return,
+    };
+
+    wrap_attribute_listeners(attributes, cx);
+    for child in children {
+        wrap_child_listeners(child, cx);
+    }
+}
+
+fn wrap_child_listeners(child: &mut Child, cx: &Expr) {
+    match child {
+        Child::Element(element) => wrap_listeners(element, cx),
+        Child::Cfg { child, .. } => wrap_child_listeners(child, cx),
+        Child::Spread(_) | Child::MethodChain(_) => {}
+    }
+}
+
+fn wrap_attribute_listeners(attributes: &mut [Attribute], cx: &Expr) {
+    for attr in attributes {
+        match attr {
+            Attribute::Event { handler, .. } => {
+                if let Some(listener) = self_method(handler, cx) {
+                    *handler = listener;
+                }
+            }
+            Attribute::Platform { attributes, .. } | Attribute::Compound { attributes, .. } => {
+                wrap_attribute_listeners(attributes, cx);
+            }
+            Attribute::Flag(_)
+            | Attribute::KeyValue { .. }
+            | Attribute::PseudoState { .. }
+            | Attribute::Prop { .. } => {}
+        }
+    }
+}
+
+/// `cx.listener(Self::method)` for a `self.method` handler.
+fn self_method(handler: &Expr, cx: &Expr) -> Option<Expr> {
+    let Expr::Field(field) = handler else {
+        return None;
+    };
+    let Member::Named(method) = &field.member else {
+        return None;
+    };
+    if !matches!(field.base.as_ref(), Expr::Path(path) if path.path.is_ident("self")) {
+        return None;
+    }
+    Some(parse_quote_spanned!(handler.span()=> #cx.listener(Self::#method)))
+}
+
+#[cfg(test)]
+mod tests {
+    use quote::{ToTokens, quote};
+    use syn::parse_quote;
+
+    use super::*;
+
+    #[test]
+    fn test_self_method() {
+        let cx: Expr = parse_quote!(cx);
+        let wrap = |handler: Expr| {
+            self_method(&handler, &cx).map(|expr| expr.to_token_stream().to_string())
+        };
+        assert_eq!(
+            wrap(parse_quote!(self.save)).unwrap(),
+            quote!(cx.listener(Self::save)).to_string()
+        );
+        assert_eq!(wrap(parse_quote!(self.0)), None);
+        assert_eq!(wrap(parse_quote!(other.save)), None);
+        assert_eq!(wrap(parse_quote!(|_, _, _| {})), None);
+    }
+}
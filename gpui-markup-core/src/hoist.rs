@@ -0,0 +1,189 @@
+//! Hoisting of static subtrees into nested functions.
+//!
+//! A native element whose attributes and children are all literal, like the
+//! chrome of a toolbar or an empty state, captures nothing from the render
+//! function, so it is built by a nested `fn` item instead:
+//!
+//! ```text
+//! { fn __static() -> impl IntoElement { div().flex().child("No results") } __static() }
+//! ```
+//!
+//! Function items are type-checked on their own, which keeps the expression
+//! of the enclosing tree small. Elements can't be cloned, so the subtree is
+//! still built on every render.
+//!
+//! Values count as literal when they are literals (including units like
+//! `16px`), paths into a module (`gpui::px(16.0)`, `crate::theme::GAP`) or
+//! constants, possibly negated or grouped in tuples. A single lowercase name
+//! may be a local variable, and a path starting with a type like
+//! `FontWeight::BOLD` may start with a generic parameter of the enclosing
+//! function (`T::WIDTH`), which the nested function can't name, so their
+//! subtrees are left in place. For the same
+//! reason a hoisted subtree never names a function like `__static` itself:
+//! the nested function can't shadow an item the subtree refers to.
+
+use proc_macro2::{Span, TokenStream};
+use quote::{ToTokens, quote};
+use syn::{Expr, Ident, UnOp};
+
+use crate::ast::{Attribute, Child, Element};
+use crate::config::Config;
+
+/// Static subtrees of at least this many elements, text included, are
+/// hoisted; smaller ones aren't worth a function.
+const HOIST_THRESHOLD: usize = 4;
+
+/// Native elements built from the attributes and children alone. The others
+/// read the window or theme, or take their children as arguments.
+const HOISTABLE_ELEMENTS: &[&str] = &[
+    "div", "h_flex", "v_flex", "stack", "spacer", "svg", "anchored", "overlay", "empty",
+];
+
+/// `element` built by a nested function, if it is a large enough static
+/// subtree.
+pub fn hoisted(element: &Element) -> Option<TokenStream> {
+    if !is_hoisted(element) {
+        return None;
+    }
+    let into_element = Config::current().item("IntoElement");
+    let function = Ident::new("__static", Span::mixed_site());
+    Some(quote! {
+        {
+            fn #function() -> impl #into_element {
+                #element
+            }
+            #function()
+        }
+    })
+}
+
+/// Whether `element` is a static subtree large enough to be hoisted.
+pub fn is_hoisted(element: &Element) -> bool {
+    matches!(element, Element::Native(_))
+        && static_size(element).is_some_and(|size| size >= HOIST_THRESHOLD)
+}
+
+/// The number of elements in `element` if it captures nothing.
+fn static_size(element: &Element) -> Option<usize> {
+    match element {
+        Element::Native(el) => {
+            let name = el.name.to_string();
+            if el.definition.is_none() && !HOISTABLE_ELEMENTS.contains(&name.as_str()) {
+                return None;
+            }
+            if !el.attributes.iter().all(is_static_attribute) {
+                return None;
+            }
+            el.children
+                .iter()
+                .try_fold(1, |size, child| Some(size + static_child_size(child)?))
+        }
+        Element::Expression(el) if el.attributes.is_empty() && el.children.is_empty() => {
+            is_static(&el.expr).then_some(1)
+        }
+        _ => None,
+    }
+}
+
+fn static_child_size(child: &Child) -> Option<usize> {
+    match child {
+        Child::Element(element) => static_size(element),
+        Child::MethodChain(_) | Child::Spread(_) | Child::Cfg { .. } => None,
+    }
+}
+
+fn is_static_attribute(attr: &Attribute) -> bool {
+    match attr {
+        Attribute::Flag(_) => true,
+        Attribute::KeyValue { value, .. } => is_static(value),
+        Attribute::PseudoState {
+            group, attributes, ..
+        } => group.as_ref().is_none_or(is_static) && attributes.iter().all(is_static_attribute),
+        Attribute::Platform { attributes, .. } | Attribute::Compound { attributes, .. } => {
+            attributes.iter().all(is_static_attribute)
+        }
+        Attribute::Prop { .. } | Attribute::Event { .. } => false,
+    }
+}
+
+fn is_static(expr: &Expr) -> bool {
+    match expr {
+        Expr::Lit(_) => true,
+        Expr::Unary(unary) => matches!(unary.op, UnOp::Neg(_)) && is_static(&unary.expr),
+        Expr::Paren(paren) => is_static(&paren.expr),
+        Expr::Group(group) => is_static(&group.expr),
+        Expr::Tuple(tuple) => tuple.elems.iter().all(is_static),
+        Expr::Path(path) => is_item_path(&path.path, path.qself.is_some()),
+        Expr::Call(call) => {
+            matches!(call.func.as_ref(), Expr::Path(path) if is_item_path(&path.path, path.qself.is_some()) || is_constructor(&path.path))
+                && call.args.iter().all(is_static)
+        }
+        _ => false,
+    }
+}
+
+/// Whether `path` names an item rather than a local variable or a generic
+/// parameter of the enclosing function: a path into a module or a constant,
+/// without generic arguments that could name parameters.
+fn is_item_path(path: &syn::Path, qualified: bool) -> bool {
+    if qualified
+        || path
+            .segments
+            .iter()
+            .any(|segment| !segment.arguments.is_none())
+    {
+        return false;
+    }
+    if path.segments.len() > 1 {
+        // Generic parameters and `Self` are capitalized, modules aren't
+        return path.leading_colon.is_some()
+            || path.segments.first().is_some_and(|segment| {
+                segment.ident != "self"
+                    && !segment
+                        .ident
+                        .to_string()
+                        .starts_with(|c: char| c.is_ascii_uppercase())
+            });
+    }
+    // A single letter is more likely a const generic parameter, like `N`
+    let name = path.to_token_stream().to_string();
+    name.len() > 1
+        && name
+            .chars()
+            .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == '_')
+}
+
+/// Whether `path` is a tuple struct or variant, like `Pixels` or `Some`.
+fn is_constructor(path: &syn::Path) -> bool {
+    path.get_ident().is_some_and(|ident| {
+        ident
+            .to_string()
+            .starts_with(|c: char| c.is_ascii_uppercase())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use syn::parse_quote;
+
+    use super::*;
+
+    #[test]
+    fn test_is_static() {
+        assert!(is_static(&parse_quote!(-1.0)));
+        assert!(is_static(&parse_quote!(gpui::px(16.0))));
+        assert!(is_static(&parse_quote!(crate::theme::GAP)));
+        assert!(is_static(&parse_quote!(::gpui::FontWeight::BOLD)));
+        assert!(is_static(&parse_quote!((gpui::px(4.0), MAX_WIDTH))));
+        assert!(is_static(&parse_quote!(Pixels(1.0))));
+        assert!(!is_static(&parse_quote!(gap)));
+        assert!(!is_static(&parse_quote!(px(gap))));
+        assert!(!is_static(&parse_quote!(size(4.0))));
+        assert!(!is_static(&parse_quote!(Self::WIDTH)));
+        assert!(!is_static(&parse_quote!(T::WIDTH)));
+        assert!(!is_static(&parse_quote!(FontWeight::BOLD)));
+        assert!(!is_static(&parse_quote!(N)));
+        assert!(!is_static(&parse_quote!(Sizes::<T>::SMALL)));
+        assert!(!is_static(&parse_quote!(cx.theme().border)));
+    }
+}
@@ -0,0 +1,93 @@
+//! Layout helper elements built on `div`: `stack` (a relative container with
+//! absolutely positioned children), `spacer` (a flex-grow filler) and
+//! `divider` (a 1px line in the theme's `$border` color).
+
+use proc_macro_error2::abort;
+use proc_macro2::Span;
+use quote::quote;
+use syn::{Expr, Ident, parse_quote};
+
+use crate::ast::{Attribute, Child};
+use crate::codegen::spread_children;
+use crate::config::Config;
+
+/// Prepare the attributes and children of a layout helper element.
+///
+/// `stack` children are each wrapped in an absolutely positioned div filling
+/// the stack, and `divider` gets its size from its orientation (`vertical`
+/// flag, horizontal by default).
+pub fn expand_layout_element(
+    name: &Ident,
+    attributes: &mut Vec<Attribute>,
+    children: Vec<Child>,
+) -> Vec<Child> {
+    match name.to_string().as_str() {
+        "stack" => children.into_iter().map(stack_child).collect(),
+        "spacer" | "divider" => {
+            if !children.is_empty() {
+                abort!(name, "`{}` takes no children", name);
+            }
+            if name == "divider" {
+                divider_size(attributes);
+            }
+            children
+        }
+        _ => children,
+    }
+}
+
+fn stack_child(child: Child) -> Child {
+    let gpui = Config::current().gpui();
+    let parent_element = Config::current().item("ParentElement");
+    let styled = Config::current().item("Styled");
+    let layer = quote! { #styled::inset_0(#styled::absolute(#gpui::div())) };
+    match child {
+        Child::Element(element) => Child::expression(Expr::Verbatim(quote! {
+            #parent_element::child(#layer, #element)
+        })),
+        Child::Spread(expr) => {
+            let children = spread_children(&expr);
+            Child::Spread(parse_quote! {
+                ::std::iter::Iterator::map(
+                    ::std::iter::IntoIterator::into_iter(#children),
+                    |child| #parent_element::child(#layer, child),
+                )
+            })
+        }
+        Child::Cfg { attrs, child } => Child::Cfg {
+            attrs,
+            child: Box::new(stack_child(*child)),
+        },
+        child @ Child::MethodChain(_) => child,
+    }
+}
+
+/// Replace the `vertical` flag of a `divider` with its size, set before the
+/// other attributes so they can override it.
+fn divider_size(attributes: &mut Vec<Attribute>) {
+    let vertical = attributes.iter().find_map(|attr| match attr {
+        Attribute::Flag(flag) if flag == "vertical" => Some(flag.span()),
+        _ => None,
+    });
+    attributes.retain(|attr| !matches!(attr, Attribute::Flag(flag) if flag == "vertical"));
+    let (thickness, length) = vertical.map_or_else(
+        || {
+            (
+                Ident::new("h", Span::call_site()),
+                Ident::new("w_full", Span::call_site()),
+            )
+        },
+        |span| (Ident::new("w", span), Ident::new("h_full", span)),
+    );
+    let gpui = Config::current().gpui();
+    attributes.splice(
+        0..0,
+        [
+            Attribute::KeyValue {
+                key: thickness,
+                value: parse_quote! { #gpui::px(1.0) },
+            },
+            Attribute::Flag(length),
+        ],
+    );
+}
@@ -0,0 +1,103 @@
+//! `lazy { child }`: a child built when its parent lays it out.
+//!
+//! ```ignore
+//! let details = ui! { lazy { Details(item.clone()) {} } };
+//! ui! { div @[when: (expanded, move |this| this.child(details))] {} }
+//! ```
+//!
+//! The child is wrapped in a `move` closure held by a small element, which
+//! calls it in `request_layout` and forwards prepaint and paint to the built
+//! element. A lazy child that is never laid out, like one in a branch that
+//! isn't taken or a layer that isn't shown, is dropped without being built.
+//! Like tooltip builders, the closure can only capture owned values.
+
+use proc_macro_error2::abort;
+use proc_macro2::{Span, TokenStream};
+use quote::quote;
+use syn::Ident;
+
+use crate::ast::{Attribute, Child};
+use crate::config::Config;
+
+/// Check that a `lazy` element wraps a single element and takes no
+/// attributes.
+pub fn check_lazy(name: &Ident, attributes: &[Attribute], children: &[Child]) {
+    if !attributes.is_empty() {
+        abort!(name, "`lazy` takes no attributes, style its child instead");
+    }
+    if children.len() != 1 || !matches!(children[0], Child::Element(_)) {
+        abort!(name, "`lazy` must have exactly one element child");
+    }
+}
+
+/// Build the child of a `lazy` element when it is laid out.
+pub fn lazy(child: &Child) -> TokenStream {
+    let gpui = Config::current().gpui();
+    let into_element = Config::current().item("IntoElement");
+    let child = child.element();
+    let element = Ident::new("__Lazy", Span::mixed_site());
+    // Constructed outside the block declaring it, so the child doesn't see
+    // its items
+    quote! {
+        ({
+            struct #element<F>(::std::option::Option<F>);
+            impl<F: FnOnce() -> #gpui::AnyElement + 'static> #into_element for #element<F> {
+                type Element = Self;
+
+                fn into_element(self) -> Self {
+                    self
+                }
+            }
+            impl<F: FnOnce() -> #gpui::AnyElement + 'static> #gpui::Element for #element<F> {
+                type RequestLayoutState = #gpui::AnyElement;
+                type PrepaintState = ();
+
+                fn id(&self) -> ::std::option::Option<#gpui::ElementId> {
+                    None
+                }
+
+                fn source_location(&self) -> ::std::option::Option<&'static ::core::panic::Location<'static>> {
+                    None
+                }
+
+                fn request_layout(
+                    &mut self,
+                    _: ::std::option::Option<&#gpui::GlobalElementId>,
+                    _: ::std::option::Option<&#gpui::InspectorElementId>,
+                    window: &mut #gpui::Window,
+                    cx: &mut #gpui::App,
+                ) -> (#gpui::LayoutId, #gpui::AnyElement) {
+                    let build = self.0.take().expect("lazy child is laid out once");
+                    let mut element = build();
+                    (element.request_layout(window, cx), element)
+                }
+
+                fn prepaint(
+                    &mut self,
+                    _: ::std::option::Option<&#gpui::GlobalElementId>,
+                    _: ::std::option::Option<&#gpui::InspectorElementId>,
+                    _: #gpui::Bounds<#gpui::Pixels>,
+                    element: &mut #gpui::AnyElement,
+                    window: &mut #gpui::Window,
+                    cx: &mut #gpui::App,
+                ) {
+                    element.prepaint(window, cx);
+                }
+
+                fn paint(
+                    &mut self,
+                    _: ::std::option::Option<&#gpui::GlobalElementId>,
+                    _: ::std::option::Option<&#gpui::InspectorElementId>,
+                    _: #gpui::Bounds<#gpui::Pixels>,
+                    element: &mut #gpui::AnyElement,
+                    _: &mut (),
+                    window: &mut #gpui::Window,
+                    cx: &mut #gpui::App,
+                ) {
+                    element.paint(window, cx);
+                }
+            }
+            #element
+        })(::std::option::Option::Some(move || #into_element::into_any_element(#child)))
+    }
+}
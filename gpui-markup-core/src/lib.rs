@@ -0,0 +1,128 @@
+//! Parser and code generator behind the [`gpui-markup`] `ui!` macro.
+//!
+//! This crate lets other procedural macros embed `ui!` markup without
+//! re-parsing it from a string. Parse the markup tokens with
+//! [`parse_markup`] and interpolate the resulting [`Markup`] with `quote!`:
+//!
+//! ```ignore
+//! #[proc_macro]
+//! #[proc_macro_error2::proc_macro_error]
+//! pub fn route(input: TokenStream) -> TokenStream {
+//!     let markup = match gpui_markup_core::parse_markup(input.into()) {
+//!         Ok(markup) => markup,
+//!         Err(err) => return err.to_compile_error().into(),
+//!     };
+//!     quote! { Box::new(move || #markup) }.into()
+//! }
+//! ```
+//!
+//! Like `ui!`, the parser reports some errors through [`proc_macro_error2`],
+//! so the calling macro must be annotated with `#[proc_macro_error]`.
+//!
+//! [`gpui-markup`]: https://docs.rs/gpui-markup
+
+mod anchored;
+pub mod ast;
+mod canvas;
+mod child;
+mod closure;
+pub(crate) mod codegen;
+mod component;
+mod condition;
+mod config;
+mod declare;
+mod deferred;
+mod delegate;
+mod derive;
+mod drag;
+mod event;
+mod focus;
+mod header;
+mod hoist;
+mod layout;
+mod lazy;
+mod link;
+mod lint;
+mod list;
+#[cfg(feature = "markdown")]
+mod markdown;
+mod memo;
+mod methods;
+mod mixin;
+mod options;
+mod parser;
+mod partial;
+mod portal;
+mod preset;
+mod props;
+mod registry;
+mod render;
+mod shorthand;
+mod span;
+mod split;
+mod styled;
+mod suggest;
+mod template;
+mod text;
+mod theme;
+mod tooltip;
+mod units;
+
+use proc_macro2::TokenStream;
+use quote::quote;
+
+pub use crate::ast::{Markup, MarkupVec, StyleMixin};
+pub use crate::closure::MarkupFn;
+pub use crate::component::expand_component;
+pub use crate::declare::{ComponentDecl, expand_declaration};
+pub use crate::delegate::{
+    derive_base_component, derive_interactive_component, derive_parent_component,
+    derive_styled_component,
+};
+pub use crate::derive::derive_markup;
+pub use crate::partial::partial_expansion;
+pub use crate::props::derive_props;
+pub use crate::render::{RenderImpl, expand_render};
+pub use crate::template::Templates;
+
+/// Parse the body of a `ui!` invocation into a [`Markup`] tree.
+///
+/// The returned tree implements [`quote::ToTokens`], expanding to the same
+/// code `ui!` would generate.
+///
+/// # Errors
+///
+/// Returns an error if `tokens` is not valid markup.
+pub fn parse_markup(tokens: TokenStream) -> syn::Result<Markup> {
+    syn::parse2(tokens)
+}
+
+/// The syntax errors in `error` as an expression, for macros expanding to
+/// one. Several `compile_error!` invocations only form an expression, and are
+/// all reported, inside a block.
+#[must_use]
+pub fn expression_errors(error: &syn::Error) -> TokenStream {
+    let errors = error.to_compile_error();
+    quote! {{ #errors }}
+}
+
+#[cfg(test)]
+mod tests {
+    use quote::{ToTokens, quote};
+
+    use super::*;
+
+    #[test]
+    fn test_parse_markup() {
+        let markup = parse_markup(quote! { div @[flex] { "Hello" } }).unwrap();
+        assert_eq!(
+            markup.to_token_stream().to_string(),
+            quote! { gpui::ParentElement::child(div().flex(), "Hello") }.to_string()
+        );
+    }
+
+    #[test]
+    fn test_parse_markup_error() {
+        assert!(parse_markup(quote! { div { a b } }).is_err());
+    }
+}
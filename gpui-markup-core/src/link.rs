@@ -0,0 +1,95 @@
+//! `link("label", url)` children: text that opens a URL when clicked.
+
+use proc_macro2::{Ident, Span};
+use quote::quote_spanned;
+use syn::spanned::Spanned;
+use syn::{Expr, ExprCall, ExprLit, Lit};
+
+use crate::config::Config;
+use crate::text::text_literal;
+
+/// Lower `link("label", url)` into a clickable, hover-underlined element.
+///
+/// Only a bare `link` call with a string literal label and a URL is taken
+/// over. Other calls, like `link(item)` to a function of your own, are
+/// expression elements.
+///
+/// The element id is the location of the call joined with the URL, so links
+/// written in different places, or built in a loop for different URLs, don't
+/// share one.
+pub fn link_child(expr: Expr) -> Expr {
+    let Expr::Call(ExprCall { func, args, .. }) = &expr else {
+        return expr;
+    };
+    if !matches!(func.as_ref(), Expr::Path(path) if path.qself.is_none() && path.path.is_ident("link"))
+    {
+        return expr;
+    }
+    if args.len() != 2
+        || !matches!(
+            &args[0],
+            Expr::Lit(ExprLit {
+                lit: Lit::Str(_),
+                ..
+            })
+        )
+    {
+        return expr;
+    }
+
+    let label = text_literal(args[0].clone());
+    let url = &args[1];
+    let gpui = Config::current().gpui();
+    let styled = Config::current().item("Styled");
+    let parent_element = Config::current().item("ParentElement");
+    let interactive = Config::current().item("InteractiveElement");
+    let stateful = Config::current().item("StatefulInteractiveElement");
+    let span = func.span();
+    let url_binding = Ident::new("__url", Span::mixed_site());
+    let link = Ident::new("__link", Span::mixed_site());
+    Expr::Verbatim(quote_spanned! {span=>
+        {
+            let #url_binding = #gpui::SharedString::from(#url);
+            let #link = #interactive::id(
+                #gpui::div(),
+                #gpui::ElementId::NamedChild(
+                    ::std::boxed::Box::new(#gpui::ElementId::CodeLocation(
+                        *::core::panic::Location::caller(),
+                    )),
+                    #url_binding.clone(),
+                ),
+            );
+            let #link = #interactive::hover(#styled::cursor_pointer(#link), #styled::underline);
+            #stateful::on_click(
+                #parent_element::child(#link, #label),
+                move |_, _, cx: &mut #gpui::App| cx.open_url(&#url_binding),
+            )
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use quote::ToTokens;
+    use syn::parse_quote;
+
+    use super::*;
+
+    #[test]
+    fn test_other_calls_are_kept() {
+        for expr in [
+            parse_quote!(links("a", b)),
+            parse_quote!(ui::link("a", b)),
+            parse_quote!((link("a", b))),
+            parse_quote!(link(item)),
+            parse_quote!(link(label, url)),
+            parse_quote!(link("a", b, c)),
+        ] {
+            let expr: Expr = expr;
+            assert_eq!(
+                link_child(expr.clone()).to_token_stream().to_string(),
+                expr.to_token_stream().to_string()
+            );
+        }
+    }
+}
@@ -0,0 +1,412 @@
+//! Compile-time lints over parsed attributes, and their levels.
+//!
+//! ```ignore
+//! ui! {
+//!     #![warn(duplicate_attribute)]
+//!     #![deny(unknown_attribute)]
+//!     div @[w: px(10.0), w: px(20.0)] {}
+//! }
+//! ```
+//!
+//! Each lint is allowed, warned about or denied for an invocation by the
+//! `#![allow(..)]`, `#![warn(..)]` and `#![deny(..)]` headers, the last one
+//! naming a lint winning, or for every invocation by the `[lints]` table of
+//! `gpui-markup.toml`. Without either, `duplicate_attribute` and
+//! `conflicting_attribute` are denied, `unknown_attribute` is denied with
+//! `#![options(checked)]` and allowed otherwise, and `unknown_keyword` is
+//! warned about.
+//!
+//! A denied lint aborts the expansion. Proc macros can't emit warnings on
+//! stable, so a warned lint expands to a deprecated constant used at the
+//! offending attribute, which the compiler reports as a `deprecated` warning
+//! with the lint's message. Like the options, the levels are kept in a
+//! thread-local while an invocation is expanded.
+
+use std::cell::{Cell, RefCell};
+
+use proc_macro_error2::abort;
+use proc_macro2::{Span, TokenStream};
+use quote::quote_spanned;
+use serde::Deserialize;
+use syn::Ident;
+
+use crate::ast::Attribute;
+use crate::config::Config;
+use crate::options::current_options;
+use crate::suggest::closest_match;
+
+/// Lints accepted by the `#![allow(..)]`, `#![warn(..)]` and `#![deny(..)]`
+/// headers, in the order of `AttributeLint`.
+const LINTS: &[&str] = &[
+    "unknown_attribute",
+    "duplicate_attribute",
+    "conflicting_attribute",
+    "unknown_keyword",
+];
+
+/// A lint over the attributes of an element.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AttributeLint {
+    /// A `div` attribute close to, but not, one of its methods
+    Unknown,
+    /// An attribute given twice on the same native element
+    Duplicate,
+    /// Mutually exclusive flags on the same element
+    Conflicting,
+    /// A `rounded:` or `shadow:` identifier close to, but not, one of their
+    /// scale keywords, which may as well be a variable
+    Keyword,
+}
+
+impl AttributeLint {
+    const ALL: [Self; 4] = [
+        Self::Unknown,
+        Self::Duplicate,
+        Self::Conflicting,
+        Self::Keyword,
+    ];
+
+    const fn name(self) -> &'static str {
+        LINTS[self as usize]
+    }
+
+    /// The level of the lint in the invocation being expanded: set by its
+    /// headers, then by the configuration file.
+    pub fn level(self) -> Level {
+        let configured = || Config::current().lints().get(self);
+        LINTS_IN_EFFECT
+            .get()
+            .get(self)
+            .or_else(configured)
+            .unwrap_or_else(|| match self {
+                Self::Unknown if !current_options().checked => Level::Allow,
+                Self::Keyword => Level::Warn,
+                _ => Level::Deny,
+            })
+    }
+}
+
+/// How a lint is reported.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Level {
+    Allow,
+    Warn,
+    Deny,
+}
+
+impl Level {
+    /// The level set by the header `name`, if it is a lint level header.
+    pub fn from_header(name: &str) -> Option<Self> {
+        match name {
+            "allow" => Some(Self::Allow),
+            "warn" => Some(Self::Warn),
+            "deny" => Some(Self::Deny),
+            _ => None,
+        }
+    }
+}
+
+/// Lint levels set by the headers of an invocation.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Lints {
+    levels: [Option<Level>; LINTS.len()],
+}
+
+impl Lints {
+    /// Set the level of the lint `name`.
+    pub fn set(&mut self, name: &Ident, level: Level) {
+        let Some(lint) = AttributeLint::ALL
+            .into_iter()
+            .find(|lint| name == lint.name())
+        else {
+            unknown_lint(name);
+        };
+        self.levels[lint as usize] = Some(level);
+    }
+
+    /// The level set for `lint`, if any.
+    pub const fn get(self, lint: AttributeLint) -> Option<Level> {
+        self.levels[lint as usize]
+    }
+}
+
+fn unknown_lint(name: &Ident) -> ! {
+    if let Some(suggestion) = closest_match(&name.to_string(), LINTS.iter().copied()) {
+        abort!(
+            name,
+            "unknown lint `{}`, did you mean `{}`?",
+            name,
+            suggestion
+        );
+    }
+    abort!(
+        name,
+        "unknown lint `{}`, expected one of: {}",
+        name,
+        LINTS.join(", ")
+    );
+}
+
+thread_local! {
+    static LINTS_IN_EFFECT: Cell<Lints> = const { Cell::new(Lints { levels: [None; LINTS.len()] }) };
+    static WARNINGS: RefCell<TokenStream> = RefCell::new(TokenStream::new());
+}
+
+/// Run `f` with `lints` in effect, restoring the previous levels after.
+/// Returns the result of `f` and the warnings it reported.
+pub fn with_lints<R>(lints: Lints, f: impl FnOnce() -> R) -> (R, TokenStream) {
+    let previous = LINTS_IN_EFFECT.replace(lints);
+    let previous_warnings = WARNINGS.take();
+    let result = f();
+    LINTS_IN_EFFECT.set(previous);
+    (result, WARNINGS.replace(previous_warnings))
+}
+
+/// Report `message` at `span` for `lint`, at its level.
+pub fn report(lint: AttributeLint, span: Span, message: &str) {
+    match lint.level() {
+        Level::Allow => {}
+        Level::Warn => {
+            WARNINGS.with_borrow_mut(|warnings| warnings.extend(warning(lint, span, message)));
+        }
+        Level::Deny => abort!(span, "{}", message),
+    }
+}
+
+/// A statement the compiler reports as a `deprecated` warning at `span`.
+fn warning(lint: AttributeLint, span: Span, message: &str) -> TokenStream {
+    let name = Ident::new(lint.name(), span);
+    quote_spanned! {span=>
+        {
+            #[deprecated(note = #message)]
+            #[allow(non_upper_case_globals)]
+            const #name: () = ();
+            let () = #name;
+        }
+    }
+}
+
+/// Attributes that accumulate rather than overwrite, so repeating them is
+/// intentional.
+const REPEATABLE_ATTRIBUTES: &[&str] = &[
+    "when",
+    "when_some",
+    "when_none",
+    "when_else",
+    "map",
+    "child",
+    "children",
+    "use",
+    "apply",
+];
+
+/// Prefixes of repeatable attributes (event listeners).
+const REPEATABLE_PREFIXES: &[&str] = &["on_", "capture_"];
+
+/// Groups of mutually exclusive `Styled` flags: each sets the same style
+/// property, so only the last one takes effect.
+const EXCLUSIVE_FLAGS: &[&[&str]] = &[
+    &["flex", "block", "grid", "hidden"],
+    &["absolute", "relative"],
+    &["visible", "invisible"],
+    &[
+        "flex_row",
+        "flex_row_reverse",
+        "flex_col",
+        "flex_col_reverse",
+    ],
+    &["flex_wrap", "flex_wrap_reverse", "flex_nowrap"],
+    &["overflow_hidden", "overflow_scroll"],
+    &["overflow_x_hidden", "overflow_x_scroll"],
+    &["overflow_y_hidden", "overflow_y_scroll"],
+    &["items_start", "items_end", "items_center", "items_baseline"],
+    &[
+        "justify_start",
+        "justify_end",
+        "justify_center",
+        "justify_between",
+        "justify_around",
+    ],
+    &["text_left", "text_center", "text_right"],
+    &["whitespace_normal", "whitespace_nowrap"],
+];
+
+fn is_repeatable(key: &Ident) -> bool {
+    let key = key.to_string();
+    REPEATABLE_ATTRIBUTES.contains(&key.as_str())
+        || REPEATABLE_PREFIXES
+            .iter()
+            .any(|prefix| key.starts_with(prefix))
+}
+
+/// Report attributes specified more than once on the same native element,
+/// since later values silently win. Components aren't checked: their builder
+/// methods may add rather than set, like a menu's `item`.
+///
+/// Each pseudo-state, platform and compound block is checked on its own, as
+/// overriding a base style there is the point of the block. Props are checked
+/// apart from attributes.
+pub fn check_duplicate_attributes(attributes: &[Attribute]) {
+    let mut seen: Vec<(&Ident, bool)> = vec![];
+
+    for attr in attributes {
+        let (key, is_prop) = match attr {
+            Attribute::Flag(key) | Attribute::KeyValue { key, .. } => (key, false),
+            Attribute::Prop { key, .. } => (key, true),
+            Attribute::PseudoState {
+                state: key,
+                attributes,
+                ..
+            }
+            | Attribute::Compound { key, attributes } => {
+                check_duplicate_attributes(attributes);
+                (key, false)
+            }
+            Attribute::Platform { attributes, .. } => {
+                check_duplicate_attributes(attributes);
+                continue;
+            }
+            // Listeners accumulate
+            Attribute::Event { .. } => continue,
+        };
+
+        if is_repeatable(key) {
+            continue;
+        }
+
+        if seen.contains(&(key, is_prop)) {
+            let prefix = if is_prop { ":" } else { "" };
+            report(
+                AttributeLint::Duplicate,
+                key.span(),
+                &format!("duplicate attribute `{prefix}{key}`, later values override earlier ones"),
+            );
+        }
+        seen.push((key, is_prop));
+    }
+}
+
+/// Report mutually exclusive flags on the same element, e.g. `flex` and
+/// `block`.
+///
+/// Like duplicates, pseudo-state and platform blocks are checked on their own.
+pub fn check_conflicting_attributes(attributes: &[Attribute]) {
+    let mut seen: Vec<(&Ident, usize)> = vec![];
+
+    for attr in attributes {
+        let flag = match attr {
+            Attribute::Flag(flag) => flag,
+            Attribute::Compound { key, attributes } => {
+                check_conflicting_attributes(attributes);
+                key
+            }
+            Attribute::PseudoState { attributes, .. } | Attribute::Platform { attributes, .. } => {
+                check_conflicting_attributes(attributes);
+                continue;
+            }
+            Attribute::KeyValue { .. } | Attribute::Event { .. } | Attribute::Prop { .. } => {
+                continue;
+            }
+        };
+
+        let name = flag.to_string();
+        let Some(group) = EXCLUSIVE_FLAGS
+            .iter()
+            .position(|group| group.contains(&name.as_str()))
+        else {
+            continue;
+        };
+
+        if let Some((other, _)) = seen.iter().find(|(_, other_group)| *other_group == group) {
+            report(
+                AttributeLint::Conflicting,
+                flag.span(),
+                &format!(
+                    "conflicting attributes `{other}` and `{flag}`, only the last one takes effect"
+                ),
+            );
+        }
+        seen.push((flag, group));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use quote::quote;
+    use syn::parse2;
+
+    use super::*;
+    use crate::ast::Markup;
+
+    #[test]
+    fn test_exclusive_flags_are_unique() {
+        let mut flags: Vec<_> = EXCLUSIVE_FLAGS.iter().copied().flatten().collect();
+        let len = flags.len();
+        flags.sort_unstable();
+        flags.dedup();
+        assert_eq!(flags.len(), len);
+    }
+
+    #[test]
+    fn test_repeatable_attributes() {
+        let input = quote! {
+            div @[id: "a", when: (a, f), when: (b, g), on_click: h, on_click: i, map: j, map: k] {}
+        };
+        assert!(parse2::<Markup>(input).is_ok());
+    }
+
+    #[test]
+    fn test_repeated_component_builders() {
+        let input = quote! {
+            Menu @[item: a, item: b, tooltip: t, tooltip: u, :entry: c, :entry: d] {}
+        };
+        assert!(parse2::<Markup>(input).is_ok());
+    }
+
+    #[test]
+    fn test_overrides_in_blocks() {
+        let input = quote! {
+            div @[pt: px(8.0), flex, macos: { pt: px(28.0), block }] @hover[pt: px(4.0)] {}
+        };
+        assert!(parse2::<Markup>(input).is_ok());
+    }
+
+    #[test]
+    fn test_allowed_lints() {
+        let input = quote! {
+            #![allow(duplicate_attribute, conflicting_attribute)]
+            div @[id: "a", id: "b", flex, block] {}
+        };
+        let markup = parse2::<Markup>(input).unwrap();
+        assert!(markup.warnings.is_empty());
+    }
+
+    #[test]
+    fn test_warned_lints() {
+        let input = quote! {
+            #![deny(duplicate_attribute)]
+            #![warn(duplicate_attribute)]
+            div @[id: "a", id: "b"] {}
+        };
+        let markup = parse2::<Markup>(input).unwrap();
+        let warnings = markup.warnings.to_string();
+        assert!(warnings.contains("deprecated"), "{warnings}");
+        assert!(warnings.contains("duplicate_attribute"), "{warnings}");
+    }
+
+    #[test]
+    fn test_default_levels() {
+        assert_eq!(AttributeLint::Duplicate.level(), Level::Deny);
+        assert_eq!(AttributeLint::Unknown.level(), Level::Allow);
+        let mut lints = Lints::default();
+        lints.set(
+            &Ident::new("unknown_attribute", Span::call_site()),
+            Level::Warn,
+        );
+        let (level, warnings) = with_lints(lints, || AttributeLint::Unknown.level());
+        assert_eq!(level, Level::Warn);
+        assert!(warnings.is_empty());
+        assert_eq!(AttributeLint::Unknown.level(), Level::Allow);
+    }
+}
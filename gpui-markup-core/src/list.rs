@@ -0,0 +1,148 @@
+//! Virtualized lists, with the constructor arguments kept as the children of
+//! the element like `canvas`:
+//!
+//! - `list(state) { |ix, window, cx| .. }` renders items of a `ListState` with
+//!   `gpui::list`. The state can also be given as `list @[state: ..] { .. }`.
+//! - `uniform_list("files", count) |range, window, cx| { .. }` renders the
+//!   children for a range of items with `gpui::uniform_list`.
+
+use proc_macro_error2::abort;
+use quote::quote;
+use syn::parse::ParseStream;
+use syn::{Expr, Ident, Result, braced, parse_quote};
+
+use crate::ast::{Attribute, Child, Element, MarkupVec};
+use crate::closure::parse_closure_inputs;
+use crate::config::Config;
+use crate::parser::parse_children;
+
+/// Normalize a `list` into its constructor arguments: the state, cloned since
+/// a `ListState` is a shared handle, and the item renderer.
+///
+/// `sizing_behavior: ..` is renamed to the `with_sizing_behavior` method.
+pub fn list_arguments(
+    name: &Ident,
+    arguments: Option<Vec<Expr>>,
+    attributes: &mut Vec<Attribute>,
+    children: Vec<Child>,
+) -> Vec<Child> {
+    let attribute = attributes
+        .iter()
+        .position(|attr| matches!(attr, Attribute::KeyValue { key, .. } if key == "state"))
+        .map(|index| attributes.remove(index));
+    let state = arguments.map(|arguments| {
+        let mut arguments = arguments.into_iter();
+        match (arguments.next(), arguments.next()) {
+            (Some(state), None) => state,
+            _ => abort!(name, "`list` takes a single argument, its `ListState`"),
+        }
+    });
+    let state = match (state, attribute) {
+        (Some(_), Some(Attribute::KeyValue { key, .. })) => {
+            abort!(key, "`list` state is already given as `list(state)`");
+        }
+        (Some(state), _) | (None, Some(Attribute::KeyValue { value: state, .. })) => state,
+        _ => abort!(
+            name,
+            "`list` requires a `ListState`, e.g. `list(self.state) { .. }` or `list @[state: self.state] { .. }`"
+        ),
+    };
+
+    for attr in attributes.iter_mut() {
+        if let Attribute::KeyValue { key, .. } = attr
+            && key == "sizing_behavior"
+        {
+            *key = Ident::new("with_sizing_behavior", key.span());
+        }
+    }
+
+    let Some(render) = renderer(children) else {
+        abort!(
+            name.span(),
+            "list must have exactly one child, its item renderer, e.g. `list(state) { |ix, window, cx| .. }`"
+        );
+    };
+
+    vec![
+        Child::expression(parse_quote! { ::std::clone::Clone::clone(&#state) }),
+        Child::expression(render_item(render)),
+    ]
+}
+
+/// Normalize a `uniform_list` into its constructor arguments: the id, the
+/// item count and the range renderer.
+pub fn uniform_list_arguments(
+    name: &Ident,
+    arguments: Option<Vec<Expr>>,
+    children: Vec<Child>,
+) -> Vec<Child> {
+    let Some(arguments) = arguments.filter(|arguments| arguments.len() == 2) else {
+        abort!(
+            name,
+            "`uniform_list` takes an id and an item count, e.g. `uniform_list(\"files\", count) |range, window, cx| { .. }`"
+        );
+    };
+    let Some(render) = renderer(children) else {
+        abort!(
+            name,
+            "`uniform_list` requires a range renderer, e.g. `uniform_list(\"files\", count) |range, window, cx| { .. }`"
+        );
+    };
+    arguments
+        .into_iter()
+        .chain([render])
+        .map(Child::expression)
+        .collect()
+}
+
+/// The renderer of a list: its only child, an expression.
+fn renderer(children: Vec<Child>) -> Option<Expr> {
+    if children.len() != 1 {
+        return None;
+    }
+    match children.into_iter().next() {
+        Some(Child::Element(Element::Expression(render))) => Some(render.expr),
+        _ => None,
+    }
+}
+
+/// Parse the range renderer of a `uniform_list`: `|range, window, cx|`
+/// followed by the children rendered for the range, built into a
+/// `Vec<AnyElement>`.
+pub fn parse_range_renderer(input: ParseStream) -> Result<Vec<Child>> {
+    let inputs = parse_closure_inputs(input)?;
+    if inputs.len() != 3 {
+        abort!(
+            inputs,
+            "the range renderer takes 3 parameters: `|range, window, cx|`"
+        );
+    }
+    let content;
+    braced!(content in input);
+    let children = MarkupVec {
+        children: parse_children(&content)?,
+    };
+    Ok(vec![Child::expression(Expr::Verbatim(quote! {
+        move |#inputs| #children
+    }))])
+}
+
+/// Convert the result of an item renderer closure into an `AnyElement`, so it
+/// can return any element.
+fn render_item(expr: Expr) -> Expr {
+    let Expr::Closure(mut closure) = expr else {
+        return expr;
+    };
+    if closure.inputs.len() != 3 {
+        abort!(
+            closure.or1_token,
+            "the item renderer takes 3 parameters: `|ix, window, cx|`"
+        );
+    }
+    let into_element = Config::current().item("IntoElement");
+    let body = &closure.body;
+    closure.body = Box::new(parse_quote! {
+        #into_element::into_any_element(#body)
+    });
+    Expr::Closure(closure)
+}
@@ -0,0 +1,231 @@
+//! `markdown: source` children.
+//!
+//! Supports a small Markdown subset: `#` headings, paragraphs, `-`/`*` and
+//! numbered lists, fenced code blocks, and inline `**strong**`, `*emphasis*`,
+//! `` `code` `` and `[links](url)` (rendered as underlined text).
+//!
+//! A string literal is rendered to GPUI elements at compile time. Any other
+//! source is parsed when the element is built, by the `gpui-markup-markdown`
+//! crate that `gpui-markup` re-exports.
+
+use std::ops::Range;
+
+use gpui_markup_markdown::{Block, Inline, dedent, parse_blocks, parse_styled};
+use proc_macro2::{Ident, Span, TokenStream};
+use quote::{quote, quote_spanned};
+use syn::Expr;
+use syn::spanned::Spanned;
+
+use crate::config::Config;
+
+/// The element tree for a Markdown source string.
+pub fn render(source: &str) -> TokenStream {
+    let gpui = Config::current().gpui();
+    let styled = Config::current().item("Styled");
+    let parent_element = Config::current().item("ParentElement");
+    let blocks = parse_blocks(&dedent(source));
+    let blocks = blocks.iter().map(|block| render_block(&gpui, block));
+    let container = Ident::new("__markdown", Span::mixed_site());
+
+    quote! {
+        {
+            let #container = #styled::gap_2(
+                #styled::flex_col(#styled::flex(#gpui::div())),
+            );
+            #(let #container = #parent_element::child(#container, #blocks);)*
+            #container
+        }
+    }
+}
+
+fn render_block(gpui: &TokenStream, block: &Block) -> TokenStream {
+    let parent_element = Config::current().item("ParentElement");
+    let styled = Config::current().item("Styled");
+    let div = quote! { #gpui::div() };
+    match block {
+        Block::Heading(level, text) => {
+            let size = match level {
+                1 => quote! { text_2xl },
+                2 => quote! { text_xl },
+                3 => quote! { text_lg },
+                _ => quote! { text_base },
+            };
+            let text = render_inline(gpui, text);
+            quote! {
+                #parent_element::child(
+                    #styled::font_weight(#styled::#size(#div), #gpui::FontWeight::BOLD),
+                    #text,
+                )
+            }
+        }
+        Block::Paragraph(text) => {
+            let text = render_inline(gpui, text);
+            quote! { #parent_element::child(#div, #text) }
+        }
+        Block::List { ordered, items } => {
+            let items = items.iter().enumerate().map(|(index, item)| {
+                let marker = if *ordered {
+                    format!("{}.", index + 1)
+                } else {
+                    "•".to_owned()
+                };
+                let item = render_inline(gpui, item);
+                quote! {
+                    #parent_element::child(
+                        #parent_element::child(
+                            #styled::gap_1(#styled::flex(#div)),
+                            #marker,
+                        ),
+                        #parent_element::child(#div, #item),
+                    )
+                }
+            });
+            quote! {
+                #parent_element::children(
+                    #styled::gap_1(#styled::flex_col(#styled::flex(#div))),
+                    [#(#items),*],
+                )
+            }
+        }
+        Block::Code(code) => quote! {
+            #parent_element::child(
+                #styled::bg(
+                    #styled::rounded_md(#styled::p_2(
+                        #styled::font_family(#div, "monospace"),
+                    )),
+                    #gpui::hsla(0.0, 0.0, 0.5, 0.15),
+                ),
+                #code,
+            )
+        },
+    }
+}
+
+/// Inline text as a string literal, or a `StyledText` if it has any markup.
+fn render_inline(gpui: &TokenStream, source: &str) -> TokenStream {
+    let (text, styles) = parse_styled(source);
+
+    let highlights = styles.iter().map(|(range, styles)| {
+        let Range { start, end } = range;
+        let fields = styles.iter().map(|style| match style {
+            Inline::Strong => quote! { font_weight: ::core::option::Option::Some(#gpui::FontWeight::BOLD), },
+            Inline::Emphasis => quote! { font_style: ::core::option::Option::Some(#gpui::FontStyle::Italic), },
+            Inline::Code => {
+                quote! { background_color: ::core::option::Option::Some(#gpui::hsla(0.0, 0.0, 0.5, 0.15)), }
+            }
+            Inline::Link => quote! {
+                underline: ::core::option::Option::Some(#gpui::UnderlineStyle {
+                    thickness: #gpui::px(1.0),
+                    ..::core::default::Default::default()
+                }),
+            },
+        });
+        quote! {
+            (#start..#end, #gpui::HighlightStyle {
+                #(#fields)*
+                ..::core::default::Default::default()
+            })
+        }
+    });
+
+    if styles.is_empty() {
+        quote! { #text }
+    } else {
+        quote! { #gpui::StyledText::new(#text).with_highlights([#(#highlights),*]) }
+    }
+}
+
+/// The element tree for a Markdown source only known at runtime.
+///
+/// Mirrors [`render`], dedenting and parsing the source when the element is
+/// built.
+pub fn render_dynamic(source: &Expr) -> TokenStream {
+    let gpui = Config::current().gpui();
+    let styled = Config::current().item("Styled");
+    let parent_element = Config::current().item("ParentElement");
+    let into_element = Config::current().item("IntoElement");
+    let markdown = quote_spanned! {source.span()=> ::gpui_markup::__markdown };
+    let inline = Ident::new("__inline", Span::mixed_site());
+    let div = quote! { #gpui::div() };
+
+    quote! {
+        {
+            let #inline = |source: &str| {
+                let (text, styles) = #markdown::parse_styled(source);
+                let highlights = styles.into_iter().map(|(range, styles)| {
+                    let mut highlight = <#gpui::HighlightStyle as ::core::default::Default>::default();
+                    for style in styles {
+                        match style {
+                            #markdown::Inline::Strong => {
+                                highlight.font_weight = ::core::option::Option::Some(#gpui::FontWeight::BOLD);
+                            }
+                            #markdown::Inline::Emphasis => {
+                                highlight.font_style = ::core::option::Option::Some(#gpui::FontStyle::Italic);
+                            }
+                            #markdown::Inline::Code => {
+                                highlight.background_color = ::core::option::Option::Some(#gpui::hsla(0.0, 0.0, 0.5, 0.15));
+                            }
+                            #markdown::Inline::Link => {
+                                highlight.underline = ::core::option::Option::Some(#gpui::UnderlineStyle {
+                                    thickness: #gpui::px(1.0),
+                                    ..::core::default::Default::default()
+                                });
+                            }
+                        }
+                    }
+                    (range, highlight)
+                });
+                #gpui::StyledText::new(text).with_highlights(highlights)
+            };
+            let blocks = #markdown::parse_blocks(&#markdown::dedent(
+                ::core::convert::AsRef::<str>::as_ref(&(#source)),
+            ));
+            #parent_element::children(
+                #styled::gap_2(#styled::flex_col(#styled::flex(#div))),
+                blocks.into_iter().map(|block| match block {
+                    #markdown::Block::Heading(level, text) => {
+                        let heading = #styled::font_weight(#div, #gpui::FontWeight::BOLD);
+                        let heading = match level {
+                            1 => #styled::text_2xl(heading),
+                            2 => #styled::text_xl(heading),
+                            3 => #styled::text_lg(heading),
+                            _ => #styled::text_base(heading),
+                        };
+                        #into_element::into_any_element(#parent_element::child(heading, #inline(&text)))
+                    }
+                    #markdown::Block::Paragraph(text) => {
+                        #into_element::into_any_element(#parent_element::child(#div, #inline(&text)))
+                    }
+                    #markdown::Block::List { ordered, items } => {
+                        let items = items.into_iter().enumerate().map(|(index, item)| {
+                            let marker = if ordered {
+                                ::std::format!("{}.", index + 1)
+                            } else {
+                                ::std::borrow::ToOwned::to_owned("•")
+                            };
+                            #parent_element::child(
+                                #parent_element::child(#styled::gap_1(#styled::flex(#div)), marker),
+                                #parent_element::child(#div, #inline(&item)),
+                            )
+                        });
+                        #into_element::into_any_element(#parent_element::children(
+                            #styled::gap_1(#styled::flex_col(#styled::flex(#div))),
+                            items,
+                        ))
+                    }
+                    #markdown::Block::Code(code) => {
+                        #into_element::into_any_element(#parent_element::child(
+                            #styled::bg(
+                                #styled::rounded_md(#styled::p_2(
+                                    #styled::font_family(#div, "monospace"),
+                                )),
+                                #gpui::hsla(0.0, 0.0, 0.5, 0.15),
+                            ),
+                            code,
+                        ))
+                    }
+                }),
+            )
+        }
+    }
+}
@@ -0,0 +1,144 @@
+//! `memo(key) { .. }`: a subtree rendered again only when its key changes.
+//!
+//! ```ignore
+//! ui! { memo(self.version) @[h: px(320.)] { Chart(self.points.clone()) {} } }
+//! ```
+//!
+//! gpui consumes elements when they are painted, so the subtree can't be kept
+//! as an `AnyElement`. It is rendered by a small view instead, drawn as a
+//! cached `AnyView`: gpui reuses its layout and paint from the previous frame
+//! until a new view is created for a new key (or its bounds change). The
+//! subtree becomes a `move` closure owned by the view, so it can only capture
+//! owned values, like the tooltip builders.
+//!
+//! A cached view is laid out without its content: the attributes of `memo`
+//! style its box on top of `size_full`. The cache lives in element state keyed
+//! by the source location, or by `memo(key, id)` for memos rendered in a loop.
+//! Like the constructor arguments of `canvas`, the key and id are kept as the
+//! first children of the element.
+
+use proc_macro_error2::abort;
+use proc_macro2::{Span, TokenStream};
+use quote::{quote, quote_spanned};
+use syn::{Expr, Ident};
+
+use crate::ast::{Attribute, Child};
+use crate::config::Config;
+
+/// Move the key and id of `memo(key, id)` in front of its child.
+pub fn memo_arguments(
+    name: &Ident,
+    arguments: Option<Vec<Expr>>,
+    attributes: &[Attribute],
+    children: Vec<Child>,
+) -> Vec<Child> {
+    let arguments = arguments.unwrap_or_default();
+    if arguments.is_empty() {
+        abort!(
+            name,
+            "`memo` requires a key, e.g. `memo(self.version) { .. }`"
+        );
+    }
+    if let Some(extra) = arguments.get(2) {
+        abort!(extra, "`memo(key, id)` takes a key and an optional id");
+    }
+    if children.len() != 1 {
+        abort!(name, "`memo` must have exactly one child");
+    }
+    if !matches!(children[0], Child::Element(_)) {
+        abort!(name, "`memo` child must be an element");
+    }
+    if let Some(key) = attributes.iter().find_map(|attr| match attr {
+        Attribute::KeyValue { key, .. } if key == "id" => Some(key),
+        _ => None,
+    }) {
+        abort!(
+            key,
+            "the id of a `memo` is its second argument, `memo(key, id)`"
+        );
+    }
+    arguments
+        .into_iter()
+        .map(Child::expression)
+        .chain(children)
+        .collect()
+}
+
+/// The constructor of the style of a memo's box.
+pub fn memo_style() -> TokenStream {
+    let gpui = Config::current().gpui();
+    quote! { #gpui::StyleRefinement::default().size_full() }
+}
+
+/// Render the child of a memo through a cached view, given its key (and id)
+/// and the generated style of its box.
+pub fn memo(name: &Ident, children: &[Child], style: &TokenStream) -> TokenStream {
+    let gpui = Config::current().gpui();
+    let into_element = Config::current().item("IntoElement");
+    let Some((child, arguments)) = children.split_last() else {
+        abort!(name, "`memo` must have exactly one child");
+    };
+    let Some(key) = arguments.first() else {
+        abort!(
+            name,
+            "`memo` requires a key, e.g. `memo(self.version) { .. }`"
+        );
+    };
+    let key = key.element();
+    let id = arguments.get(1).and_then(Child::element).map_or_else(
+        || {
+            // The location of the `memo` tag in the caller's source
+            quote_spanned! {name.span()=>
+                #gpui::ElementId::CodeLocation(*::core::panic::Location::caller())
+            }
+        },
+        |id| quote! { #gpui::ElementId::from(#id) },
+    );
+    let child = child.element();
+    let view = Ident::new("__MemoView", Span::mixed_site());
+    let function = Ident::new("__memo", Span::mixed_site());
+    // Called outside the block declaring it, so the key and the child don't
+    // see its items
+    quote! {
+        ({
+            struct #view(::std::boxed::Box<dyn Fn() -> #gpui::AnyElement>);
+            impl #gpui::Render for #view {
+                fn render(
+                    &mut self,
+                    _: &mut #gpui::Window,
+                    _: &mut #gpui::Context<Self>,
+                ) -> impl #into_element {
+                    (self.0)()
+                }
+            }
+            fn #function<K: PartialEq + 'static>(
+                key: K,
+                id: #gpui::ElementId,
+                style: #gpui::StyleRefinement,
+                build: impl Fn() -> #gpui::AnyElement + 'static,
+                window: &mut #gpui::Window,
+                cx: &mut #gpui::App,
+            ) -> #gpui::AnyView {
+                let state = window.use_keyed_state(id, cx, |_, _| None::<(K, #gpui::Entity<#view>)>);
+                let cached = match state.read(cx) {
+                    Some((cached, view)) if *cached == key => Some(view.clone()),
+                    _ => None,
+                };
+                let view = cached.unwrap_or_else(|| {
+                    let view = #gpui::AppContext::new(cx, |_| #view(::std::boxed::Box::new(build)));
+                    state.update(cx, |state, _| *state = Some((key, view.clone())));
+                    view
+                });
+                #gpui::AnyView::from(view).cached(style)
+            }
+            #function
+        })(
+            #key,
+            #id,
+            #style,
+            move || #into_element::into_any_element(#child),
+            window,
+            cx,
+        )
+    }
+}
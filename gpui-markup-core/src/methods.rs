@@ -0,0 +1,375 @@
+//! The methods of a gpui `div`, for suggestions on misspelled attributes.
+//!
+//! ```ignore
+//! ui! {
+//!     #![options(checked)]
+//!     div @[widht: px(120.), bg_color: red()] {}
+//!     // error: unknown attribute `widht` on `div`, did you mean `width`?
+//! }
+//! ```
+//!
+//! An attribute is a method call, so the compiler reports a misspelled one as
+//! a missing method, without a suggestion when the method comes from a trait
+//! like `Styled`. With `#![options(checked)]` or `#![deny(unknown_attribute)]`,
+//! the attributes of `div` and the elements built on it are looked up in the
+//! methods of `Styled`, `InteractiveElement`, `StatefulInteractiveElement` and
+//! `FluentBuilder`, and the attributes of this crate. A name that isn't one of
+//! them but is close to one is reported with the suggestion, as the
+//! `unknown_attribute` lint, see `lint`. Other unknown names, like the methods
+//! of extension traits, are left to the compiler.
+//!
+//! Most `Styled` methods are generated by gpui from a prefix and a suffix
+//! (`p_2`, `rounded_t_md`, `mx_neg_4`), so the names are kept the same way.
+
+use syn::Ident;
+
+use crate::ast::Attribute;
+use crate::lint::{AttributeLint, report};
+use crate::shorthand::{BORDER_WIDTHS, ROUNDED_KEYWORDS, SHADOW_KEYWORDS, SPACING_STEPS};
+use crate::suggest::closest_match;
+
+/// Native elements with the methods of a `div`.
+const DIV_ELEMENTS: &[&str] = &["div", "h_flex", "v_flex", "stack", "spacer", "divider"];
+
+/// Methods of `Styled` not generated from a prefix, and of the interactive
+/// traits and `FluentBuilder`.
+const METHODS: &[&str] = &[
+    // Styled
+    "block",
+    "flex",
+    "grid",
+    "hidden",
+    "visible",
+    "invisible",
+    "relative",
+    "absolute",
+    "whitespace_normal",
+    "whitespace_nowrap",
+    "text_ellipsis",
+    "text_overflow",
+    "text_align",
+    "text_left",
+    "text_center",
+    "text_right",
+    "truncate",
+    "line_clamp",
+    "flex_col",
+    "flex_col_reverse",
+    "flex_row",
+    "flex_row_reverse",
+    "flex_1",
+    "flex_auto",
+    "flex_initial",
+    "flex_none",
+    "flex_basis",
+    "flex_grow",
+    "flex_shrink",
+    "flex_shrink_0",
+    "flex_wrap",
+    "flex_wrap_reverse",
+    "flex_nowrap",
+    "items_start",
+    "items_end",
+    "items_center",
+    "items_baseline",
+    "justify_start",
+    "justify_end",
+    "justify_center",
+    "justify_between",
+    "justify_around",
+    "content_normal",
+    "content_center",
+    "content_start",
+    "content_end",
+    "content_between",
+    "content_around",
+    "content_evenly",
+    "content_stretch",
+    "overflow_hidden",
+    "overflow_x_hidden",
+    "overflow_y_hidden",
+    "bg",
+    "border_color",
+    "border_dashed",
+    "text_style",
+    "text_color",
+    "text_bg",
+    "text_size",
+    "text_xs",
+    "text_sm",
+    "text_base",
+    "text_lg",
+    "text_xl",
+    "text_2xl",
+    "text_3xl",
+    "font",
+    "font_family",
+    "font_weight",
+    "italic",
+    "not_italic",
+    "underline",
+    "line_through",
+    "line_height",
+    "text_decoration_none",
+    "text_decoration_color",
+    "text_decoration_solid",
+    "text_decoration_wavy",
+    "text_decoration_0",
+    "text_decoration_1",
+    "text_decoration_2",
+    "text_decoration_4",
+    "text_decoration_8",
+    "opacity",
+    "grid_cols",
+    "grid_rows",
+    "col_start",
+    "col_start_auto",
+    "col_end",
+    "col_end_auto",
+    "col_span",
+    "col_span_full",
+    "row_start",
+    "row_start_auto",
+    "row_end",
+    "row_end_auto",
+    "row_span",
+    "row_span_full",
+    "cursor",
+    "cursor_default",
+    "cursor_pointer",
+    "cursor_text",
+    "cursor_move",
+    "cursor_not_allowed",
+    "cursor_context_menu",
+    "cursor_crosshair",
+    "cursor_vertical_text",
+    "cursor_alias",
+    "cursor_copy",
+    "cursor_no_drop",
+    "cursor_grab",
+    "cursor_grabbing",
+    "cursor_ew_resize",
+    "cursor_ns_resize",
+    "cursor_nesw_resize",
+    "cursor_nwse_resize",
+    "cursor_col_resize",
+    "cursor_row_resize",
+    "cursor_n_resize",
+    "cursor_e_resize",
+    "cursor_s_resize",
+    "cursor_w_resize",
+    "cursor_none",
+    "debug",
+    "debug_below",
+    // InteractiveElement and StatefulInteractiveElement
+    "group",
+    "id",
+    "track_focus",
+    "tab_stop",
+    "tab_index",
+    "tab_group",
+    "key_context",
+    "hover",
+    "group_hover",
+    "debug_selector",
+    "drag_over",
+    "group_drag_over",
+    "can_drop",
+    "occlude",
+    "window_control_area",
+    "block_mouse_except_scroll",
+    "focus",
+    "in_focus",
+    "focusable",
+    "overflow_scroll",
+    "overflow_x_scroll",
+    "overflow_y_scroll",
+    "scrollbar_width",
+    "track_scroll",
+    "anchor_scroll",
+    "active",
+    "group_active",
+    "tooltip",
+    "hoverable_tooltip",
+    "on_mouse_down",
+    "on_any_mouse_down",
+    "on_mouse_up",
+    "on_mouse_down_out",
+    "on_mouse_up_out",
+    "on_mouse_move",
+    "on_drag_move",
+    "on_scroll_wheel",
+    "on_action",
+    "on_boxed_action",
+    "on_key_down",
+    "on_key_up",
+    "on_modifiers_changed",
+    "on_drop",
+    "on_click",
+    "on_drag",
+    "on_hover",
+    "capture_any_mouse_down",
+    "capture_any_mouse_up",
+    "capture_action",
+    "capture_key_down",
+    "capture_key_up",
+    "on_children_prepainted",
+    "image_cache",
+    // FluentBuilder
+    "map",
+    "when",
+    "when_else",
+    "when_some",
+    "when_none",
+    // Attributes of this crate, and their aliases
+    "use",
+    "style",
+    "apply",
+    "bind",
+    "drag",
+    "scroll",
+    "action",
+    "focus_trap",
+    "child",
+    "children",
+    "width",
+    "height",
+    "background",
+    "padding",
+    "margin",
+    "color",
+];
+
+/// Prefixes of the length methods of `Styled`, each also taking the suffixes
+/// in `SPACING_STEPS` and `LENGTH_SUFFIXES`, negated or not (`mx_neg_4`).
+const LENGTH_PREFIXES: &[&str] = &[
+    "m", "mt", "mb", "my", "mx", "ml", "mr", "p", "pt", "pb", "px", "py", "pl", "pr", "inset",
+    "top", "bottom", "left", "right", "w", "h", "size", "min_w", "min_h", "max_w", "max_h", "gap",
+    "gap_x", "gap_y",
+];
+
+/// Length suffixes other than the steps of the spacing scale.
+const LENGTH_SUFFIXES: &[&str] = &[
+    "auto", "px", "full", "1_2", "1_3", "2_3", "1_4", "2_4", "3_4", "1_5", "2_5", "3_5", "4_5",
+    "1_6", "5_6", "1_12",
+];
+
+/// Prefixes of the corner radius methods, taking `ROUNDED_KEYWORDS`.
+const CORNER_PREFIXES: &[&str] = &[
+    "rounded",
+    "rounded_t",
+    "rounded_b",
+    "rounded_r",
+    "rounded_l",
+    "rounded_tl",
+    "rounded_tr",
+    "rounded_bl",
+    "rounded_br",
+];
+
+/// Prefixes of the border width methods, taking `BORDER_WIDTHS`.
+const BORDER_PREFIXES: &[&str] = &[
+    "border", "border_t", "border_b", "border_r", "border_l", "border_x", "border_y",
+];
+
+/// Every method of a `div` known to this crate.
+fn div_methods() -> Vec<String> {
+    let mut methods: Vec<String> = METHODS.iter().map(ToString::to_string).collect();
+    for prefix in LENGTH_PREFIXES {
+        methods.push((*prefix).to_string());
+        for suffix in SPACING_STEPS.iter().chain(LENGTH_SUFFIXES) {
+            methods.push(format!("{prefix}_{suffix}"));
+            methods.push(format!("{prefix}_neg_{suffix}"));
+        }
+    }
+    let suffixed = [
+        (CORNER_PREFIXES, ROUNDED_KEYWORDS),
+        (BORDER_PREFIXES, BORDER_WIDTHS),
+        (&["shadow"], SHADOW_KEYWORDS),
+    ];
+    for (prefixes, suffixes) in suffixed {
+        for prefix in prefixes {
+            methods.push((*prefix).to_string());
+            methods.extend(suffixes.iter().map(|suffix| format!("{prefix}_{suffix}")));
+        }
+    }
+    methods
+}
+
+/// Report attributes of a `div` that aren't one of its methods but are close
+/// to one.
+pub fn check_unknown_attributes(name: &Ident, attributes: &[Attribute]) {
+    if !DIV_ELEMENTS.iter().any(|element| name == element) {
+        return;
+    }
+    check_attributes(name, attributes, &div_methods());
+}
+
+fn check_attributes(name: &Ident, attributes: &[Attribute], methods: &[String]) {
+    for attr in attributes {
+        match attr {
+            Attribute::Flag(key)
+            | Attribute::KeyValue { key, .. }
+            | Attribute::Compound { key, .. } => check_attribute(name, key, methods),
+            Attribute::PseudoState { attributes, .. } | Attribute::Platform { attributes, .. } => {
+                check_attributes(name, attributes, methods);
+            }
+            Attribute::Prop { .. } | Attribute::Event { .. } => {}
+        }
+    }
+}
+
+fn check_attribute(name: &Ident, key: &Ident, methods: &[String]) {
+    let key_name = key.to_string();
+    if methods.contains(&key_name) {
+        return;
+    }
+    if let Some(suggestion) = suggestion(&key_name, methods) {
+        report(
+            AttributeLint::Unknown,
+            key.span(),
+            &format!("unknown attribute `{key}` on `{name}`, did you mean `{suggestion}`?"),
+        );
+    }
+}
+
+/// The method closest to `name`, or else the longest method `name` extends
+/// with `_` segments, like `bg` for `bg_color`.
+fn suggestion<'a>(name: &str, methods: &'a [String]) -> Option<&'a str> {
+    closest_match(name, methods.iter().map(String::as_str)).or_else(|| {
+        name.match_indices('_')
+            .rev()
+            .find_map(|(end, _)| methods.iter().find(|method| **method == name[..end]))
+            .map(String::as_str)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_div_methods() {
+        let methods = div_methods();
+        for method in [
+            "p_2",
+            "mx_neg_4",
+            "w_1_2",
+            "size_full",
+            "rounded_t_md",
+            "border_b_2",
+        ] {
+            assert!(methods.iter().any(|m| m == method), "{method}");
+        }
+    }
+
+    #[test]
+    fn test_suggestion() {
+        let methods = div_methods();
+        assert_eq!(suggestion("flx", &methods), Some("flex"));
+        assert_eq!(suggestion("widht", &methods), Some("width"));
+        assert_eq!(suggestion("bg_color", &methods), Some("bg"));
+        assert_eq!(suggestion("rounded_mdd", &methods), Some("rounded_md"));
+        assert_eq!(suggestion("on_sumbit", &methods), None);
+    }
+}
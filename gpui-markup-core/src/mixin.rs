@@ -0,0 +1,44 @@
+//! Style mixins: `style![rounded_md, p: px(12.0)]` builds a reusable
+//! `StyleRefinement`, and `use: mixin` applies it to an element. On native
+//! elements `style: r` is an alias of it, for refinements computed elsewhere.
+
+use proc_macro2::{Ident, Span, TokenStream};
+use quote::{ToTokens, quote, quote_spanned};
+use syn::Expr;
+
+use crate::ast::StyleMixin;
+use crate::codegen::{Call, generate_native_element};
+use crate::config::Config;
+
+impl ToTokens for StyleMixin {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let gpui = Config::current().gpui();
+        generate_native_element(
+            &quote! { <#gpui::StyleRefinement as ::std::default::Default>::default() },
+            &self.attributes,
+            &[],
+            tokens,
+        );
+    }
+}
+
+/// Lower `use: mixin` (or `use: (a, b)`) into a `FluentBuilder::map(..)`
+/// refining the element's style with each mixin in turn.
+pub fn use_call(key: &Ident, value: &Expr) -> Call {
+    let gpui = Config::current().gpui();
+    let styled = Config::current().item("Styled");
+    let mixins: Vec<_> = match value {
+        Expr::Tuple(tuple) => tuple.elems.iter().collect(),
+        value => vec![value],
+    };
+    let element = Ident::new("element", Span::mixed_site());
+    Call::Map(
+        key.span(),
+        quote_spanned! {key.span()=>
+            |mut #element| {
+                #(#gpui::Refineable::refine(#styled::style(&mut #element), &#mixins);)*
+                #element
+            }
+        },
+    )
+}
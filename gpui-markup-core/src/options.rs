@@ -0,0 +1,137 @@
+//! Code generation options set by the `#![options(..)]` header:
+//!
+//! ```ignore
+//! ui! {
+//!     #![options(boxed_children)]
+//!     div { Header {}, Body {}, Footer {} }
+//! }
+//! ```
+//!
+//! - `boxed_children`: convert every element child into an `AnyElement` before
+//!   it is added to its parent, so `ParentElement::child` is instantiated once
+//!   rather than for each child type. This costs an allocation per child at
+//!   runtime but cuts monomorphization, which dominates the build time of large
+//!   render functions.
+//! - `linear`: build each element with a sequence of statements, one per
+//!   attribute and child (`let __element = __element.flex();`), rather than one
+//!   nested expression, so a compiler error points at a single small statement.
+//! - `checked`: assert in debug builds that components and expression elements
+//!   given style attributes are `Styled`, see `styled`, and assert that
+//!   expression children are elements, see `child`.
+//!
+//! `strict = true` in `gpui-markup.toml` turns on `checked` in every
+//! invocation.
+//!
+//! Options apply to the code generated for an invocation, so they are kept in
+//! a thread-local while it is expanded.
+
+use std::cell::Cell;
+
+use proc_macro_error2::abort;
+use syn::Ident;
+use syn::parse::{Parse, ParseStream};
+use syn::punctuated::Punctuated;
+
+use crate::config::Config;
+use crate::suggest::closest_match;
+
+/// Options accepted by `#![options(..)]`.
+const OPTIONS: &[&str] = &["boxed_children", "linear", "checked"];
+
+/// Code generation options of an invocation.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Options {
+    /// Convert element children into `AnyElement`s before adding them
+    pub boxed_children: bool,
+    /// Build elements with a statement per attribute and child
+    pub linear: bool,
+    /// Assert that elements given style attributes are `Styled`
+    pub checked: bool,
+}
+
+impl Parse for Options {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut options = Self::default();
+        for name in Punctuated::<Ident, syn::Token![,]>::parse_terminated(input)? {
+            if name == "boxed_children" {
+                options.boxed_children = true;
+            } else if name == "linear" {
+                options.linear = true;
+            } else if name == "checked" {
+                options.checked = true;
+            } else {
+                unknown_option(&name);
+            }
+        }
+        Ok(options)
+    }
+}
+
+fn unknown_option(name: &Ident) -> ! {
+    if let Some(suggestion) = closest_match(&name.to_string(), OPTIONS.iter().copied()) {
+        abort!(
+            name,
+            "unknown option `{}`, did you mean `{}`?",
+            name,
+            suggestion
+        );
+    }
+    abort!(
+        name,
+        "unknown option `{}`, expected one of: {}",
+        name,
+        OPTIONS.join(", ")
+    );
+}
+
+thread_local! {
+    static OPTIONS_IN_EFFECT: Cell<Options> = const {
+        Cell::new(Options {
+            boxed_children: false,
+            linear: false,
+            checked: false,
+        })
+    };
+}
+
+/// Run `f` with `options` in effect, restoring the previous options after.
+pub fn with_options<R>(options: Options, f: impl FnOnce() -> R) -> R {
+    let previous = OPTIONS_IN_EFFECT.replace(options);
+    let result = f();
+    OPTIONS_IN_EFFECT.set(previous);
+    result
+}
+
+/// The options of the invocation being expanded, with those the
+/// configuration file turns on everywhere.
+pub fn current_options() -> Options {
+    let mut options = OPTIONS_IN_EFFECT.get();
+    options.checked |= Config::current().strict();
+    options
+}
+
+#[cfg(test)]
+mod tests {
+    use syn::parse_quote;
+
+    use super::*;
+
+    #[test]
+    fn test_parse_options() {
+        let options: Options = parse_quote!(boxed_children);
+        assert!(options.boxed_children);
+        let options: Options = parse_quote!(linear, boxed_children);
+        assert!(options.linear && options.boxed_children);
+    }
+
+    #[test]
+    fn test_with_options() {
+        let options = Options {
+            boxed_children: true,
+            linear: false,
+            checked: false,
+        };
+        with_options(options, || assert!(current_options().boxed_children));
+        assert!(!current_options().boxed_children);
+    }
+}
@@ -0,0 +1,1835 @@
+//! Parser for gpui-markup DSL.
+//!
+//! Syntax:
+//! - `#![listener(cx)]` - invocation header, before the root element
+//! - `div @[flex, w: px(200.0)] { "Content", child }` - native element with
+//!   attrs
+//! - `div { "Content" }` - no attributes
+//! - `div {}` - minimal
+//! - `div @hover[bg: red()] {}` - pseudo-state attributes
+//! - `div @group_hover("row")[bg: red()] {}` - group pseudo-state attributes
+//! - `div @[macos: { pt: px(28.0) }] {}` - platform-conditional attributes
+//! - `div @[grid: (cols: 3, gap: 2)] {}` - composite attributes
+//! - `div @[bg: $surface] {}` - theme tokens
+//! - `div @[id: "b", on:click: |event, window, cx| {}] {}` - event listeners
+//! - `div { "Hello, {name}!" }` - text child with `format!` interpolation
+//! - `div { text: count }` or `div { text(count) }` - text child from a
+//!   `Display` value
+//! - `div { t: "greeting" }` - translated text child
+//! - `div { markdown: source }` - Markdown child (`markdown` feature)
+//! - `div { "one " "two" }` - adjacent string literals joined into one child
+//! - `div { link("Docs", url) }` - clickable text opening a URL
+//! - `div { "Press ", span @[italic] { "Enter" } }` - styled text run
+//! - `rich_text @[id: "log"] { span @[on_click: f] { "here" } }` - clickable
+//!   text ranges
+//! - `deferred { child }` - deferred element
+//! - `Button::new("Hi") @[style: Primary] {}` - expression element
+//! - `(complex + expr) @[style: Primary] {}` - parenthesized expression element
+
+use proc_macro_error2::abort;
+use proc_macro2::{Delimiter, Spacing, Span, TokenStream, TokenTree};
+use syn::buffer::Cursor;
+use syn::ext::IdentExt;
+use syn::parse::{Parse, ParseStream, Parser};
+use syn::punctuated::Punctuated;
+use syn::spanned::Spanned;
+use syn::token::{Brace, Bracket, Paren};
+use syn::{
+    Expr, ExprLit, ExprPath, Ident, Lit, LitStr, Path, Result, Token, Type, braced, bracketed,
+    parenthesized, parse_quote,
+};
+
+use crate::anchored::{check_anchored_attributes, expand_fit_mode};
+use crate::ast::{
+    Attribute, Child, ComponentElement, DeferredElement, Element, ExprElement, Markup, MarkupVec,
+    NativeElement, RichTextElement, SpanElement, StyleMixin,
+};
+use crate::canvas::parse_canvas_callbacks;
+use crate::condition::fold_constant_conditions;
+use crate::config::Config;
+use crate::deferred::check_deferred_attributes;
+use crate::event::{
+    check_event, check_event_argument, check_event_payload, check_event_support, check_modifiers,
+    ensure_stateful, expand_actions,
+};
+use crate::focus::expand_focus;
+use crate::header::Header;
+use crate::layout::expand_layout_element;
+use crate::lazy::check_lazy;
+use crate::link::link_child;
+use crate::lint::{check_conflicting_attributes, check_duplicate_attributes, with_lints};
+use crate::list::{list_arguments, parse_range_renderer, uniform_list_arguments};
+use crate::memo::memo_arguments;
+use crate::portal::portal_arguments;
+use crate::preset::check_preset_props;
+use crate::registry::{check_element_defs, custom_element, custom_tags, with_elements};
+use crate::shorthand::resolve_aliases;
+use crate::suggest::closest_match;
+use crate::text::{display_text, is_text, text_child, translated_text};
+use crate::theme::expand_theme_tokens;
+use crate::tooltip::expand_tooltips;
+use crate::units::expand_units;
+
+const NATIVE_ELEMENTS: &[&str] = &[
+    "div",
+    "h_flex",
+    "v_flex",
+    "stack",
+    "spacer",
+    "divider",
+    "svg",
+    "anchored",
+    "overlay",
+    "portal",
+    "surface",
+    "canvas",
+    "list",
+    "uniform_list",
+    "empty",
+    "memo",
+    "lazy",
+];
+
+/// Native elements that don't implement `ParentElement`, and take only empty
+/// braces. Natives whose braces hold something else, like the callbacks of a
+/// `canvas`, check them where they are lowered.
+const CHILDLESS_ELEMENTS: &[&str] = &["svg"];
+
+/// Native elements taking constructor arguments: `list(state)`,
+/// `uniform_list(id, count)`, `div(id)` for a stateful div, `memo(key)` and
+/// `portal(window)`
+const CALL_ELEMENTS: &[&str] = &["list", "uniform_list", "div", "memo", "portal"];
+
+/// Pseudo-states accepted as `@state[...]`, lowered to `.state(|s| ...)`
+const PSEUDO_STATES: &[&str] = &["hover", "active"];
+
+/// Block forwarding style attributes to a component's base element,
+/// `@base[...]`, lowered to `.map_base(|s| ...)`
+const BASE_STATE: &str = "base";
+
+/// Platforms accepted as `platform: { attrs }`, see `codegen::platform_cfg`
+const PLATFORMS: &[&str] = &["macos", "windows", "linux", "freebsd", "unix"];
+
+/// Group pseudo-states accepted as `@state("group")[...]`, lowered to
+/// `.state("group", |s| ...)`
+const GROUP_PSEUDO_STATES: &[&str] = &["group_hover", "group_active"];
+
+/// Element head (the identifier or expression part before attributes/children)
+enum ElementHead {
+    /// Native element: `div`, `svg`, `anchored`, `overlay`, `surface`,
+    /// `canvas`, `list`, `uniform_list`, `empty`, `memo`, `lazy`
+    Native(Ident),
+    /// Native element with constructor arguments: `list(state)`,
+    /// `uniform_list("files", count)`, `memo(key)`
+    NativeCall(Ident, Vec<Expr>),
+    /// `deferred` element
+    Deferred(Ident),
+    /// Component (uppercase): Header, `ui::Footer`, `Button("Save")`
+    Component(Path, Vec<Expr>),
+    /// `span` inline text run
+    Span(Ident),
+    /// `rich_text` clickable text
+    RichText(Ident),
+    /// Expression element: s, `foo()`, `Button::new()`, (expr)
+    Expression(Expr),
+}
+
+impl ElementHead {
+    fn span(&self) -> proc_macro2::Span {
+        match self {
+            Self::Native(ident)
+            | Self::NativeCall(ident, _)
+            | Self::Deferred(ident)
+            | Self::Span(ident)
+            | Self::RichText(ident) => ident.span(),
+            Self::Component(path, _) => path.span(),
+            Self::Expression(expr) => expr.span(),
+        }
+    }
+}
+
+impl Parse for Markup {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let header = Header::parse(input)?;
+        check_element_defs(&Config::current().elements(), is_builtin_element);
+        check_element_defs(header.elements(), is_builtin_element);
+        let (element, warnings) = with_lints(header.lints(), || {
+            with_elements(header.elements(), || parse_root_element(input))
+        });
+        let mut element = element?;
+        if let Some(name) = element.bound_name() {
+            abort!(
+                name,
+                "`bind:` names an element for its later siblings, the root element has none"
+            );
+        }
+        header.apply(&mut element);
+        check_group_names(&element);
+
+        Ok(Self {
+            element,
+            options: header.options(),
+            lints: header.lints(),
+            warnings,
+        })
+    }
+}
+
+impl Parse for MarkupVec {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let children = parse_children(input)?;
+        if let Some(chain) = children.iter().find_map(|child| match child {
+            Child::MethodChain(tokens) => Some(tokens),
+            _ => None,
+        }) {
+            abort!(
+                chain,
+                "method chains have no element to apply to in `ui_vec!`, call them on an element instead"
+            );
+        }
+
+        Ok(Self { children })
+    }
+}
+
+impl Parse for StyleMixin {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let mut attributes = parse_attribute_list(input)?;
+        if let Some(key) = attributes.iter().find_map(|attr| match attr {
+            Attribute::Event { event, .. } => Some(event),
+            Attribute::Prop { key, .. } => Some(key),
+            Attribute::PseudoState { state, .. } => Some(state),
+            _ => None,
+        }) {
+            abort!(
+                key,
+                "`style!` only takes style attributes, apply `{}` to the element instead",
+                key
+            );
+        }
+        resolve_aliases(&mut attributes);
+
+        Ok(Self { attributes })
+    }
+}
+
+/// Report group pseudo-states whose literal group name looks like a typo of a
+/// group declared in the same tree.
+///
+/// Groups may also be declared outside the macro invocation, so names without
+/// a close match are accepted.
+fn check_group_names(root: &Element) {
+    fn collect<'a>(element: &'a Element, declared: &mut Vec<String>, used: &mut Vec<&'a LitStr>) {
+        collect_attributes(element.attributes(), declared, used);
+        for element in element.children().iter().filter_map(Child::element) {
+            collect(element, declared, used);
+        }
+    }
+
+    fn collect_attributes<'a>(
+        attributes: &'a [Attribute],
+        declared: &mut Vec<String>,
+        used: &mut Vec<&'a LitStr>,
+    ) {
+        for attr in attributes {
+            match attr {
+                Attribute::KeyValue { key, value } if key == "group" => {
+                    if let Some(name) = as_lit_str(value) {
+                        declared.push(name.value());
+                    }
+                }
+                Attribute::PseudoState {
+                    group, attributes, ..
+                } => {
+                    if let Some(name) = group.as_ref().and_then(as_lit_str) {
+                        used.push(name);
+                    }
+                    collect_attributes(attributes, declared, used);
+                }
+                _ => {}
+            }
+        }
+    }
+
+    const fn as_lit_str(expr: &Expr) -> Option<&LitStr> {
+        match expr {
+            Expr::Lit(ExprLit {
+                lit: Lit::Str(lit), ..
+            }) => Some(lit),
+            _ => None,
+        }
+    }
+
+    let mut declared = vec![];
+    let mut used = vec![];
+    collect(root, &mut declared, &mut used);
+
+    for name in used {
+        let value = name.value();
+        if declared.contains(&value) {
+            continue;
+        }
+        if let Some(suggestion) = closest_match(&value, declared.iter().map(String::as_str)) {
+            abort!(
+                name.span(),
+                "unknown group `{}`, did you mean `{}`?",
+                value,
+                suggestion
+            );
+        }
+    }
+}
+
+/// Check if the input can continue as an expression (e.g. `div()`,
+/// `Header::new()`)
+fn can_continue_as_expr(input: ParseStream) -> bool {
+    !input.is_empty() && input.parse::<Expr>().is_ok()
+}
+
+/// Parse a component tag followed by attributes or children: an uppercase
+/// name, optionally path-qualified (`ui::Button`) or generic
+/// (`List::<FileEntry>`), with optional constructor arguments
+/// (`Button("Save")`).
+fn parse_component_head(input: ParseStream) -> Result<Option<ElementHead>> {
+    let fork = input.fork();
+    let Ok(ExprPath {
+        qself: None, path, ..
+    }) = fork.parse()
+    else {
+        return Ok(None);
+    };
+    if !path
+        .segments
+        .last()
+        .is_some_and(|segment| segment.ident.to_string().starts_with(char::is_uppercase))
+    {
+        return Ok(None);
+    }
+    let has_arguments = fork.peek(Paren);
+    if has_arguments {
+        let _ = fork.parse::<TokenTree>();
+    }
+    if !fork.peek(Token![@]) && !fork.peek(Brace) {
+        return Ok(None);
+    }
+
+    input.parse::<ExprPath>()?;
+    let arguments = if has_arguments {
+        let content;
+        parenthesized!(content in input);
+        Punctuated::<Expr, Token![,]>::parse_terminated(&content)?
+            .into_iter()
+            .collect()
+    } else {
+        vec![]
+    };
+    Ok(Some(ElementHead::Component(path, arguments)))
+}
+
+/// Parse `list(state)`, `uniform_list(id, count)`, `memo(key)`,
+/// `portal(window)` or `div(id)` (also written `div id(id)`) followed by
+/// attributes, children or (for `uniform_list`) an item closure. Without them,
+/// the call is an expression calling the gpui function directly.
+fn parse_native_call_head(input: ParseStream) -> Result<Option<ElementHead>> {
+    let fork = input.fork();
+    let Ok(name) = fork.call(Ident::parse_any) else {
+        return Ok(None);
+    };
+    if !CALL_ELEMENTS.iter().any(|element| name == element) {
+        return Ok(None);
+    }
+    // `div id("toolbar")`
+    let id_keyword = name == "div" && fork.peek2(Paren) && {
+        let keyword = fork.fork().call(Ident::parse_any);
+        keyword.is_ok_and(|keyword| keyword == "id")
+    };
+    if id_keyword {
+        fork.call(Ident::parse_any)?;
+    }
+    if !fork.peek(Paren) {
+        return Ok(None);
+    }
+    let _ = fork.parse::<TokenTree>();
+    let has_body = fork.peek(Token![@])
+        || fork.peek(Brace)
+        || (name == "uniform_list" && fork.peek(Token![|]));
+    if !has_body {
+        return Ok(None);
+    }
+
+    input.call(Ident::parse_any)?;
+    if id_keyword {
+        input.call(Ident::parse_any)?;
+    }
+    let content;
+    parenthesized!(content in input);
+    let arguments = Punctuated::<Expr, Token![,]>::parse_terminated(&content)?;
+    Ok(Some(ElementHead::NativeCall(
+        name,
+        arguments.into_iter().collect(),
+    )))
+}
+
+/// Tags that are elements even when bare, so `div { div }` is reported as a
+/// missing pair of braces. They name gpui functions, while the other tags name
+/// what a local variable often does (`span`, `list`, `empty`, `divider`), so
+/// those are elements only when followed by `@`, `{` or `(`.
+const BRACED_TAGS: &[&str] = &["div", "svg", "anchored", "deferred"];
+
+/// Element tags of this crate other than the native elements.
+const SPECIAL_ELEMENTS: &[&str] = &["deferred", "span", "rich_text"];
+
+/// Whether `name` is an element tag of this crate.
+fn is_builtin_element(name: &str) -> bool {
+    NATIVE_ELEMENTS.contains(&name) || SPECIAL_ELEMENTS.contains(&name)
+}
+
+/// Report a lowercase tag that looks like a misspelled element, rather than
+/// leaving it to be evaluated as a variable. An expression element can still
+/// be written in parentheses, `(dvi) {}`.
+fn check_unknown_tag(tag: &Ident) {
+    let custom = custom_tags();
+    let candidates = NATIVE_ELEMENTS
+        .iter()
+        .chain(SPECIAL_ELEMENTS)
+        .copied()
+        .chain(custom.iter().map(String::as_str));
+    if let Some(suggestion) = closest_match(&tag.to_string(), candidates) {
+        abort!(
+            tag,
+            "unknown native element `{}`, did you mean `{}`?",
+            tag,
+            suggestion
+        );
+    }
+}
+
+/// Parse the element head (identifier or expression before attributes/children)
+fn parse_element_head(input: ParseStream) -> Result<ElementHead> {
+    // arse parenthesized expression as Expression
+    if input.peek(Paren) {
+        let expr: Expr = input.parse()?;
+        return Ok(ElementHead::Expression(expr));
+    }
+
+    if let Some(head) = parse_component_head(input)? {
+        return Ok(head);
+    }
+
+    if let Some(head) = parse_native_call_head(input)? {
+        return Ok(head);
+    }
+
+    if input.peek(Ident::peek_any) {
+        let fork = input.fork();
+        let ident = fork.call(Ident::parse_any)?;
+        let name = ident.to_string();
+
+        // Check if followed by element suffix (@ or {)
+        let has_element_suffix = fork.peek(Token![@]) || fork.peek(Brace);
+
+        // If not followed by element suffix, check if we need to parse as
+        // expression
+        if !has_element_suffix {
+            // Bare tags other than these are expressions, like a local `span`
+            let is_known =
+                BRACED_TAGS.contains(&name.as_str()) || name.starts_with(char::is_uppercase);
+
+            if !is_known || can_continue_as_expr(&fork) {
+                // For unknown lowercase idents or known elements with expression continuation,
+                // parse as full expression. This handles cases like `text.method()`, `foo()`,
+                // `div()`, etc.
+                let expr: Expr = input.parse()?;
+                return Ok(ElementHead::Expression(expr));
+            }
+        }
+
+        // known element
+        let ident = input.call(Ident::parse_any)?;
+
+        if NATIVE_ELEMENTS.contains(&name.as_str()) || custom_element(&name).is_some() {
+            return Ok(ElementHead::Native(ident));
+        }
+        if name == "deferred" {
+            return Ok(ElementHead::Deferred(ident));
+        }
+        if name == "span" {
+            return Ok(ElementHead::Span(ident));
+        }
+        if name == "rich_text" {
+            return Ok(ElementHead::RichText(ident));
+        }
+        if name.starts_with(char::is_uppercase) {
+            return Ok(ElementHead::Component(ident.into(), vec![]));
+        }
+
+        // Other lowercase identifiers
+        check_unknown_tag(&ident);
+        let expr = Expr::Path(syn::ExprPath {
+            attrs: vec![],
+            qself: None,
+            path: ident.into(),
+        });
+
+        return Ok(ElementHead::Expression(expr));
+    }
+
+    let expr: Expr = input.parse()?;
+
+    Ok(ElementHead::Expression(expr))
+}
+
+/// Turn the constructor arguments of `list` and `uniform_list` into their
+/// children, and the id of `div(id)` into its first attribute, leaving a
+/// native element head.
+fn normalize_call_elements(
+    head: ElementHead,
+    attributes: &mut Vec<Attribute>,
+    children: Vec<Child>,
+) -> (ElementHead, Vec<Child>) {
+    let (name, arguments) = match head {
+        ElementHead::NativeCall(name, arguments) => (name, Some(arguments)),
+        ElementHead::Native(name)
+            if ["list", "uniform_list", "memo", "portal"].contains(&name.to_string().as_str()) =>
+        {
+            (name, None)
+        }
+        head => return (head, children),
+    };
+    let children = match name.to_string().as_str() {
+        "list" => list_arguments(&name, arguments, attributes, children),
+        "uniform_list" => uniform_list_arguments(&name, arguments, children),
+        "memo" => memo_arguments(&name, arguments, attributes, children),
+        "portal" => portal_arguments(&name, arguments, attributes, children),
+        _ => {
+            stateful_div(&name, arguments.unwrap_or_default(), attributes);
+            children
+        }
+    };
+    (ElementHead::Native(name), children)
+}
+
+/// Apply the id of `div(id)` first, so the rest of the chain is on a
+/// `Stateful<Div>`.
+fn stateful_div(name: &Ident, arguments: Vec<Expr>, attributes: &mut Vec<Attribute>) {
+    let mut arguments = arguments.into_iter();
+    let Some(id) = arguments.next() else {
+        return;
+    };
+    if let Some(extra) = arguments.next() {
+        abort!(extra, "`{}(id)` takes a single argument, its id", name);
+    }
+    if let Some(key) = attributes.iter().find_map(|attr| match attr {
+        Attribute::KeyValue { key, .. } if key == "id" => Some(key),
+        _ => None,
+    }) {
+        abort!(key, "`{}` already has an id from `{}(id)`", name, name);
+    }
+    let key = Ident::new("id", id.span());
+    // Attribute tuples are split into arguments, keep a tuple id whole
+    let value = if matches!(id, Expr::Tuple(_)) {
+        parse_quote! { (#id,) }
+    } else {
+        id
+    };
+    attributes.insert(0, Attribute::KeyValue { key, value });
+}
+
+/// Validate and expand the attributes and children of a native element.
+fn prepare_native(
+    name: &Ident,
+    attributes: &mut Vec<Attribute>,
+    mut children: Vec<Child>,
+) -> Vec<Child> {
+    if name == "surface" {
+        check_surface_source(name, &children);
+    }
+    if name == "empty" && (!attributes.is_empty() || !children.is_empty()) {
+        abort!(
+            name,
+            "`empty` renders nothing, it takes no attributes or children"
+        );
+    }
+    if name == "lazy" {
+        check_lazy(name, attributes, &children);
+    }
+    if name == "anchored" || name == "overlay" {
+        expand_fit_mode(attributes);
+        check_anchored_attributes(name, attributes);
+    }
+    children = expand_layout_element(name, attributes, children);
+    resolve_aliases(attributes);
+    expand_focus(attributes);
+    if !custom_element(&name.to_string()).is_some_and(|def| def.is_stateful()) {
+        ensure_stateful(name, attributes);
+    }
+    expand_actions(attributes);
+    expand_tooltips(attributes);
+    check_event_support(name, attributes);
+    children
+}
+
+/// Build an Element from the parsed head, attributes, and children
+fn build_element(
+    head: ElementHead,
+    mut attributes: Vec<Attribute>,
+    children: Vec<Child>,
+) -> Element {
+    let (head, mut children) = normalize_call_elements(head, &mut attributes, children);
+    check_component_attributes(&head, &attributes);
+    check_bind(&head, &attributes);
+    if let ElementHead::Native(name) = &head {
+        children = prepare_native(name, &mut attributes, children);
+        // Component builders may be called repeatedly on purpose, like a
+        // menu's `item: a, item: b`
+        check_duplicate_attributes(&attributes);
+    }
+    check_conflicting_attributes(&attributes);
+    fold_constant_conditions(&mut attributes);
+
+    match head {
+        ElementHead::Native(name) => Element::Native(NativeElement {
+            definition: custom_element(&name.to_string()),
+            name,
+            attributes,
+            children,
+        }),
+        ElementHead::NativeCall(..) => unreachable!("normalized into a native element above"),
+        ElementHead::Deferred(name) => {
+            if children.len() != 1 {
+                abort!(name.span(), "deferred must have exactly one child");
+            }
+            if !matches!(children[0], Child::Element(_)) {
+                abort!(name.span(), "deferred child must be an element");
+            }
+            check_deferred_attributes(&name, &attributes);
+            Element::Deferred(DeferredElement {
+                name,
+                attributes,
+                child: Box::new(children.into_iter().next().unwrap()),
+            })
+        }
+        ElementHead::Component(name, arguments) => {
+            check_preset_props(&name, &attributes);
+            Element::Component(ComponentElement {
+                name,
+                arguments,
+                attributes,
+                children,
+            })
+        }
+        ElementHead::Expression(expr) => Element::Expression(ExprElement {
+            expr,
+            attributes,
+            children,
+        }),
+        ElementHead::Span(name) => {
+            if let Some(child) = children.iter().find(|child| !is_text(child)) {
+                abort!(
+                    child_span(child),
+                    "`span` children must be text, like string literals or `text: expr`"
+                );
+            }
+            Element::Span(SpanElement {
+                name,
+                attributes,
+                children,
+            })
+        }
+        ElementHead::RichText(name) => {
+            if let Some(child) = children.iter().find(|child| {
+                !is_text(child)
+                    && child
+                        .element()
+                        .is_none_or(|el| !matches!(el, Element::Span(_)))
+            }) {
+                abort!(
+                    child_span(child),
+                    "`rich_text` children must be text or `span` elements"
+                );
+            }
+            let Some(index) = attributes
+                .iter()
+                .position(|attr| matches!(attr, Attribute::KeyValue { key, .. } if key == "id"))
+            else {
+                abort!(
+                    name,
+                    "`rich_text` requires an `id`, e.g. `rich_text @[id: \"log\"] { ... }`"
+                );
+            };
+            let Attribute::KeyValue { value: id, .. } = attributes.remove(index) else {
+                unreachable!()
+            };
+            Element::RichText(RichTextElement {
+                name,
+                id,
+                attributes,
+                children,
+            })
+        }
+    }
+}
+
+fn child_span(child: &Child) -> Span {
+    match child {
+        Child::Element(element) => element_span(element),
+        Child::Spread(expr) => expr.span(),
+        Child::MethodChain(tokens) => tokens.span(),
+        Child::Cfg { attrs, .. } => attrs[0].span(),
+    }
+}
+
+fn element_span(element: &Element) -> Span {
+    match element {
+        Element::Native(el) => el.name.span(),
+        Element::Component(el) => el.name.span(),
+        Element::Deferred(el) => el.name.span(),
+        Element::Expression(el) => el.expr.span(),
+        Element::Span(el) => el.name.span(),
+        Element::RichText(el) => el.name.span(),
+    }
+}
+
+/// Parse element children `{...}` with optional requirement
+fn parse_element_children(
+    input: ParseStream,
+    require_braces: bool,
+    head_span: Span,
+) -> Result<Vec<Child>> {
+    if !input.peek(Brace) {
+        if require_braces {
+            return Err(syn::Error::new(head_span, "element requires braces: `{}`"));
+        }
+        return Ok(vec![]); // No children
+    }
+
+    let content;
+    braced!(content in input);
+    parse_children(&content)
+}
+
+/// Parse the body of an element: its children, or the callbacks of a
+/// `canvas`.
+fn parse_body(head: &ElementHead, input: ParseStream, require_braces: bool) -> Result<Vec<Child>> {
+    match head {
+        ElementHead::Native(name) if name == "canvas" && input.peek(Brace) => {
+            parse_canvas_callbacks(name, input)
+        }
+        ElementHead::Native(name) | ElementHead::NativeCall(name, _)
+            if CHILDLESS_ELEMENTS.contains(&name.to_string().as_str()) && input.peek(Brace) =>
+        {
+            parse_no_children(name, input)
+        }
+        _ if has_range_renderer(head, input) => parse_range_renderer(input),
+        _ => parse_element_children(input, require_braces, head.span()),
+    }
+}
+
+/// Parse the braces of an element that can't have children, reporting any
+/// children at the braces.
+fn parse_no_children(name: &Ident, input: ParseStream) -> Result<Vec<Child>> {
+    let content;
+    let brace = braced!(content in input);
+    if !content.is_empty() {
+        return Err(syn::Error::new(
+            brace.span.join(),
+            format!("element `{name}` cannot have children"),
+        ));
+    }
+    Ok(vec![])
+}
+
+/// Whether a `uniform_list` is followed by its range renderer closure.
+fn has_range_renderer(head: &ElementHead, input: ParseStream) -> bool {
+    matches!(
+        head,
+        ElementHead::Native(name) | ElementHead::NativeCall(name, _) if name == "uniform_list"
+    ) && input.peek(Token![|])
+}
+
+/// Parse an element at the top level
+fn parse_root_element(input: ParseStream) -> Result<Element> {
+    if input.peek(Token![#]) {
+        let attrs = input.call(syn::Attribute::parse_outer)?;
+        abort!(
+            attrs[0].span(),
+            "attributes are not supported on the top-level element, only on children"
+        );
+    }
+
+    let head = parse_element_head(input)?;
+    let attributes = parse_attributes(input)?;
+    let head_span = head.span();
+
+    // Root element always requires braces, or the item closure of a
+    // `uniform_list`
+    if !input.peek(Brace) && !has_range_renderer(&head, input) {
+        return Err(syn::Error::new(
+            head_span,
+            "top-level element requires braces, e.g. `expr @[attrs] { children }`\n\
+             note: braces declare this as a UI element in the component tree, not just an expression",
+        ));
+    }
+
+    let children = parse_body(&head, input, true)?;
+
+    Ok(build_element(head, attributes, children))
+}
+
+/// Report props and `@base[...]` blocks on elements other than components
+/// and expression elements
+fn check_component_attributes(head: &ElementHead, attributes: &[Attribute]) {
+    if matches!(
+        head,
+        ElementHead::Component(..) | ElementHead::Expression(_)
+    ) {
+        return;
+    }
+    if let Some(prop) = find_prop(attributes) {
+        abort!(
+            prop,
+            "`:{}` props are only supported on components, use `{}: ...`",
+            prop,
+            prop
+        );
+    }
+    if let Some(base) = attributes.iter().find_map(|attr| match attr {
+        Attribute::PseudoState { state, .. } if state == BASE_STATE => Some(state),
+        _ => None,
+    }) {
+        abort!(
+            base,
+            "`@base[...]` is only supported on components, move the attributes to `@[...]`"
+        );
+    }
+}
+
+/// Check that a `surface` has a single expression child: its frame source.
+fn check_surface_source(name: &Ident, children: &[Child]) {
+    if children.len() != 1 {
+        abort!(
+            name.span(),
+            "surface must have exactly one child, its frame source"
+        );
+    }
+    if !matches!(children[0], Child::Element(Element::Expression(_))) {
+        abort!(
+            child_span(&children[0]),
+            "surface child must be an expression, e.g. `surface { frame }`"
+        );
+    }
+}
+
+/// Report `bind:` values other than a variable name, and `bind:` on spans,
+/// which are merged into the surrounding text.
+fn check_bind(head: &ElementHead, attributes: &[Attribute]) {
+    let Some(value) = attributes.iter().find_map(|attr| match attr {
+        Attribute::KeyValue { key, value } if key == "bind" => Some(value),
+        _ => None,
+    }) else {
+        return;
+    };
+    if !matches!(value, Expr::Path(path) if path.path.get_ident().is_some()) {
+        abort!(value, "`bind:` takes a variable name, e.g. `bind: header`");
+    }
+    if let ElementHead::Span(name) = head {
+        abort!(
+            name,
+            "`bind:` is not supported on `span`, spans are merged into their text"
+        );
+    }
+}
+
+/// The first `:prop` among `attributes`, looking into platform and compound
+/// blocks.
+fn find_prop(attributes: &[Attribute]) -> Option<&Ident> {
+    attributes.iter().find_map(|attr| match attr {
+        Attribute::Prop { key, .. } => Some(key),
+        Attribute::Platform { attributes, .. } | Attribute::Compound { attributes, .. } => {
+            find_prop(attributes)
+        }
+        _ => None,
+    })
+}
+
+/// Parse attribute blocks: `@[...]`, `@hover[...]`, `@group_hover("g")[...]`
+fn parse_attributes(input: ParseStream) -> Result<Vec<Attribute>> {
+    let mut attributes = vec![];
+
+    while input.peek(Token![@]) {
+        let at_token: Token![@] = input.parse()?;
+
+        if input.peek(Ident::peek_any) && (input.peek2(Bracket) || input.peek2(Paren)) {
+            attributes.push(parse_pseudo_state(input)?);
+            continue;
+        }
+
+        if !input.peek(Bracket) {
+            abort!(
+                at_token.span(),
+                "expected `[` after `@` for attributes, e.g. `@[attr1, attr2]`"
+            );
+        }
+
+        let content;
+        bracketed!(content in input);
+        attributes.extend(parse_attribute_list(&content)?);
+    }
+
+    Ok(attributes)
+}
+
+/// Parse a pseudo-state block after `@`: `hover[...]` or
+/// `group_hover("g")[...]`
+fn parse_pseudo_state(input: ParseStream) -> Result<Attribute> {
+    let state = input.call(Ident::parse_any)?;
+    let name = state.to_string();
+    let is_group_state = GROUP_PSEUDO_STATES.contains(&name.as_str());
+
+    if !is_group_state && name != BASE_STATE && !PSEUDO_STATES.contains(&name.as_str()) {
+        abort!(
+            state.span(),
+            "unknown pseudo-state `{}`, expected one of: {}",
+            state,
+            PSEUDO_STATES
+                .iter()
+                .chain(GROUP_PSEUDO_STATES)
+                .chain([&BASE_STATE])
+                .copied()
+                .collect::<Vec<_>>()
+                .join(", ")
+        );
+    }
+
+    let group = if input.peek(Paren) {
+        if !is_group_state {
+            abort!(
+                state.span(),
+                "`@{}` does not take a group name, use `@group_{}(\"group\")[...]` for group styles",
+                state,
+                state
+            );
+        }
+        let content;
+        parenthesized!(content in input);
+        Some(content.parse::<Expr>()?)
+    } else {
+        if is_group_state {
+            abort!(
+                state.span(),
+                "`@{}` requires a group name, e.g. `@{}(\"group\")[...]`",
+                state,
+                state
+            );
+        }
+        None
+    };
+
+    let content;
+    bracketed!(content in input);
+    let attributes = parse_attribute_list(&content)?;
+    if let Some(event) = attributes.iter().find_map(|attr| match attr {
+        Attribute::Event { event, .. } => Some(event),
+        _ => None,
+    }) {
+        abort!(
+            event,
+            "`@{}[...]` only takes style attributes, move `on:{}` to `@[...]`",
+            state,
+            event
+        );
+    }
+    if let Some(prop) = find_prop(&attributes) {
+        abort!(
+            prop,
+            "`@{}[...]` only takes style attributes, move `:{}` to `@[...]`",
+            state,
+            prop
+        );
+    }
+
+    Ok(Attribute::PseudoState {
+        state,
+        group,
+        attributes,
+    })
+}
+
+/// Parse the attributes of an attribute block, expanding theme tokens
+/// (`$surface`) and unit literals (`200px`) first
+fn parse_attribute_list(content: ParseStream) -> Result<Vec<Attribute>> {
+    let tokens = expand_units(expand_theme_tokens(content.parse()?));
+    let parser = |input: ParseStream| parse_comma_separated(input, parse_attribute);
+    parser.parse2(tokens)
+}
+
+/// Parse a single attribute: `flex`, `w: px(200.0)`, `macos: { pt: px(28.0) }`,
+/// `on:click: handler` or a `:label: "Save"` prop
+fn parse_attribute(input: ParseStream) -> Result<Attribute> {
+    if input.peek(Token![:]) && !input.peek(Token![::]) {
+        input.parse::<Token![:]>()?;
+        let key = input.call(Ident::parse_any)?;
+        let value = if input.peek(Token![:]) {
+            input.parse::<Token![:]>()?;
+            Some(input.parse()?)
+        } else {
+            None
+        };
+        return Ok(Attribute::Prop { key, value });
+    }
+
+    let key = input.call(Ident::parse_any)?;
+
+    // `drag_over::<T>: |style, payload, window, cx| style`
+    if key == "drag_over" && input.peek(Token![::]) {
+        return parse_event(input, key);
+    }
+
+    if !input.peek(Token![:]) {
+        return Ok(Attribute::Flag(key));
+    }
+
+    input.parse::<Token![:]>()?;
+
+    if input.peek(Brace) && PLATFORMS.contains(&key.to_string().as_str()) {
+        let content;
+        braced!(content in input);
+        let attributes = parse_attribute_list(&content)?;
+        return Ok(Attribute::Platform {
+            platform: key,
+            attributes,
+        });
+    }
+
+    if key == "on" && is_event(input) {
+        let event = input.call(Ident::parse_any)?;
+        return parse_event(input, event);
+    }
+
+    if is_named_arguments(input) {
+        let content;
+        parenthesized!(content in input);
+        let attributes = parse_attribute_list(&content)?;
+        return Ok(Attribute::Compound { key, attributes });
+    }
+
+    let value: Expr = input.parse()?;
+
+    Ok(Attribute::KeyValue { key, value })
+}
+
+/// Parse the rest of an event attribute after its name: the payload type
+/// (`::<Tab>`), argument (`("cmd-s")`), modifiers (`.double`) and handler.
+fn parse_event(input: ParseStream, event: Ident) -> Result<Attribute> {
+    check_event(&event);
+    let payload = if input.peek(Token![::]) {
+        input.parse::<Token![::]>()?;
+        input.parse::<Token![<]>()?;
+        let payload: Type = input.parse()?;
+        input.parse::<Token![>]>()?;
+        Some(Box::new(payload))
+    } else {
+        None
+    };
+    check_event_payload(&event, payload.as_deref());
+    let argument = if input.peek(Paren) {
+        let content;
+        parenthesized!(content in input);
+        Some(content.parse::<Expr>()?)
+    } else {
+        None
+    };
+    check_event_argument(&event, argument.as_ref());
+    let mut modifiers = vec![];
+    while input.peek(Token![.]) {
+        input.parse::<Token![.]>()?;
+        modifiers.push(input.call(Ident::parse_any)?);
+    }
+    check_modifiers(&event, &modifiers);
+    input.parse::<Token![:]>()?;
+    let handler: Expr = input.parse()?;
+    Ok(Attribute::Event {
+        event,
+        payload,
+        argument,
+        modifiers,
+        handler,
+    })
+}
+
+/// Whether the input after `on:` is an event name with optional modifiers and
+/// a colon (`click:`, `click.double:`, `key("cmd-s"):`, `drop::<Tab>:`), rather
+/// than the value of an `on` attribute.
+fn is_event(input: ParseStream) -> bool {
+    let fork = input.fork();
+    if fork.call(Ident::parse_any).is_err() {
+        return false;
+    }
+    if fork.peek(Token![::]) && fork.peek3(Token![<]) {
+        let _ = fork.parse::<Token![::]>();
+        let _ = fork.parse::<Token![<]>();
+        if fork.parse::<Type>().is_err() || fork.parse::<Token![>]>().is_err() {
+            return false;
+        }
+    }
+    if fork.peek(Paren) {
+        let _ = fork.parse::<TokenTree>();
+    }
+    while fork.peek(Token![.]) && fork.peek2(Ident::peek_any) {
+        let _ = fork.parse::<Token![.]>();
+        let _ = fork.call(Ident::parse_any);
+    }
+    fork.peek(Token![:]) && !fork.peek(Token![::])
+}
+
+/// Whether the parenthesized group ahead starts with `name:`, which can't
+/// begin a tuple expression.
+fn is_named_arguments(input: ParseStream) -> bool {
+    let Some((content, ..)) = input.cursor().group(Delimiter::Parenthesis) else {
+        return false;
+    };
+    content
+        .ident()
+        .and_then(|(_, rest)| rest.punct())
+        .is_some_and(|(punct, _)| punct.as_char() == ':' && punct.spacing() == Spacing::Alone)
+}
+
+/// Parse comma-separated children. Like a block-like statement, a child
+/// ending in a brace-delimited body, `div { .. }`, needs no comma after it.
+///
+/// A child that fails to parse is skipped up to the next comma, so the syntax
+/// errors of all children are reported at once.
+pub fn parse_children(input: ParseStream) -> Result<Vec<Child>> {
+    let mut children = vec![];
+    let mut errors = Errors::default();
+    while !input.is_empty() {
+        let start = input.cursor();
+        match parse_child(input) {
+            Ok(child) => children.push(child),
+            Err(error) => {
+                errors.push(error);
+                skip_to_comma(input);
+            }
+        }
+        if input.is_empty() {
+            break;
+        }
+        if input.peek(Token![,]) {
+            input.parse::<Token![,]>()?;
+        } else if !ends_with_brace(start, input) || input.peek(Token![.]) {
+            // Reported at the token after the child rather than as whatever
+            // the next parser expects, the usual cause is a forgotten comma.
+            // A method chain needs one even after a brace, as `div {} .flex()`
+            // reads as styling the inner `div`
+            errors.push(input.error("expected `,` between children"));
+        }
+    }
+    errors.finish(children)
+}
+
+/// Whether the last token between `start` and `input` is a brace group.
+fn ends_with_brace(start: Cursor, input: ParseStream) -> bool {
+    let mut cursor = start;
+    let mut last = None;
+    while cursor != input.cursor() {
+        let Some((tree, next)) = cursor.token_tree() else {
+            break;
+        };
+        last = Some(tree);
+        cursor = next;
+    }
+    matches!(last, Some(TokenTree::Group(group)) if group.delimiter() == Delimiter::Brace)
+}
+
+/// Parse comma-separated items, recovering from an item that fails to parse
+/// at the next comma like `parse_children`.
+fn parse_comma_separated<T>(
+    input: ParseStream,
+    parser: fn(ParseStream) -> Result<T>,
+) -> Result<Vec<T>> {
+    let mut items = vec![];
+    let mut errors = Errors::default();
+    while !input.is_empty() {
+        match parser(input) {
+            Ok(item) => items.push(item),
+            Err(error) => {
+                errors.push(error);
+                skip_to_comma(input);
+            }
+        }
+        if input.is_empty() {
+            break;
+        }
+        if input.peek(Token![,]) {
+            input.parse::<Token![,]>()?;
+        } else {
+            errors.push(input.error("expected `,`"));
+        }
+    }
+    errors.finish(items)
+}
+
+/// Syntax errors collected while parsing a list, reported together.
+#[derive(Default)]
+struct Errors(Option<syn::Error>);
+
+impl Errors {
+    fn push(&mut self, error: syn::Error) {
+        match &mut self.0 {
+            Some(errors) => errors.combine(error),
+            None => self.0 = Some(error),
+        }
+    }
+
+    /// `value` if no error was collected.
+    fn finish<T>(self, value: T) -> Result<T> {
+        self.0.map_or(Ok(value), Err)
+    }
+}
+
+/// Skip the rest of an item that failed to parse, up to the next comma.
+fn skip_to_comma(input: ParseStream) {
+    while !input.is_empty() && !input.peek(Token![,]) {
+        // A token tree always parses, and groups are skipped whole
+        let _ = input.parse::<TokenTree>();
+    }
+}
+
+/// Parse a single child
+fn parse_child(input: ParseStream) -> Result<Child> {
+    if input.peek(Token![#]) {
+        let attrs = input.call(syn::Attribute::parse_outer)?;
+        if let Some(attr) = attrs.iter().find(|attr| !attr.path().is_ident("cfg")) {
+            abort!(
+                attr.span(),
+                "only `#[cfg(...)]` attributes are supported on children"
+            );
+        }
+        return Ok(Child::Cfg {
+            attrs,
+            child: Box::new(parse_child(input)?),
+        });
+    }
+
+    if input.peek(Ident) && input.peek2(Token![:]) && !input.peek2(Token![::]) {
+        return parse_named_child(input);
+    }
+
+    if input.peek(Token![..]) {
+        input.parse::<Token![..]>()?;
+        return Ok(Child::Spread(input.parse()?));
+    }
+
+    if input.peek(Token![.]) {
+        input.parse::<Token![.]>()?;
+        return Ok(Child::MethodChain(parse_method_chain(input)?));
+    }
+
+    let head = match parse_element_head(input)? {
+        ElementHead::Expression(expr) => {
+            ElementHead::Expression(link_child(text_child(expr, input)?))
+        }
+        head => head,
+    };
+    let attributes = parse_attributes(input)?;
+
+    let require_braces = matches!(
+        head,
+        ElementHead::Native(_)
+            | ElementHead::NativeCall(..)
+            | ElementHead::Deferred(_)
+            | ElementHead::Component(..)
+            | ElementHead::Span(_)
+            | ElementHead::RichText(_)
+    );
+
+    let children = parse_body(&head, input, require_braces)?;
+
+    let element = build_element(head, attributes, children);
+
+    Ok(Child::Element(element))
+}
+
+/// Parse a named child: `text: expr` renders a `Display` value, `t: key` a
+/// translation and `markdown: source` renders Markdown.
+fn parse_named_child(input: ParseStream) -> Result<Child> {
+    let key: Ident = input.parse()?;
+    input.parse::<Token![:]>()?;
+    let value: Expr = input.parse()?;
+
+    let expr = match key.to_string().as_str() {
+        "text" => display_text(&value),
+        "t" => translated_text(&key, &value),
+        "markdown" => markdown(&key, &value),
+        _ => abort!(
+            key,
+            "unknown child `{}: ...`, expected `text: expr`, `t: key` or `markdown: source`",
+            key
+        ),
+    };
+
+    Ok(Child::Element(Element::Expression(ExprElement {
+        expr,
+        attributes: vec![],
+        children: vec![],
+    })))
+}
+
+/// `markdown: source`, rendered at compile time if the source is a string
+/// literal and when the element is built otherwise.
+fn markdown(key: &Ident, value: &Expr) -> Expr {
+    #[cfg(feature = "markdown")]
+    {
+        let _ = key;
+        if let Expr::Lit(ExprLit {
+            lit: Lit::Str(source),
+            ..
+        }) = value
+        {
+            Expr::Verbatim(crate::markdown::render(&source.value()))
+        } else {
+            Expr::Verbatim(crate::markdown::render_dynamic(value))
+        }
+    }
+    #[cfg(not(feature = "markdown"))]
+    {
+        let _ = value;
+        abort!(
+            key,
+            "`markdown:` requires the `markdown` feature of gpui-markup"
+        );
+    }
+}
+
+/// Parse a method chain until comma.
+///
+/// We need to track angle bracket depth because `<>` are not paired delimiters
+/// in Rust's tokenizer (unlike `()`, `[]`, `{}`). They are parsed as individual
+/// `Punct` tokens, so commas inside generics like `.map::<Div, _>()` would
+/// incorrectly terminate the method chain without this tracking.
+fn parse_method_chain(input: ParseStream) -> Result<TokenStream> {
+    let mut tokens = TokenStream::new();
+    let mut angle_depth = 0i32;
+
+    while !input.is_empty() {
+        // Only stop on comma when not inside angle brackets (generics)
+        if input.peek(Token![,]) && angle_depth == 0 {
+            break;
+        }
+
+        let tt = input.parse()?;
+
+        // Track angle bracket depth for generics
+        if let TokenTree::Punct(p) = &tt {
+            match p.as_char() {
+                '<' => angle_depth += 1,
+                '>' => angle_depth = (angle_depth - 1).max(0),
+                _ => {}
+            }
+        }
+
+        tokens.extend([tt]);
+    }
+
+    Ok(tokens)
+}
+
+#[cfg(test)]
+mod tests {
+    use quote::quote;
+    use syn::parse2;
+
+    use super::*;
+
+    #[test]
+    fn test_parse_simple_div() {
+        let input = quote! { div {} };
+        let markup: Markup = parse2(input).unwrap();
+        assert!(matches!(markup.element, Element::Native(_)));
+    }
+
+    #[test]
+    fn test_parse_div_with_attributes() {
+        let input = quote! {
+            div @[flex, w: px(200.0)] {}
+        };
+        let markup: Markup = parse2(input).unwrap();
+        if let Element::Native(el) = markup.element {
+            assert_eq!(el.attributes.len(), 2);
+        } else {
+            panic!("Expected Native element");
+        }
+    }
+
+    #[test]
+    fn test_parse_braced_children_without_commas() {
+        let input = quote! {
+            div {
+                div { "Title" }
+                Body {}
+                "Footer",
+            }
+        };
+        let markup: Markup = parse2(input).unwrap();
+        assert_eq!(markup.element.children().len(), 3);
+    }
+
+    #[test]
+    fn test_parse_missing_comma() {
+        let input = quote! {
+            div {
+                "Hello"
+                div {}
+            }
+        };
+        let Err(error) = parse2::<Markup>(input) else {
+            panic!("Expected a missing comma error");
+        };
+        assert_eq!(error.to_string(), "expected `,` between children");
+    }
+
+    #[test]
+    fn test_parse_svg_children() {
+        let input = quote! {
+            div {
+                svg @[path: "icons/check.svg"] { "Check" },
+            }
+        };
+        let Err(error) = parse2::<Markup>(input) else {
+            panic!("Expected an error for the children of `svg`");
+        };
+        assert_eq!(error.to_string(), "element `svg` cannot have children");
+    }
+
+    #[test]
+    fn test_parse_bare_tags_as_expressions() {
+        let input = quote! {
+            #![elements(icon = my_ui::icon -> my_ui::Icon)]
+            div { span, list, header, divider, empty, memo, icon, span @[font_weight: BOLD] { "b" } }
+        };
+        let markup = parse2::<Markup>(input).unwrap();
+        let Element::Native(root) = &markup.element else {
+            panic!("Expected a native root");
+        };
+        for child in &root.children[..7] {
+            assert!(
+                matches!(child, Child::Element(Element::Expression(_))),
+                "{child:?}"
+            );
+        }
+        assert!(matches!(
+            &root.children[7],
+            Child::Element(Element::Span(_))
+        ));
+    }
+
+    #[test]
+    fn test_parse_reports_every_error() {
+        let input = quote! {
+            div {
+                div @[flex, w: , h: px(10.0)] {},
+                div {
+                    "Body" label,
+                },
+                Footer {},
+            }
+        };
+        let Err(error) = parse2::<Markup>(input) else {
+            panic!("Expected syntax errors");
+        };
+        let messages: Vec<String> = error.into_iter().map(|error| error.to_string()).collect();
+        assert_eq!(messages.len(), 2, "{messages:?}");
+        assert_eq!(messages[1], "expected `,` between children");
+    }
+
+    #[test]
+    fn test_parse_div_with_children() {
+        let input = quote! {
+            div {
+                "Hello",
+            }
+        };
+        let markup: Markup = parse2(input).unwrap();
+        if let Element::Native(el) = markup.element {
+            assert_eq!(el.children.len(), 1);
+        } else {
+            panic!("Expected Native element");
+        }
+    }
+
+    #[test]
+    fn test_parse_div_full() {
+        let input = quote! {
+            div @[flex, flex_col] {
+                "Content",
+                div @[bold] { "Nested" },
+            }
+        };
+        let markup: Markup = parse2(input).unwrap();
+        if let Element::Native(el) = markup.element {
+            assert_eq!(el.attributes.len(), 2);
+            assert_eq!(el.children.len(), 2);
+        } else {
+            panic!("Expected Native element");
+        }
+    }
+
+    #[test]
+    fn test_parse_pseudo_states() {
+        let input = quote! {
+            div @[flex] @hover[bg: red(), underline] @active[opacity: 0.5] {}
+        };
+        let markup: Markup = parse2(input).unwrap();
+        if let Element::Native(el) = markup.element {
+            assert_eq!(el.attributes.len(), 3);
+            assert!(matches!(
+                &el.attributes[1],
+                Attribute::PseudoState { state, group: None, attributes } if state == "hover" && attributes.len() == 2
+            ));
+        } else {
+            panic!("Expected Native element");
+        }
+    }
+
+    #[test]
+    fn test_parse_group_pseudo_state() {
+        let input = quote! {
+            div @group_hover("row")[visible] {}
+        };
+        let markup: Markup = parse2(input).unwrap();
+        assert!(matches!(
+            markup.element.attributes(),
+            [Attribute::PseudoState { state, group: Some(_), .. }] if state == "group_hover"
+        ));
+    }
+
+    #[test]
+    fn test_parse_platform_attributes() {
+        let input = quote! {
+            div @[macos: { pt: px(28.0), flex }, w: { px(10.0) }] {}
+        };
+        let markup: Markup = parse2(input).unwrap();
+        assert!(matches!(
+            markup.element.attributes(),
+            [
+                Attribute::Platform { platform, attributes },
+                Attribute::KeyValue { .. },
+            ] if platform == "macos" && attributes.len() == 2
+        ));
+    }
+
+    #[test]
+    fn test_parse_compound_attribute() {
+        let input = quote! {
+            div @[grid: (cols: 3, gap: 2), size: (a, b), w: (x::y)] {}
+        };
+        let markup: Markup = parse2(input).unwrap();
+        assert!(matches!(
+            markup.element.attributes(),
+            [
+                Attribute::Compound { key, attributes },
+                Attribute::KeyValue { .. },
+                Attribute::KeyValue { .. },
+            ] if key == "grid" && attributes.len() == 2
+        ));
+    }
+
+    #[test]
+    fn test_parse_event_attribute() {
+        let input = quote! {
+            div @[id: "a", on:click: |_, _, _| {}, on: value.field, on:mouse_down.right: handler] {}
+        };
+        let markup: Markup = parse2(input).unwrap();
+        assert!(matches!(
+            markup.element.attributes(),
+            [
+                Attribute::KeyValue { .. },
+                Attribute::Event { event, .. },
+                Attribute::KeyValue { key, .. },
+                Attribute::Event { modifiers, .. },
+            ] if event == "click" && key == "on" && modifiers.len() == 1
+        ));
+    }
+
+    #[test]
+    fn test_parse_text_child() {
+        let input = quote! {
+            div { text: count, 42, -1, text(total) }
+        };
+        let markup: Markup = parse2(input).unwrap();
+        assert!(matches!(
+            markup.element.children(),
+            [
+                Child::Element(Element::Expression(_)),
+                Child::Element(Element::Expression(_)),
+                Child::Element(Element::Expression(_)),
+                Child::Element(Element::Expression(_))
+            ]
+        ));
+        assert!(markup.element.children().iter().all(is_text));
+    }
+
+    #[test]
+    fn test_parse_span() {
+        let input = quote! {
+            div { "Press ", span @[italic] { "Enter" } }
+        };
+        let markup: Markup = parse2(input).unwrap();
+        assert!(matches!(
+            markup.element.children(),
+            [Child::Element(Element::Expression(_)), Child::Element(Element::Span(span))]
+                if span.attributes.len() == 1 && span.children.len() == 1
+        ));
+    }
+
+    #[test]
+    fn test_parse_cfg_child() {
+        let input = quote! {
+            div {
+                #[cfg(debug_assertions)]
+                div {},
+            }
+        };
+        let markup: Markup = parse2(input).unwrap();
+        assert!(matches!(
+            markup.element.children(),
+            [Child::Cfg { attrs, child }]
+                if attrs.len() == 1 && matches!(child.as_ref(), Child::Element(_))
+        ));
+    }
+
+    #[test]
+    fn test_parse_expression_element() {
+        let input = quote! {
+            Container::new(title) {}
+        };
+        let markup: Markup = parse2(input).unwrap();
+        assert!(matches!(markup.element, Element::Expression(_)));
+    }
+
+    #[test]
+    fn test_parse_expression_element_with_attrs() {
+        let input = quote! {
+            Button::new("Click") @[style: Primary] {}
+        };
+        let markup: Markup = parse2(input).unwrap();
+        if let Element::Expression(el) = markup.element {
+            assert_eq!(el.attributes.len(), 1);
+        } else {
+            panic!("Expected Expression element");
+        }
+    }
+
+    #[test]
+    fn test_parse_spread_children() {
+        let input = quote! {
+            div {
+                ..items,
+            }
+        };
+        let markup: Markup = parse2(input).unwrap();
+        if let Element::Native(el) = markup.element {
+            assert_eq!(el.children.len(), 1);
+            assert!(matches!(el.children[0], Child::Spread(_)));
+        } else {
+            panic!("Expected Native element");
+        }
+    }
+
+    #[test]
+    fn test_parse_method_chain() {
+        let input = quote! {
+            div {
+                .when(cond, |d| d.flex()),
+            }
+        };
+        let markup: Markup = parse2(input).unwrap();
+        if let Element::Native(el) = markup.element {
+            assert_eq!(el.children.len(), 1);
+            assert!(matches!(el.children[0], Child::MethodChain(_)));
+        } else {
+            panic!("Expected Native element");
+        }
+    }
+
+    #[test]
+    fn test_parse_stateful_div() {
+        for input in [
+            quote! { div("toolbar") @[flex] {} },
+            quote! { div id("toolbar") @[flex] {} },
+        ] {
+            let markup: Markup = parse2(input).unwrap();
+            let Element::Native(el) = &markup.element else {
+                panic!("Expected Native element");
+            };
+            assert!(matches!(
+                &el.attributes[..],
+                [Attribute::KeyValue { key, .. }, Attribute::Flag(_)] if key == "id"
+            ));
+        }
+    }
+
+    #[test]
+    fn test_parse_deferred() {
+        let input = quote! {
+            deferred {
+                div { "Content" },
+            }
+        };
+        let markup: Markup = parse2(input).unwrap();
+        assert!(matches!(markup.element, Element::Deferred(_)));
+    }
+
+    #[test]
+    fn test_parse_method_with_generics() {
+        let input = quote! {
+            div {
+                .map::<Div, _>(|d| d),
+            }
+        };
+        let markup: Markup = parse2(input).unwrap();
+        if let Element::Native(el) = markup.element {
+            assert!(matches!(el.children[0], Child::MethodChain(_)));
+        } else {
+            panic!("Expected Native element");
+        }
+    }
+
+    #[test]
+    fn test_parse_paren_child_without_attrs() {
+        let input = quote! {
+            div {
+                (some_expr),
+            }
+        };
+        let markup: Markup = parse2(input).unwrap();
+        if let Element::Native(el) = markup.element {
+            assert!(matches!(
+                el.children[0],
+                Child::Element(Element::Expression(_))
+            ));
+        } else {
+            panic!("Expected Native element");
+        }
+    }
+
+    #[test]
+    fn test_parse_paren_child_with_attrs() {
+        let input = quote! {
+            div {
+                Button::new() @[flex] {},
+            }
+        };
+        let markup: Markup = parse2(input).unwrap();
+        if let Element::Native(el) = markup.element {
+            assert!(matches!(
+                el.children[0],
+                Child::Element(Element::Expression(_))
+            ));
+        } else {
+            panic!("Expected Native element");
+        }
+    }
+
+    #[test]
+    fn test_parse_component() {
+        let input = quote! { Header {} };
+        let markup: Markup = parse2(input).unwrap();
+        assert!(matches!(markup.element, Element::Component(_)));
+    }
+
+    #[test]
+    fn test_parse_component_with_arguments() {
+        let input = quote! { Button("Save", Size::Small) @[disabled: busy] {} };
+        let markup: Markup = parse2(input).unwrap();
+        if let Element::Component(el) = markup.element {
+            assert_eq!(el.arguments.len(), 2);
+            assert_eq!(el.attributes.len(), 1);
+        } else {
+            panic!("Expected Component element");
+        }
+    }
+
+    #[test]
+    fn test_parse_path_component() {
+        let input = quote! { ui::button::Button @[disabled: busy] {} };
+        let markup: Markup = parse2(input).unwrap();
+        if let Element::Component(el) = markup.element {
+            assert_eq!(el.name.segments.len(), 3);
+        } else {
+            panic!("Expected Component element");
+        }
+
+        let input = quote! { Picker::<FileFinderDelegate> {} };
+        let markup: Markup = parse2(input).unwrap();
+        assert!(matches!(markup.element, Element::Component(_)));
+
+        let input = quote! { Button::new("Save") {} };
+        let markup: Markup = parse2(input).unwrap();
+        assert!(matches!(markup.element, Element::Expression(_)));
+    }
+
+    #[test]
+    fn test_parse_props() {
+        let input = quote! { Button @[:label: "Save", :disabled, flex] {} };
+        let markup: Markup = parse2(input).unwrap();
+        let Element::Component(el) = markup.element else {
+            panic!("Expected Component element");
+        };
+        assert!(matches!(
+            el.attributes.as_slice(),
+            [
+                Attribute::Prop { value: Some(_), .. },
+                Attribute::Prop { value: None, .. },
+                Attribute::Flag(_),
+            ]
+        ));
+    }
+
+    #[test]
+    fn test_parse_component_with_attrs() {
+        let input = quote! {
+            Header @[flex, style: Primary] {}
+        };
+        let markup: Markup = parse2(input).unwrap();
+        if let Element::Component(el) = markup.element {
+            assert_eq!(el.attributes.len(), 2);
+        } else {
+            panic!("Expected Component element");
+        }
+    }
+
+    #[test]
+    fn test_parse_component_with_children() {
+        let input = quote! {
+            Container {
+                "Content",
+                div { "Nested" },
+            }
+        };
+        let markup: Markup = parse2(input).unwrap();
+        if let Element::Component(el) = markup.element {
+            assert_eq!(el.children.len(), 2);
+        } else {
+            panic!("Expected Component element");
+        }
+    }
+
+    #[test]
+    fn test_parse_component_child() {
+        let input = quote! {
+            div {
+                Header @[flex] {},
+            }
+        };
+        let markup: Markup = parse2(input).unwrap();
+        if let Element::Native(el) = markup.element {
+            assert!(matches!(
+                el.children[0],
+                Child::Element(Element::Component(_))
+            ));
+        } else {
+            panic!("Expected Native element");
+        }
+    }
+}
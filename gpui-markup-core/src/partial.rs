@@ -0,0 +1,306 @@
+//! Best-effort expansion of markup that doesn't parse, for IDE completion.
+//!
+//! ```ignore
+//! ui! {
+//!     div @[fl] // missing braces while typing
+//! }
+//! ```
+//!
+//! Markup with a syntax error expands to the errors alone, which leaves an
+//! IDE nothing to complete: rust-analyzer completes inside a macro call by
+//! expanding it again with a placeholder at the cursor, and finding that
+//! placeholder in the expansion. So when running in rust-analyzer, the errors
+//! are followed by a sketch of the markup read leniently, without the
+//! checks and lowering of the parser:
+//!
+//! ```ignore
+//! {
+//!     let __element = gpui::div();
+//!     __element.fl();
+//! }
+//! ```
+//!
+//! Element heads become their constructor, attributes method calls on it,
+//! and expression children `let _ = expr;`, keeping the spans of the input
+//! so methods, paths and variables can still be completed. Tokens that don't
+//! fit are skipped. The compiler never sees the sketch.
+
+use proc_macro2::{Span, TokenStream, TokenTree};
+use quote::{ToTokens, quote};
+use syn::ext::IdentExt;
+use syn::parse::discouraged::Speculative;
+use syn::parse::{ParseStream, Parser};
+use syn::token::{Brace, Bracket, Paren};
+use syn::{Expr, Ident, Path, Token};
+
+use crate::config::Config;
+use crate::theme::expand_theme_tokens;
+use crate::units::expand_units;
+
+/// Native elements built on a `div`.
+const DIV_ELEMENTS: &[&str] = &["div", "h_flex", "v_flex", "stack", "spacer", "divider"];
+
+/// Native elements built by the gpui function of the same name.
+const GPUI_ELEMENTS: &[&str] = &["svg", "img", "anchored"];
+
+/// The syntax errors in `error` as an expression, like `expression_errors`,
+/// followed by a sketch of the markup `tokens` when expanded by
+/// rust-analyzer.
+#[must_use]
+pub fn partial_expansion(error: &syn::Error, tokens: TokenStream) -> TokenStream {
+    let errors = error.to_compile_error();
+    let sketch = if is_rust_analyzer() {
+        sketch(tokens)
+    } else {
+        TokenStream::new()
+    };
+    quote! {{ #errors #sketch }}
+}
+
+/// Whether the macro is expanded by the proc-macro server of rust-analyzer.
+fn is_rust_analyzer() -> bool {
+    std::env::var_os("RUST_ANALYZER_INTERNALS_DO_NOT_USE").is_some()
+        || std::env::current_exe().is_ok_and(|exe| {
+            exe.file_name()
+                .is_some_and(|name| name.to_string_lossy().starts_with("rust-analyzer"))
+        })
+}
+
+/// Statements for the children in `tokens`, read leniently.
+fn sketch(tokens: TokenStream) -> TokenStream {
+    (|input: ParseStream| Ok(sketch_children(input)))
+        .parse2(tokens)
+        .unwrap_or_default()
+}
+
+fn sketch_children(input: ParseStream) -> TokenStream {
+    let mut statements = TokenStream::new();
+    while !input.is_empty() {
+        statements.extend(sketch_child(input));
+    }
+    statements
+}
+
+fn sketch_child(input: ParseStream) -> TokenStream {
+    let element = Ident::new("__element", Span::mixed_site());
+    if input.parse::<Token![#]>().is_ok() {
+        // The header, or the `#[cfg(...)]` of a child
+        let _ = input.parse::<Token![!]>();
+        group_stream(input, Bracket);
+        return TokenStream::new();
+    }
+    if input.parse::<Token![..]>().is_ok() {
+        return parse_expr(input).map_or_else(TokenStream::new, |expr| quote! { let _ = #expr; });
+    }
+    if input.peek(Token![.]) {
+        let chain = take_until_comma(input);
+        return syn::parse2::<Expr>(quote! { #element #chain })
+            .map_or_else(|_| TokenStream::new(), |expr| quote! { #expr; });
+    }
+    if let Some(base) = element_base(input) {
+        let mut statements = TokenStream::new();
+        while input.parse::<Token![@]>().is_ok() {
+            if input.peek(Ident) {
+                skip_token(input);
+                if input.peek(Paren) {
+                    skip_token(input);
+                }
+            }
+            if let Some(content) = group_stream(input, Bracket) {
+                let content = expand_units(expand_theme_tokens(content));
+                statements.extend(
+                    (|input: ParseStream| Ok(sketch_attributes(input)))
+                        .parse2(content)
+                        .unwrap_or_default(),
+                );
+            }
+        }
+        if let Some(content) = group_stream(input, Brace) {
+            statements.extend(sketch(content));
+        }
+        return quote! {
+            {
+                let #element = #base;
+                #statements
+            }
+        };
+    }
+    let fork = input.fork();
+    if let Ok(expr) = fork.parse::<Expr>() {
+        input.advance_to(&fork);
+        return quote! { let _ = #expr; };
+    }
+    skip_token(input);
+    TokenStream::new()
+}
+
+/// The constructor of the element at the start of `input`, consuming its
+/// head: `div`, `Button("Save")` or `(expr)` followed by attributes or
+/// children.
+fn element_base(input: ParseStream) -> Option<TokenStream> {
+    let fork = input.fork();
+    if fork.peek(Paren) {
+        let group: TokenTree = fork.parse().ok()?;
+        if !(fork.peek(Token![@]) || fork.peek(Brace)) {
+            return None;
+        }
+        input.advance_to(&fork);
+        return Some(group.into_token_stream());
+    }
+
+    let path = fork.call(Path::parse_mod_style).ok()?;
+    let arguments = group_stream(&fork, Paren).unwrap_or_default();
+    let name = path.segments.last()?.ident.to_string();
+    let is_component = name.starts_with(char::is_uppercase);
+    let is_native = path.segments.len() == 1
+        && (DIV_ELEMENTS.contains(&name.as_str()) || GPUI_ELEMENTS.contains(&name.as_str()));
+    let ends = fork.is_empty() || fork.peek(Token![,]);
+    if !(fork.peek(Token![@]) || fork.peek(Brace) || ((is_component || is_native) && ends)) {
+        return None;
+    }
+    input.advance_to(&fork);
+
+    let gpui = Config::current().gpui();
+    let ident = &path.segments[0].ident;
+    Some(if DIV_ELEMENTS.contains(&name.as_str()) {
+        let div = Ident::new("div", ident.span());
+        quote! { #gpui::#div() }
+    } else if is_native {
+        quote! { #gpui::#ident(#arguments) }
+    } else if is_component {
+        quote! { #path::new(#arguments) }
+    } else {
+        path.into_token_stream()
+    })
+}
+
+/// Method calls on the element for the attributes in `input`.
+fn sketch_attributes(input: ParseStream) -> TokenStream {
+    let mut statements = TokenStream::new();
+    while !input.is_empty() {
+        if input.parse::<Token![,]>().is_ok() {
+            continue;
+        }
+        if is_colon(input) {
+            skip_token(input);
+        }
+        let Ok(mut key) = input.call(Ident::parse_any) else {
+            skip_token(input);
+            continue;
+        };
+        if key == "on" && is_colon(input) {
+            skip_token(input);
+            if let Ok(event) = input.call(Ident::parse_any) {
+                key = Ident::new(&format!("on_{event}"), event.span());
+            }
+            while !(input.is_empty() || input.peek(Token![,]) || is_colon(input)) {
+                skip_token(input);
+            }
+        }
+        let value = if is_colon(input) {
+            skip_token(input);
+            if let Some(content) = group_stream(input, Brace) {
+                // A platform block, `macos: { pt: px(28.0) }`
+                statements.extend(
+                    (|input: ParseStream| Ok(sketch_attributes(input)))
+                        .parse2(content)
+                        .unwrap_or_default(),
+                );
+                continue;
+            }
+            parse_expr(input)
+        } else {
+            take_until_comma(input);
+            None
+        };
+        if syn::parse2::<Ident>(key.to_token_stream()).is_ok() {
+            let element = Ident::new("__element", Span::mixed_site());
+            statements.extend(quote! { #element.#key(#value); });
+        }
+    }
+    statements
+}
+
+/// The expression up to the next `,`, if it parses.
+fn parse_expr(input: ParseStream) -> Option<Expr> {
+    syn::parse2(take_until_comma(input)).ok()
+}
+
+fn take_until_comma(input: ParseStream) -> TokenStream {
+    let mut tokens = TokenStream::new();
+    while !input.is_empty() && !input.peek(Token![,]) {
+        tokens.extend(input.parse::<TokenTree>());
+    }
+    tokens
+}
+
+/// The contents of the group at the start of `input`, if it is delimited
+/// like `delimiter`.
+fn group_stream(input: ParseStream, delimiter: impl syn::parse::Peek) -> Option<TokenStream> {
+    if !input.peek(delimiter) {
+        return None;
+    }
+    match input.parse::<TokenTree>() {
+        Ok(TokenTree::Group(group)) => Some(group.stream()),
+        _ => None,
+    }
+}
+
+/// Whether `input` starts with a single `:`.
+fn is_colon(input: ParseStream) -> bool {
+    input.peek(Token![:]) && !input.peek(Token![::])
+}
+
+fn skip_token(input: ParseStream) {
+    let _ = input.parse::<TokenTree>();
+}
+
+#[cfg(test)]
+mod tests {
+    use quote::quote;
+
+    use super::*;
+
+    fn assert_sketch(markup: TokenStream, expected: &TokenStream) {
+        assert_eq!(sketch(markup).to_string(), expected.to_string());
+    }
+
+    #[test]
+    fn test_sketch_missing_braces() {
+        assert_sketch(
+            quote! { div @[fl] },
+            &quote! {{ let __element = gpui::div(); __element.fl(); }},
+        );
+    }
+
+    #[test]
+    fn test_sketch_attributes() {
+        assert_sketch(
+            quote! { Button("Save") @[w: 200px, on:click: handler, macos: { pt: }, use: x] },
+            &quote! {{
+                let __element = Button::new("Save");
+                __element.w(gpui::px(200.0));
+                __element.on_click(handler);
+                __element.pt();
+            }},
+        );
+    }
+
+    #[test]
+    fn test_sketch_children() {
+        assert_sketch(
+            quote! {
+                #![options(checked)]
+                div @hover[bg: $surface] { "Title" na, ..items, .when(a, |d| d) }
+            },
+            &quote! {{
+                let __element = gpui::div();
+                __element.bg(cx.theme().surface);
+                let _ = "Title";
+                let _ = na;
+                let _ = items;
+                __element.when(a, |d| d);
+            }},
+        );
+    }
+}
@@ -0,0 +1,98 @@
+//! `portal(window) { .. }`: a modal layer escaping the clipping and stacking
+//! of its parents, for modals and toasts.
+//!
+//! The children are laid out in a div covering `window`, anchored at its
+//! origin and painted after the rest of the frame with `gpui::deferred`:
+//!
+//! ```text
+//! deferred(anchored().position(origin).position_mode(Window).child(layer))
+//!     .with_priority(priority)
+//! ```
+//!
+//! The attributes of the portal style the layer, except `priority: n` (`1` by
+//! default), which orders overlapping portals. Like the constructor arguments
+//! of `canvas`, the priority and window are kept as the first children of the
+//! element.
+
+use proc_macro_error2::abort;
+use proc_macro2::{Ident, Span, TokenStream};
+use quote::quote;
+use syn::{Expr, parse_quote};
+
+use crate::ast::{Attribute, Child};
+use crate::config::Config;
+
+/// Move the `priority` of a portal and the window of `portal(window)` in
+/// front of its children.
+pub fn portal_arguments(
+    name: &Ident,
+    arguments: Option<Vec<Expr>>,
+    attributes: &mut Vec<Attribute>,
+    mut children: Vec<Child>,
+) -> Vec<Child> {
+    let mut arguments = arguments.unwrap_or_default().into_iter();
+    let Some(window) = arguments.next() else {
+        abort!(
+            name,
+            "`portal` covers the window it is given, e.g. `portal(window) { .. }`"
+        );
+    };
+    if let Some(extra) = arguments.next() {
+        abort!(
+            extra,
+            "`portal(window)` takes a single argument, the window"
+        );
+    }
+    let priority = attributes
+        .iter()
+        .position(|attr| match attr {
+            Attribute::KeyValue { key, .. } => key == "priority",
+            Attribute::Flag(key) if key == "priority" => {
+                abort!(key, "`priority` takes a value, e.g. `priority: 10`")
+            }
+            _ => false,
+        })
+        .map(|index| attributes.remove(index));
+    let priority = match priority {
+        Some(Attribute::KeyValue { value, .. }) => value,
+        _ => parse_quote! { 1 },
+    };
+    children.splice(
+        0..0,
+        [Child::expression(priority), Child::expression(window)],
+    );
+    children
+}
+
+/// The constructor of the layer holding the children of a portal, covering
+/// the window. `Styled::size` takes a single length, so the width and height
+/// of the viewport are set separately, evaluating `window` once.
+pub fn portal_layer(window: &Child) -> TokenStream {
+    let gpui = Config::current().gpui();
+    let window_type = Config::current().item("Window");
+    let styled = Config::current().item("Styled");
+    let window = window.element();
+    let size = Ident::new("__size", Span::mixed_site());
+    quote! {
+        {
+            let #size = #window_type::viewport_size(#window);
+            #styled::h(#styled::w(#gpui::div(), #size.width), #size.height)
+        }
+    }
+}
+
+/// Wrap the generated layer of a portal in its deferred, anchored container.
+pub fn portal(priority: &Child, layer: &TokenStream) -> TokenStream {
+    let gpui = Config::current().gpui();
+    let parent_element = Config::current().item("ParentElement");
+    let priority = priority.element();
+    quote! {
+        #gpui::deferred(#parent_element::child(
+            #gpui::anchored()
+                .position(#gpui::Point::default())
+                .position_mode(#gpui::AnchoredPositionMode::Window),
+            #layer,
+        ))
+        .with_priority(#priority)
+    }
+}
@@ -0,0 +1,347 @@
+//! Presets teaching the macro about component libraries, each enabled by a
+//! cargo feature:
+//!
+//! - `gpui-component`: `h_flex` and `v_flex` build with
+//!   `gpui_component::h_flex()` / `v_flex()`, and the props of its components
+//!   (`Button`, `Label`, ...) are checked.
+//! - `zed-ui`: the same for Zed's `ui` crate, with `ui::h_flex()` /
+//!   `ui::v_flex()` and components like `Label`, `IconButton` and `ButtonLike`,
+//!   and `tooltip: "..."` builds with `ui::Tooltip::text`.
+//!
+//! Props (`:label: ..`) of a component named like one of the library's, by
+//! the last segment of its path, are checked against its builder methods: a
+//! prop close to a known one is reported as a typo. Other props are left
+//! alone, as the component may be an app's own or come from a newer version,
+//! and so are other attributes, since the components are also `Styled`.
+
+use proc_macro_error2::abort;
+use proc_macro2::TokenStream;
+use syn::Path;
+
+use crate::ast::Attribute;
+use crate::suggest::closest_match;
+
+/// A component library known to the macro.
+struct Preset {
+    /// Constructors of the `h_flex` and `v_flex` elements
+    flex: Option<(&'static str, &'static str)>,
+    /// Function building a tooltip from the text of `tooltip: "..."`
+    tooltip: Option<&'static str>,
+    /// Components and the builder methods accepted as their props
+    components: &'static [(&'static str, &'static [&'static str])],
+}
+
+/// Builder methods shared by sizable components (`Sizable`).
+#[cfg(feature = "gpui-component")]
+macro_rules! sizable {
+    ($($method:literal),* $(,)?) => {
+        &[$($method,)* "xsmall", "small", "large", "with_size"]
+    };
+}
+
+#[cfg(feature = "gpui-component")]
+const GPUI_COMPONENT: Preset = Preset {
+    flex: Some(("gpui_component::h_flex", "gpui_component::v_flex")),
+    tooltip: None,
+    components: &[
+        (
+            "Button",
+            sizable![
+                "label",
+                "icon",
+                "primary",
+                "danger",
+                "warning",
+                "success",
+                "info",
+                "ghost",
+                "link",
+                "text",
+                "outline",
+                "compact",
+                "rounded",
+                "selected",
+                "disabled",
+                "loading",
+                "loading_icon",
+                "tooltip",
+                "dropdown_caret",
+                "tab_stop",
+                "on_click",
+            ],
+        ),
+        ("Label", &["secondary", "masked"]),
+        ("Icon", sizable!["path", "rotate"]),
+        (
+            "Checkbox",
+            sizable!["label", "checked", "disabled", "tooltip", "on_click"],
+        ),
+        (
+            "Switch",
+            sizable![
+                "label",
+                "label_side",
+                "checked",
+                "disabled",
+                "tooltip",
+                "on_click",
+            ],
+        ),
+        (
+            "Radio",
+            sizable!["label", "checked", "disabled", "on_click"],
+        ),
+        (
+            "Input",
+            sizable![
+                "prefix",
+                "suffix",
+                "cleanable",
+                "mask_toggle",
+                "appearance",
+                "bordered",
+                "focus_bordered",
+                "disabled",
+            ],
+        ),
+    ],
+};
+
+/// Builder methods of Zed's clickable buttons (`ButtonCommon`, `Clickable`,
+/// `Disableable`, `Toggleable`, `SelectableButton` and `FixedWidth`).
+#[cfg(feature = "zed-ui")]
+macro_rules! zed_button {
+    ($($method:literal),* $(,)?) => {
+        &[
+            $($method,)*
+            "style",
+            "size",
+            "tooltip",
+            "tab_index",
+            "layer",
+            "on_click",
+            "cursor_style",
+            "disabled",
+            "toggle_state",
+            "selected_style",
+            "width",
+            "full_width",
+        ]
+    };
+}
+
+/// Builder methods of Zed's labels (`LabelCommon`).
+#[cfg(feature = "zed-ui")]
+macro_rules! zed_label {
+    ($($method:literal),* $(,)?) => {
+        &[
+            $($method,)*
+            "size",
+            "weight",
+            "line_height_style",
+            "color",
+            "strikethrough",
+            "italic",
+            "underline",
+            "alpha",
+            "truncate",
+            "single_line",
+            "buffer_font",
+        ]
+    };
+}
+
+#[cfg(feature = "zed-ui")]
+const ZED_UI: Preset = Preset {
+    flex: Some(("ui::h_flex", "ui::v_flex")),
+    tooltip: Some("ui::Tooltip::text"),
+    components: &[
+        ("Label", zed_label![]),
+        ("HighlightedLabel", zed_label![]),
+        ("Headline", &["size", "color"]),
+        ("Icon", &["size", "color", "transform"]),
+        (
+            "Button",
+            zed_button![
+                "color",
+                "label_size",
+                "selected_label",
+                "selected_label_color",
+                "icon",
+                "icon_position",
+                "icon_size",
+                "icon_color",
+                "selected_icon",
+                "selected_icon_color",
+                "key_binding",
+                "key_binding_position",
+                "alpha",
+                "truncate",
+                "loading",
+            ],
+        ),
+        (
+            "IconButton",
+            zed_button![
+                "shape",
+                "icon_size",
+                "icon_color",
+                "selected_icon",
+                "selected_icon_color",
+                "indicator",
+                "indicator_border_color",
+                "alpha",
+            ],
+        ),
+        ("ButtonLike", zed_button!["rounding"]),
+        (
+            "ListItem",
+            &[
+                "spacing",
+                "selectable",
+                "disabled",
+                "toggle_state",
+                "indent_level",
+                "indent_step_size",
+                "inset",
+                "start_slot",
+                "end_slot",
+                "end_hover_slot",
+                "toggle",
+                "on_toggle",
+                "on_click",
+                "on_secondary_mouse_down",
+                "tooltip",
+                "outlined",
+                "rounded",
+            ],
+        ),
+    ],
+};
+
+/// Presets enabled by cargo features.
+const PRESETS: &[Preset] = &[
+    #[cfg(feature = "gpui-component")]
+    GPUI_COMPONENT,
+    #[cfg(feature = "zed-ui")]
+    ZED_UI,
+];
+
+/// Constructor of the `h_flex` or `v_flex` element from an enabled preset.
+pub fn flex_helper(tag: &str) -> Option<TokenStream> {
+    let (h_flex, v_flex) = PRESETS.iter().find_map(|preset| preset.flex)?;
+    Some(preset_path(if tag == "h_flex" { h_flex } else { v_flex }))
+}
+
+/// Function building a tooltip from its text from an enabled preset.
+pub fn tooltip_helper() -> Option<TokenStream> {
+    PRESETS
+        .iter()
+        .find_map(|preset| preset.tooltip)
+        .map(preset_path)
+}
+
+fn preset_path(path: &str) -> TokenStream {
+    syn::parse_str::<Path>(path).map_or_else(
+        |_| unreachable!("preset paths are valid"),
+        quote::ToTokens::into_token_stream,
+    )
+}
+
+/// Check the props of a component known to an enabled preset.
+pub fn check_preset_props(component: &Path, attributes: &[Attribute]) {
+    let Some(name) = component.segments.last().map(|segment| &segment.ident) else {
+        return;
+    };
+    let props: Vec<&str> = PRESETS
+        .iter()
+        .flat_map(|preset| preset.components)
+        .filter(|(known, _)| name == known)
+        .flat_map(|(_, props)| props.iter().copied())
+        .collect();
+    if props.is_empty() {
+        return;
+    }
+    check_props(name, attributes, &props);
+}
+
+fn check_props(component: &syn::Ident, attributes: &[Attribute], props: &[&str]) {
+    for attr in attributes {
+        match attr {
+            Attribute::Prop { key, .. } if !props.iter().any(|prop| key == prop) => {
+                if let Some(suggestion) = closest_match(&key.to_string(), props.iter().copied()) {
+                    abort!(
+                        key,
+                        "`{}` has no prop `:{}`, did you mean `:{}`?",
+                        component,
+                        key,
+                        suggestion
+                    );
+                }
+            }
+            Attribute::Platform { attributes, .. } => check_props(component, attributes, props),
+            _ => {}
+        }
+    }
+}
+
+#[cfg(all(test, any(feature = "gpui-component", feature = "zed-ui")))]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg(feature = "gpui-component")]
+    fn test_flex_helper() {
+        assert_eq!(
+            flex_helper("v_flex").unwrap().to_string(),
+            "gpui_component :: v_flex"
+        );
+    }
+
+    #[test]
+    #[cfg(all(feature = "zed-ui", not(feature = "gpui-component")))]
+    fn test_zed_flex_helper() {
+        assert_eq!(flex_helper("h_flex").unwrap().to_string(), "ui :: h_flex");
+    }
+
+    #[test]
+    #[cfg(feature = "zed-ui")]
+    fn test_zed_tooltip_helper() {
+        assert_eq!(
+            tooltip_helper().unwrap().to_string(),
+            "ui :: Tooltip :: text"
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "zed-ui")]
+    fn test_zed_props() {
+        let attributes = vec![
+            Attribute::Prop {
+                key: syn::parse_quote!(icon_size),
+                value: Some(syn::parse_quote!(IconSize::Small)),
+            },
+            Attribute::Prop {
+                key: syn::parse_quote!(toggle_state),
+                value: Some(syn::parse_quote!(active)),
+            },
+        ];
+        check_preset_props(&syn::parse_quote!(ui::IconButton), &attributes);
+    }
+
+    #[test]
+    #[cfg(feature = "gpui-component")]
+    fn test_known_props() {
+        let attributes = vec![Attribute::Prop {
+            key: syn::parse_quote!(primary),
+            value: None,
+        }];
+        check_preset_props(&syn::parse_quote!(button::Button), &attributes);
+        check_preset_props(&syn::parse_quote!(MyButton), &attributes);
+        let attributes = vec![Attribute::Prop {
+            key: syn::parse_quote!(on_drop),
+            value: None,
+        }];
+        check_preset_props(&syn::parse_quote!(Button), &attributes);
+    }
+}
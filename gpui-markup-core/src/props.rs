@@ -0,0 +1,267 @@
+//! `#[derive(Props)]`: generate the builder of a component struct.
+//!
+//! Every named field gets a setter taking `impl Into<T>`, so `ui!` props
+//! accept `&str` for `SharedString` and the like. `Option<T>` fields take the
+//! inner type and are wrapped in `Some`. Primitive fields take the value
+//! itself, which keeps integer literals inferring the field type.
+//!
+//! `#[component]` functions get the same builder, with a prop per parameter.
+//!
+//! ```ignore
+//! #[derive(IntoElement, Props)]
+//! struct Badge {
+//!     label: SharedString,
+//!     icon: Option<IconName>,
+//!     #[prop(default = 1)]
+//!     count: usize,
+//!     #[prop(skip, default = div())]
+//!     base: Div,
+//! }
+//! ```
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::spanned::Spanned;
+use syn::{
+    Attribute, Data, DeriveInput, Error, Expr, Fields, GenericArgument, Generics, Ident,
+    PathArguments, Result, Type, Visibility,
+};
+
+/// Types whose setter takes the value itself rather than `impl Into<T>`.
+const PRIMITIVE_TYPES: &[&str] = &[
+    "bool", "char", "f32", "f64", "i8", "i16", "i32", "i64", "i128", "isize", "u8", "u16", "u32",
+    "u64", "u128", "usize",
+];
+
+/// Options of the `#[prop(...)]` field attribute.
+#[derive(Default)]
+struct Options {
+    /// Initial value, `Default::default()` if unset.
+    default: Option<Expr>,
+    /// Don't generate a setter.
+    skip: bool,
+}
+
+impl Options {
+    fn parse(attrs: &[Attribute]) -> Result<Self> {
+        let mut options = Self::default();
+        for attr in attrs.iter().filter(|attr| attr.path().is_ident("prop")) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("default") {
+                    options.default = Some(meta.value()?.parse()?);
+                    Ok(())
+                } else if meta.path.is_ident("skip") {
+                    options.skip = true;
+                    Ok(())
+                } else {
+                    Err(meta.error("unknown `prop` option, expected `default` or `skip`"))
+                }
+            })?;
+        }
+        Ok(options)
+    }
+}
+
+/// A field of a component, set through its builder.
+pub struct Prop {
+    pub name: Ident,
+    pub ty: Type,
+    /// The doc comments and `#[cfg(...)]` attributes of the field.
+    pub attrs: Vec<Attribute>,
+    options: Options,
+}
+
+impl Prop {
+    /// The prop of the field `name`, with the options of its `#[prop(...)]`
+    /// attributes among `attrs`.
+    pub fn new(name: Ident, ty: Type, attrs: &[Attribute]) -> Result<Self> {
+        Ok(Self {
+            name,
+            ty,
+            attrs: attrs
+                .iter()
+                .filter(|attr| attr.path().is_ident("doc") || attr.path().is_ident("cfg"))
+                .cloned()
+                .collect(),
+            options: Options::parse(attrs)?,
+        })
+    }
+
+    /// The `#[cfg(...)]` attributes of the field, repeated wherever it is
+    /// named.
+    pub fn cfgs(&self) -> impl Iterator<Item = &Attribute> {
+        self.attrs.iter().filter(|attr| attr.path().is_ident("cfg"))
+    }
+
+    /// The initializer of the field in `new()`.
+    fn default(&self) -> TokenStream {
+        let name = &self.name;
+        let cfgs = self.cfgs();
+        let value = self.options.default.as_ref().map_or_else(
+            || quote! { ::std::default::Default::default() },
+            |default| quote! { #default },
+        );
+        quote! { #(#cfgs)* #name: #value }
+    }
+
+    /// The setter of the field:
+    /// `fn label(mut self, label: impl Into<T>) -> Self`.
+    fn setter(&self, vis: &Visibility) -> TokenStream {
+        let name = &self.name;
+        let cfgs = self.cfgs();
+        let (ty, wrap) = option_inner(&self.ty).map_or((&self.ty, false), |inner| (inner, true));
+        let (param, mut value) = if is_primitive(ty) {
+            (quote! { #ty }, quote! { #name })
+        } else {
+            (
+                quote! { impl ::std::convert::Into<#ty> },
+                quote! { ::std::convert::Into::into(#name) },
+            )
+        };
+        if wrap {
+            value = quote! { ::std::option::Option::Some(#value) };
+        }
+
+        let doc = format!("Set the `{name}` prop.");
+        quote! {
+            #(#cfgs)*
+            #[doc = #doc]
+            #[must_use]
+            #vis fn #name(mut self, #name: #param) -> Self {
+                self.#name = #value;
+                self
+            }
+        }
+    }
+}
+
+/// `new()` and the setters of `props` for the component `name`.
+pub fn builder(name: &Ident, generics: &Generics, vis: &Visibility, props: &[Prop]) -> TokenStream {
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let defaults = props.iter().map(Prop::default);
+    let setters = props
+        .iter()
+        .filter(|prop| !prop.options.skip)
+        .map(|prop| prop.setter(vis));
+    quote! {
+        impl #impl_generics #name #ty_generics #where_clause {
+            /// Construct the component with every prop at its default.
+            #[must_use]
+            #vis fn new() -> Self {
+                Self { #(#defaults,)* }
+            }
+
+            #(#setters)*
+        }
+    }
+}
+
+/// The `T` of an `Option<T>` field.
+fn option_inner(ty: &Type) -> Option<&Type> {
+    let Type::Path(ty) = ty else {
+        return None;
+    };
+    let segment = ty.path.segments.last()?;
+    if segment.ident != "Option" {
+        return None;
+    }
+    let PathArguments::AngleBracketed(arguments) = &segment.arguments else {
+        return None;
+    };
+    match arguments.args.first()? {
+        GenericArgument::Type(inner) if arguments.args.len() == 1 => Some(inner),
+        _ => None,
+    }
+}
+
+fn is_primitive(ty: &Type) -> bool {
+    matches!(ty, Type::Path(ty) if ty.qself.is_none()
+        && PRIMITIVE_TYPES.iter().any(|name| ty.path.is_ident(name)))
+}
+
+/// Expand `#[derive(Props)]` into `new()` and a setter per field.
+///
+/// # Errors
+///
+/// Returns an error if `input` is not a struct with named fields, or a
+/// `#[prop(...)]` attribute is malformed.
+pub fn derive_props(input: &DeriveInput) -> Result<TokenStream> {
+    let Data::Struct(data) = &input.data else {
+        return Err(Error::new(
+            input.ident.span(),
+            "`Props` can only be derived for structs",
+        ));
+    };
+    let Fields::Named(fields) = &data.fields else {
+        return Err(Error::new(
+            data.fields.span(),
+            "`Props` requires named fields",
+        ));
+    };
+
+    let props = fields
+        .named
+        .iter()
+        .filter_map(|field| {
+            let name = field.ident.clone()?;
+            Some(Prop::new(name, field.ty.clone(), &field.attrs))
+        })
+        .collect::<Result<Vec<_>>>()?;
+    Ok(builder(&input.ident, &input.generics, &input.vis, &props))
+}
+
+#[cfg(test)]
+mod tests {
+    use insta::assert_snapshot;
+    use syn::parse_quote;
+
+    use super::*;
+    use crate::component::expand_component;
+
+    #[test]
+    fn test_builders() {
+        let derived = derive_props(&parse_quote! {
+            pub struct Badge {
+                /// Shown in the badge
+                label: SharedString,
+                icon: Option<IconName>,
+                #[prop(default = 1)]
+                count: usize,
+                #[cfg(feature = "dot")]
+                dot: bool,
+                #[prop(skip, default = div())]
+                base: Div,
+            }
+        })
+        .unwrap();
+        let component = expand_component(
+            &TokenStream::new(),
+            &parse_quote! {
+                /// A badge written as a function
+                #[inline]
+                #[must_use]
+                pub fn badge(
+                    /// Shown in the badge
+                    label: SharedString,
+                    icon: Option<IconName>,
+                    #[prop(default = 1)] count: usize,
+                    #[cfg(feature = "dot")] dot: bool,
+                    cx: &mut App,
+                ) -> impl IntoElement {
+                    div()
+                }
+            },
+        )
+        .unwrap();
+        let output = quote! { #derived #component };
+        assert_snapshot!(prettyplease::unparse(&syn::parse2(output).unwrap()));
+    }
+
+    #[test]
+    fn test_tuple_struct() {
+        let input: DeriveInput = parse_quote! {
+            struct Badge(SharedString);
+        };
+        assert!(derive_props(&input).is_err());
+    }
+}
@@ -0,0 +1,150 @@
+//! Custom native elements declared by the `#![elements(..)]` header:
+//!
+//! ```ignore
+//! ui! {
+//!     #![elements(icon = my_ui::icon -> my_ui::Icon, row = my_ui::h_flex -> gpui::Div)]
+//!     row @[gap_2] { icon @[size: px(16.0)] {}, "Saved" }
+//! }
+//! ```
+//!
+//! A declared tag is a native element built by calling its constructor, with
+//! the declared type as its element type. Tags are resolved while the root
+//! element is parsed, so the declarations of an invocation are kept in a
+//! thread-local for its duration.
+//!
+//! Elements of the `[elements]` table of `gpui-markup.toml`, written
+//! `icon = "my_ui::icon -> my_ui::Icon"`, are declared in every invocation.
+//! A header declaring the same tag overrides them.
+
+use std::cell::RefCell;
+
+use proc_macro_error2::abort;
+use syn::parse::{Parse, ParseStream};
+use syn::{Ident, Path, Result, Token, Type};
+
+use crate::config::Config;
+
+/// A custom element: `tag = constructor -> Type`.
+#[derive(Debug, Clone)]
+pub struct ElementDef {
+    pub tag: Ident,
+    /// Function called without arguments to create the element
+    pub constructor: Path,
+    /// The element type returned by the constructor
+    pub ty: Type,
+}
+
+impl Parse for ElementDef {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let tag: Ident = input.parse()?;
+        input.parse::<Token![=]>()?;
+        let constructor = input.parse()?;
+        input.parse::<Token![->]>()?;
+        let ty = input.parse()?;
+        Ok(Self {
+            tag,
+            constructor,
+            ty,
+        })
+    }
+}
+
+impl ElementDef {
+    /// Whether the element type is `Stateful<..>`, which already has an id.
+    pub fn is_stateful(&self) -> bool {
+        matches!(
+            &self.ty,
+            Type::Path(path) if path.path.segments.last().is_some_and(|segment| segment.ident == "Stateful")
+        )
+    }
+}
+
+thread_local! {
+    static ELEMENTS: RefCell<Vec<ElementDef>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Check declared tags, which must be lowercase and not shadow a built-in
+/// element or an earlier declaration.
+pub fn check_element_defs(defs: &[ElementDef], is_builtin: impl Fn(&str) -> bool) {
+    for (index, def) in defs.iter().enumerate() {
+        let tag = def.tag.to_string();
+        if !tag.starts_with(|c: char| c.is_ascii_lowercase()) {
+            abort!(
+                def.tag,
+                "custom element `{}` must be lowercase, uppercase tags are components",
+                tag
+            );
+        }
+        if is_builtin(&tag) {
+            abort!(def.tag, "`{}` is a built-in element", tag);
+        }
+        if defs[..index].iter().any(|earlier| earlier.tag == def.tag) {
+            abort!(def.tag, "custom element `{}` is declared twice", tag);
+        }
+    }
+}
+
+/// Run `f` with `defs` declared, restoring the previous declarations after.
+pub fn with_elements<R>(defs: &[ElementDef], f: impl FnOnce() -> R) -> R {
+    let previous = ELEMENTS.with(|elements| elements.replace(defs.to_vec()));
+    let result = f();
+    ELEMENTS.with(|elements| *elements.borrow_mut() = previous);
+    result
+}
+
+/// The declaration of the custom element `name`, if any.
+pub fn custom_element(name: &str) -> Option<ElementDef> {
+    ELEMENTS
+        .with(|elements| {
+            elements
+                .borrow()
+                .iter()
+                .find(|def| def.tag == name)
+                .cloned()
+        })
+        .or_else(|| {
+            Config::current()
+                .elements()
+                .into_iter()
+                .find(|def| def.tag == name)
+        })
+}
+
+/// The tags of the declared custom elements.
+pub fn custom_tags() -> Vec<String> {
+    let mut tags: Vec<_> = ELEMENTS.with(|elements| {
+        elements
+            .borrow()
+            .iter()
+            .map(|def| def.tag.to_string())
+            .collect()
+    });
+    tags.extend(
+        Config::current()
+            .elements()
+            .iter()
+            .map(|def| def.tag.to_string()),
+    );
+    tags
+}
+
+#[cfg(test)]
+mod tests {
+    use syn::parse_quote;
+
+    use super::*;
+
+    #[test]
+    fn test_parse_element_def() {
+        let def: ElementDef = parse_quote!(row = my_ui::h_flex -> gpui::Stateful<gpui::Div>);
+        assert_eq!(def.tag, "row");
+        assert!(def.is_stateful());
+    }
+
+    #[test]
+    fn test_with_elements() {
+        let def: ElementDef = parse_quote!(icon = my_ui::icon -> my_ui::Icon);
+        with_elements(&[def], || assert!(custom_element("icon").is_some()));
+        assert!(custom_element("icon").is_none());
+    }
+}
@@ -0,0 +1,115 @@
+//! `render!`: implement `Render` for a view whose body is pure markup.
+//!
+//! ```ignore
+//! render! {
+//!     struct = Counter,
+//!     |self, window, cx| ui! { div { "Count:", text: self.count } }
+//! }
+//! ```
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::parse::{Parse, ParseStream};
+use syn::{Expr, Ident, Result, Token, Type};
+
+use crate::config::Config;
+use crate::declare::{parse_render_arguments, render_argument};
+
+/// A parsed `render!` invocation.
+pub struct RenderImpl {
+    /// The view type
+    ty: Type,
+    window: Option<Ident>,
+    cx: Option<Ident>,
+    body: Expr,
+}
+
+impl Parse for RenderImpl {
+    fn parse(input: ParseStream) -> Result<Self> {
+        input.parse::<Token![struct]>()?;
+        input.parse::<Token![=]>()?;
+        let ty = input.parse()?;
+        input.parse::<Token![,]>()?;
+
+        input.parse::<Token![|]>()?;
+        let (window, cx) = parse_render_arguments(input)?;
+        input.parse::<Token![|]>()?;
+        let body = input.parse()?;
+        input.parse::<Option<Token![,]>>()?;
+
+        Ok(Self {
+            ty,
+            window,
+            cx,
+            body,
+        })
+    }
+}
+
+/// Expand a `render!` invocation into an `impl Render` returning the body.
+#[must_use]
+pub fn expand_render(render: &RenderImpl) -> TokenStream {
+    let gpui = Config::current().gpui();
+    let into_element = Config::current().item("IntoElement");
+    let RenderImpl { ty, body, .. } = render;
+    let window = render_argument(render.window.as_ref(), "window");
+    let cx = render_argument(render.cx.as_ref(), "cx");
+
+    quote! {
+        impl #gpui::Render for #ty {
+            fn render(
+                &mut self,
+                #window: &mut #gpui::Window,
+                #cx: &mut #gpui::Context<Self>,
+            ) -> impl #into_element {
+                #body
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use syn::parse2;
+
+    use super::*;
+
+    fn expand(tokens: TokenStream) -> Result<String> {
+        parse2(tokens).map(|render| expand_render(&render).to_string())
+    }
+
+    #[test]
+    fn test_render() {
+        let output = expand(quote! {
+            struct = Counter,
+            |self, window, cx| ui! { div {} },
+        })
+        .unwrap();
+        assert_eq!(
+            output,
+            quote! {
+                impl gpui::Render for Counter {
+                    fn render(
+                        &mut self,
+                        window: &mut gpui::Window,
+                        cx: &mut gpui::Context<Self>,
+                    ) -> impl gpui::IntoElement {
+                        ui! { div {} }
+                    }
+                }
+            }
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn test_unnamed_arguments() {
+        let output = expand(quote! { struct = Empty, |self| gpui::Empty }).unwrap();
+        assert!(output.contains(&quote! { _window: &mut gpui::Window }.to_string()));
+    }
+
+    #[test]
+    fn test_missing_self() {
+        assert!(expand(quote! { struct = Empty, |window| gpui::Empty }).is_err());
+    }
+}
@@ -0,0 +1,460 @@
+//! Shorthand attributes that expand into one or more `Styled` calls.
+
+use proc_macro_error2::abort;
+use proc_macro2::{Ident, Span, TokenStream};
+use quote::quote;
+use syn::{Expr, ExprLit, ExprTuple, Lit};
+
+use crate::ast::Attribute;
+use crate::codegen::{Call, attribute_calls, key_value_call, method_call};
+use crate::lint::{AttributeLint, report};
+use crate::suggest::closest_match;
+
+/// Expand `key: value` if `key` is a shorthand, returning the method-call
+/// suffixes that replace it.
+pub fn expand_shorthand(key: &Ident, value: &Expr) -> Option<TokenStream> {
+    let name = key.to_string();
+    if SPACING_ATTRIBUTES.contains(&name.as_str()) && scale_step(value).is_some() {
+        return Some(spacing_call(&name, key.span(), value));
+    }
+
+    match name.as_str() {
+        "size" => size(key, value),
+        "font" => font(key, value),
+        "scroll" => Some(scroll(key, value)),
+        "p" | "m" => edges(key, value),
+        "border" | "border_t" | "border_b" | "border_l" | "border_r" | "border_x" | "border_y" => {
+            border(key, value)
+        }
+        "rounded" | "rounded_t" | "rounded_b" | "rounded_l" | "rounded_r" | "rounded_tl"
+        | "rounded_tr" | "rounded_bl" | "rounded_br" => keyword(key, value, ROUNDED_KEYWORDS),
+        "shadow" => keyword(key, value, SHADOW_KEYWORDS),
+        "grid_cols" | "grid_rows" => {
+            let grid = Ident::new("grid", key.span());
+            let call = key_value_call(key, value);
+            let grid = method_call(&grid, quote! {});
+            Some(quote! { #grid #call })
+        }
+        _ => None,
+    }
+}
+
+/// Readable names accepted for common `Styled` methods on native elements.
+const ALIASES: &[(&str, &str)] = &[
+    ("width", "w"),
+    ("height", "h"),
+    ("background", "bg"),
+    ("padding", "p"),
+    ("margin", "m"),
+    ("color", "text_color"),
+    // Applies a computed `StyleRefinement` like a `style!` mixin
+    ("style", "use"),
+];
+
+/// Rename aliased attribute keys (`width: ..`) to the method they stand for.
+///
+/// Only applied to native elements: components may define methods with these
+/// names (e.g. an icon's `color` or a button's `style`).
+pub fn resolve_aliases(attributes: &mut [Attribute]) {
+    for attr in attributes {
+        match attr {
+            Attribute::KeyValue { key, .. } => {
+                if let Some((_, method)) = ALIASES.iter().find(|(alias, _)| key == alias) {
+                    *key = Ident::new(method, key.span());
+                }
+            }
+            Attribute::PseudoState { attributes, .. }
+            | Attribute::Platform { attributes, .. }
+            | Attribute::Compound { attributes, .. } => resolve_aliases(attributes),
+            Attribute::Flag(_) | Attribute::Event { .. } | Attribute::Prop { .. } => {}
+        }
+    }
+}
+
+/// Composite attributes taking named arguments, with the arguments that are
+/// renamed to the method they lower to.
+const COMPOUND_ATTRIBUTES: &[(&str, &[(&str, &str)])] = &[
+    ("grid", &[("cols", "grid_cols"), ("rows", "grid_rows")]),
+    ("absolute", &[]),
+    ("relative", &[]),
+];
+
+/// Expand `key: (name: value, ...)` into `.key()` followed by the arguments.
+pub fn expand_compound(key: &Ident, attributes: &[Attribute], shorthands: bool) -> Vec<Call> {
+    let Some((_, renames)) = COMPOUND_ATTRIBUTES.iter().find(|(name, _)| key == name) else {
+        abort!(key, "`{}` does not take named arguments", key);
+    };
+
+    let mut calls = vec![Call::Method(method_call(key, quote! {}))];
+    for attr in attributes {
+        let argument = match attr {
+            Attribute::KeyValue { key, value } => renames
+                .iter()
+                .find(|(name, _)| key == name)
+                .map(|(_, method)| (Ident::new(method, key.span()), value)),
+            _ => None,
+        };
+        match argument {
+            Some((method, value)) => calls.push(Call::Method(key_value_call(&method, value))),
+            None => calls.extend(attribute_calls(std::slice::from_ref(attr), shorthands)),
+        }
+    }
+    calls
+}
+
+/// Scale keywords accepted by `rounded:` and its corner variants.
+pub const ROUNDED_KEYWORDS: &[&str] = &["none", "xs", "sm", "md", "lg", "xl", "2xl", "3xl", "full"];
+
+/// Scale keywords accepted by `shadow:`.
+pub const SHADOW_KEYWORDS: &[&str] = &["none", "2xs", "xs", "sm", "md", "lg", "xl", "2xl"];
+
+/// `rounded: md` lowers to `.rounded_md()`. Other values are left to the
+/// plain method, so variables still work. An identifier close to a keyword is
+/// reported by the `unknown_keyword` lint, while a suffixed literal like
+/// `2xll` can only be a misspelt keyword.
+fn keyword(key: &Ident, value: &Expr, keywords: &[&str]) -> Option<TokenStream> {
+    let (name, span, identifier) = match value {
+        Expr::Path(path) if path.qself.is_none() => {
+            let ident = path.path.get_ident()?;
+            (ident.to_string(), ident.span(), true)
+        }
+        // `2xl` lexes as the literal `2` with an `xl` suffix
+        Expr::Lit(ExprLit {
+            lit: Lit::Int(lit), ..
+        }) if !lit.suffix().is_empty() => (lit.to_string(), lit.span(), false),
+        _ => return None,
+    };
+
+    if keywords.contains(&name.as_str()) {
+        let method = Ident::new(&format!("{key}_{name}"), span);
+        return Some(method_call(&method, quote! {}));
+    }
+    if let Some(suggestion) = closest_match(&name, keywords.iter().copied()) {
+        if identifier {
+            report(
+                AttributeLint::Keyword,
+                span,
+                &format!(
+                    "`{name}` is not a `{key}` keyword and is passed to `.{key}({name})`, did you mean `{suggestion}`?"
+                ),
+            );
+        } else {
+            abort!(
+                span,
+                "unknown `{}` keyword `{}`, did you mean `{}`?",
+                key,
+                name,
+                suggestion
+            );
+        }
+    }
+    None
+}
+
+/// `size: (w, h)` sets the dimensions separately. A single length is left to
+/// the plain `size` method, which sets both.
+fn size(key: &Ident, value: &Expr) -> Option<TokenStream> {
+    let Expr::Tuple(tuple) = value else {
+        return None;
+    };
+    if tuple.elems.len() != 2 {
+        abort!(
+            tuple,
+            "`size` expects a length or a `(width, height)` tuple"
+        );
+    }
+    let (width, height) = (&tuple.elems[0], &tuple.elems[1]);
+
+    let w = Ident::new("w", key.span());
+    let h = Ident::new("h", key.span());
+    let (w, h) = (method_call(&w, width), method_call(&h, height));
+    Some(quote! { #w #h })
+}
+
+/// Methods set by the elements of a `font:` tuple, in order.
+const FONT_PARTS: &[&str] = &["font_family", "text_size", "font_weight"];
+
+/// `font: (family, size, weight)` sets the typography together. Trailing
+/// parts may be omitted and `_` skips one, e.g. `(_, px(13.), weight)`. A
+/// single value is left to the plain method, which takes a `Font`.
+fn font(key: &Ident, value: &Expr) -> Option<TokenStream> {
+    let Expr::Tuple(tuple) = value else {
+        return None;
+    };
+    if tuple.elems.is_empty() || tuple.elems.len() > FONT_PARTS.len() {
+        abort!(
+            tuple,
+            "`{}` expects a `Font` or a `(family, size, weight)` tuple",
+            key
+        );
+    }
+
+    let calls = FONT_PARTS
+        .iter()
+        .zip(&tuple.elems)
+        .filter(|(_, value)| !matches!(value, Expr::Infer(_)))
+        .map(|(method, value)| key_value_call(&Ident::new(method, key.span()), value))
+        .collect();
+    Some(calls)
+}
+
+/// Axes accepted by `scroll:` and the overflow method making them scroll.
+const SCROLL_AXES: &[(&str, &str)] = &[
+    ("x", "overflow_x_scroll"),
+    ("y", "overflow_y_scroll"),
+    ("both", "overflow_scroll"),
+];
+
+/// `scroll: (axis, handle)` makes the element scroll along `x`, `y` or
+/// `both` and tracks the scroll handle. `scroll: axis` leaves the handle out.
+/// Like the other shorthands, only expanded on native elements: a component's
+/// own `scroll` method is called as written.
+fn scroll(key: &Ident, value: &Expr) -> TokenStream {
+    let (axis, handle) = match value {
+        Expr::Tuple(tuple) if tuple.elems.len() == 2 => (&tuple.elems[0], Some(&tuple.elems[1])),
+        Expr::Tuple(tuple) => abort!(
+            tuple,
+            "`{}` expects an axis or an `(axis, scroll_handle)` tuple",
+            key
+        ),
+        axis => (axis, None),
+    };
+
+    let Some(axis) = (match axis {
+        Expr::Path(path) if path.qself.is_none() => path.path.get_ident(),
+        _ => None,
+    }) else {
+        abort!(axis, "`{}` expects an axis: `x`, `y` or `both`", key);
+    };
+    let name = axis.to_string();
+    let Some((_, method)) = SCROLL_AXES.iter().find(|(known, _)| *known == name) else {
+        let candidates = SCROLL_AXES.iter().map(|(known, _)| *known);
+        if let Some(suggestion) = closest_match(&name, candidates) {
+            abort!(
+                axis,
+                "unknown `{}` axis `{}`, did you mean `{}`?",
+                key,
+                name,
+                suggestion
+            );
+        }
+        abort!(axis, "`{}` expects an axis: `x`, `y` or `both`", key);
+    };
+
+    let method = Ident::new(method, axis.span());
+    let track = handle.map(|handle| {
+        let track_scroll = Ident::new("track_scroll", key.span());
+        method_call(&track_scroll, quote! { &#handle })
+    });
+    let method = method_call(&method, quote! {});
+    quote! { #method #track }
+}
+
+/// Widths with a predefined `border_*_N()` method in gpui.
+pub const BORDER_WIDTHS: &[&str] = &[
+    "0", "1", "2", "3", "4", "5", "6", "7", "8", "9", "10", "11", "12", "16", "20", "24", "32",
+];
+
+/// `border: (width, color)` sets the width and the color together. Literal
+/// widths use the predefined `border_N()` methods.
+///
+/// gpui has a single border color, so the color of a side variant like
+/// `border_b: (width, color)` is shared by all sides: the last one set wins.
+fn border(key: &Ident, value: &Expr) -> Option<TokenStream> {
+    let Expr::Tuple(tuple) = value else {
+        return None;
+    };
+    if tuple.elems.len() != 2 {
+        abort!(
+            tuple,
+            "`{}` expects a width or a `(width, color)` tuple",
+            key
+        );
+    }
+    let (width, color) = (&tuple.elems[0], &tuple.elems[1]);
+
+    let width = predefined_width(width).map_or_else(
+        || method_call(key, width),
+        |width| {
+            let method = Ident::new(&format!("{key}_{width}"), key.span());
+            method_call(&method, quote! {})
+        },
+    );
+    let border_color = Ident::new("border_color", key.span());
+    let color = method_call(&border_color, color);
+    Some(quote! { #width #color })
+}
+
+/// The width of a numeric literal matching one of [`BORDER_WIDTHS`].
+fn predefined_width(expr: &Expr) -> Option<String> {
+    let (width, _) = scale_step(expr)?;
+    BORDER_WIDTHS.contains(&width.as_str()).then_some(width)
+}
+
+/// Attributes with predefined methods for each step of the spacing scale.
+const SPACING_ATTRIBUTES: &[&str] = &[
+    "p", "pt", "pb", "pl", "pr", "px", "py", "m", "mt", "mb", "ml", "mr", "mx", "my", "gap",
+    "gap_x", "gap_y", "inset", "top", "right", "bottom", "left",
+];
+
+/// Steps of gpui's spacing scale, as method suffixes (`p_1p5()`).
+pub const SPACING_STEPS: &[&str] = &[
+    "0", "0p5", "1", "1p5", "2", "2p5", "3", "3p5", "4", "5", "6", "7", "8", "9", "10", "11", "12",
+    "16", "20", "24", "32", "40", "48", "56", "64", "72", "80", "96", "112", "128",
+];
+
+/// An unsuffixed numeric literal as a scale method suffix: `2` is `"2"`,
+/// `1.5` is `"1p5"` and `2.0` is `"2"`.
+fn scale_step(expr: &Expr) -> Option<(String, Span)> {
+    let Expr::Lit(ExprLit { lit, .. }) = expr else {
+        return None;
+    };
+    let digits = match lit {
+        Lit::Int(lit) if lit.suffix().is_empty() => lit.base10_digits(),
+        Lit::Float(lit) if lit.suffix().is_empty() => lit
+            .base10_digits()
+            .trim_end_matches('0')
+            .trim_end_matches('.'),
+        _ => return None,
+    };
+    Some((digits.replace('.', "p"), lit.span()))
+}
+
+/// The numeric value of a scale method suffix.
+fn step_value(step: &str) -> f64 {
+    step.replace('p', ".").parse().unwrap_or(f64::MAX)
+}
+
+/// `.{method}_{step}()` for a numeric literal on the spacing scale, or
+/// `.{method}(value)` for any other value.
+fn spacing_call(method: &str, span: Span, value: &Expr) -> TokenStream {
+    let Some((step, step_span)) = scale_step(value) else {
+        let method = Ident::new(method, span);
+        return method_call(&method, value);
+    };
+
+    if !SPACING_STEPS.contains(&step.as_str()) {
+        let distance = |other: &str| (step_value(other) - step_value(&step)).abs();
+        let nearest = SPACING_STEPS
+            .iter()
+            .min_by(|a, b| distance(a).total_cmp(&distance(b)))
+            .map_or_else(String::new, |nearest| nearest.replace('p', "."));
+        abort!(
+            step_span,
+            "`{}` is not on the spacing scale, did you mean `{}`?",
+            step.replace('p', "."),
+            nearest
+        );
+    }
+
+    let method = Ident::new(&format!("{method}_{step}"), step_span);
+    method_call(&method, quote! {})
+}
+
+/// CSS-style `p:`/`m:` tuples: `(y, x)`, `(top, x, bottom)` or
+/// `(top, right, bottom, left)`. A single value is left to the plain method.
+fn edges(key: &Ident, value: &Expr) -> Option<TokenStream> {
+    let Expr::Tuple(tuple) = value else {
+        return None;
+    };
+
+    let sides: &[&str] = match tuple.elems.len() {
+        2 => &["y", "x"],
+        3 => &["t", "x", "b"],
+        4 => &["t", "r", "b", "l"],
+        _ => abort!(
+            tuple,
+            "`{}` expects a length or a tuple of 2 to 4 lengths",
+            key
+        ),
+    };
+
+    Some(calls(key, sides, tuple))
+}
+
+/// `.{key}{side}(value)` for each side and tuple element.
+fn calls(key: &Ident, sides: &[&str], tuple: &ExprTuple) -> TokenStream {
+    sides
+        .iter()
+        .zip(&tuple.elems)
+        .map(|(side, value)| spacing_call(&format!("{key}{side}"), key.span(), value))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use syn::parse_quote;
+
+    use super::*;
+
+    fn keyword_call(value: &Expr) -> Option<String> {
+        let key = Ident::new("rounded", proc_macro2::Span::call_site());
+        keyword(&key, value, ROUNDED_KEYWORDS).map(|tokens| tokens.to_string())
+    }
+
+    #[test]
+    fn test_keywords() {
+        assert_eq!(keyword_call(&parse_quote!(md)).unwrap(), ". rounded_md ()");
+        assert_eq!(
+            keyword_call(&parse_quote!(2xl)).unwrap(),
+            ". rounded_2xl ()"
+        );
+        assert_eq!(keyword_call(&parse_quote!(radius)), None);
+        // Close to `sm`, but may be a variable
+        assert_eq!(keyword_call(&parse_quote!(m)), None);
+        assert_eq!(keyword_call(&parse_quote!(theme.radius)), None);
+        assert_eq!(keyword_call(&parse_quote!(px(4.0))), None);
+    }
+
+    #[test]
+    fn test_font_parts() {
+        let font = |value: &Expr| {
+            let key = Ident::new("font", proc_macro2::Span::call_site());
+            font(&key, value).map(|tokens| tokens.to_string())
+        };
+        assert_eq!(
+            font(&parse_quote!(("Zed Mono",))).unwrap(),
+            ". font_family (\"Zed Mono\")"
+        );
+        assert_eq!(
+            font(&parse_quote!((_, size, weight))).unwrap(),
+            ". text_size (size) . font_weight (weight)"
+        );
+        assert_eq!(font(&parse_quote!(theme.font)), None);
+    }
+
+    #[test]
+    fn test_scroll() {
+        let scroll = |value: &Expr| {
+            let key = Ident::new("scroll", proc_macro2::Span::call_site());
+            scroll(&key, value).to_string()
+        };
+        assert_eq!(
+            scroll(&parse_quote!((y, self.scroll_handle))),
+            ". overflow_y_scroll () . track_scroll (& self . scroll_handle)"
+        );
+        assert_eq!(scroll(&parse_quote!(both)), ". overflow_scroll ()");
+    }
+
+    #[test]
+    fn test_scale_step() {
+        let step = |expr: &Expr| scale_step(expr).map(|(step, _)| step);
+        assert_eq!(step(&parse_quote!(2)).unwrap(), "2");
+        assert_eq!(step(&parse_quote!(1.5)).unwrap(), "1p5");
+        assert_eq!(step(&parse_quote!(0.50)).unwrap(), "0p5");
+        assert_eq!(step(&parse_quote!(4.0)).unwrap(), "4");
+        assert_eq!(step(&parse_quote!(4usize)), None);
+        assert_eq!(step(&parse_quote!(gap)), None);
+    }
+
+    #[test]
+    fn test_predefined_width() {
+        assert_eq!(predefined_width(&parse_quote!(1)).unwrap(), "1");
+        assert_eq!(predefined_width(&parse_quote!(2.)).unwrap(), "2");
+        assert_eq!(predefined_width(&parse_quote!(16.0)).unwrap(), "16");
+        assert_eq!(predefined_width(&parse_quote!(1.5)), None);
+        assert_eq!(predefined_width(&parse_quote!(13)), None);
+        assert_eq!(predefined_width(&parse_quote!(1u8)), None);
+        assert_eq!(predefined_width(&parse_quote!(width)), None);
+    }
+}
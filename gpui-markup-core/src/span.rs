@@ -0,0 +1,353 @@
+//! Inline styled text: `span` runs merged into a `StyledText`.
+//!
+//! ```ignore
+//! div { "Press ", span @[font_weight: FontWeight::BOLD] { "Enter" }, " to continue" }
+//! ```
+//!
+//! The text of consecutive text and `span` children is concatenated at
+//! runtime, and each span contributes a `HighlightStyle` over its range.
+//! Inside `rich_text`, spans with `on_click` also become clickable ranges of
+//! an `InteractiveText`. When all of the text is made of string literals, it
+//! is concatenated during expansion instead, into a static `SharedString`
+//! with constant ranges, so rendering allocates no text.
+
+use proc_macro_error2::abort;
+use proc_macro2::{Ident, Literal, Span, TokenStream};
+use quote::{ToTokens, format_ident, quote};
+use syn::{Expr, ExprLit, Lit};
+
+use crate::ast::{Attribute, Child, Element, RichTextElement, SpanElement};
+use crate::config::Config;
+use crate::suggest::closest_match;
+use crate::text::is_text;
+
+/// Flags supported on `span`.
+const SPAN_FLAGS: &[&str] = &["italic", "underline", "line_through"];
+
+/// `span` attributes and the `HighlightStyle` field they set.
+const SPAN_ATTRIBUTES: &[(&str, &str)] = &[
+    ("color", "color"),
+    ("text_color", "color"),
+    ("bg", "background_color"),
+    ("background_color", "background_color"),
+    ("font_weight", "font_weight"),
+    ("font_style", "font_style"),
+    ("underline", "underline"),
+    ("strikethrough", "strikethrough"),
+    ("fade_out", "fade_out"),
+];
+
+/// Split `children` into runs to render: consecutive text children that
+/// contain at least one `span` become a single `StyledText`.
+pub fn group_text_runs(children: &[Child]) -> Vec<TextRun<'_>> {
+    let mut runs = vec![];
+    let mut start = 0;
+
+    while start < children.len() {
+        let end = start
+            + children[start..]
+                .iter()
+                .take_while(|child| is_text(child) || is_span(child))
+                .count();
+        if children[start..end].iter().any(is_span) {
+            runs.push(TextRun::Styled(&children[start..end]));
+            start = end;
+        } else {
+            runs.push(TextRun::Child(&children[start]));
+            start += 1;
+        }
+    }
+
+    runs
+}
+
+/// A child to append, see [`group_text_runs`].
+pub enum TextRun<'a> {
+    Child(&'a Child),
+    Styled(&'a [Child]),
+}
+
+const fn is_span(child: &Child) -> bool {
+    matches!(child, Child::Element(Element::Span(_)))
+}
+
+/// A `StyledText` built from text and `span` elements.
+pub fn styled_text<'a>(elements: impl IntoIterator<Item = &'a Element> + Clone) -> TokenStream {
+    let gpui = Config::current().gpui();
+    let text = Ident::new("__text", Span::mixed_site());
+    let highlights = Ident::new("__highlights", Span::mixed_site());
+    let (statements, _) = text_statements(elements.clone(), None);
+    let text_init = text_init(elements);
+
+    quote! {
+        {
+            #text_init
+            let mut #highlights = ::std::vec::Vec::new();
+            #statements
+            #gpui::StyledText::new(#text).with_highlights(#highlights)
+        }
+    }
+}
+
+/// The binding of `__text`: a static `SharedString` when the text is all
+/// literals, or an empty `String` filled by the text statements.
+fn text_init<'a>(elements: impl IntoIterator<Item = &'a Element>) -> TokenStream {
+    let text = Ident::new("__text", Span::mixed_site());
+    literal_text(elements).map_or_else(
+        || quote! { let mut #text = ::std::string::String::new(); },
+        |literal| {
+            let gpui = Config::current().gpui();
+            quote! { let #text = #gpui::SharedString::new_static(#literal); }
+        },
+    )
+}
+
+/// The text of `elements` if it is all string literals.
+fn literal_text<'a>(elements: impl IntoIterator<Item = &'a Element>) -> Option<String> {
+    elements
+        .into_iter()
+        .try_fold(String::new(), |mut text, element| {
+            match element {
+                Element::Span(span) => {
+                    for child in &span.children {
+                        text.push_str(&literal(child.element()?)?);
+                    }
+                }
+                element => text.push_str(&literal(element)?),
+            }
+            Some(text)
+        })
+}
+
+/// The value of a string literal text element.
+fn literal(element: &Element) -> Option<String> {
+    match element {
+        Element::Expression(el) if el.attributes.is_empty() && el.children.is_empty() => {
+            match &el.expr {
+                Expr::Lit(ExprLit {
+                    lit: Lit::Str(lit), ..
+                }) => Some(lit.value()),
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+/// An `InteractiveText` built from a `rich_text` element's children. Clicks
+/// within a span with `on_click` call its listener with the window and app.
+pub fn interactive_text(rich_text: &RichTextElement) -> TokenStream {
+    let gpui = Config::current().gpui();
+    let text = Ident::new("__text", Span::mixed_site());
+    let highlights = Ident::new("__highlights", Span::mixed_site());
+    let ranges = Ident::new("__ranges", Span::mixed_site());
+    let elements = rich_text.children.iter().filter_map(Child::element);
+    let (statements, listeners) = text_statements(elements.clone(), Some(&ranges));
+    let text_init = text_init(elements);
+    let id = &rich_text.id;
+    let on_click = (!listeners.is_empty()).then(|| {
+        let index = Ident::new("__index", Span::mixed_site());
+        let window = Ident::new("__window", Span::mixed_site());
+        let cx = Ident::new("__cx", Span::mixed_site());
+        let arms = listeners.iter().enumerate().map(|(i, listener)| {
+            quote! { #i => #listener(#window, #cx), }
+        });
+        quote! {
+            .on_click(#ranges, move |#index, #window, #cx| match #index {
+                #(#arms)*
+                _ => {}
+            })
+        }
+    });
+
+    quote! {
+        {
+            #text_init
+            let mut #highlights = ::std::vec::Vec::new();
+            let mut #ranges = ::std::vec::Vec::new();
+            #statements
+            #gpui::InteractiveText::new(
+                #id,
+                #gpui::StyledText::new(#text).with_highlights(#highlights),
+            )
+            #on_click
+        }
+    }
+}
+
+/// Statements appending each element's text to `__text` and each span's
+/// style to `__highlights`.
+///
+/// When `ranges` is given, clickable spans also push their range to it and
+/// bind their listener; the bindings are returned in the same order. Literal
+/// text is already in `__text` (see `text_init`), so only the styles and
+/// constant ranges are pushed.
+fn text_statements<'a>(
+    elements: impl IntoIterator<Item = &'a Element> + Clone,
+    ranges: Option<&Ident>,
+) -> (TokenStream, Vec<Ident>) {
+    let gpui = Config::current().gpui();
+    let text = Ident::new("__text", Span::mixed_site());
+    let highlights = Ident::new("__highlights", Span::mixed_site());
+    let mut listeners = vec![];
+    let is_literal = literal_text(elements.clone()).is_some();
+    let mut offset = 0;
+
+    let statements = elements
+        .into_iter()
+        .map(|element| match element {
+            Element::Span(span) => {
+                let start = Ident::new("__start", Span::mixed_site());
+                let texts = span.children.iter().filter_map(Child::element);
+                let (pushes, end) = if is_literal {
+                    let begin = Literal::usize_unsuffixed(offset);
+                    offset += texts
+                        .filter_map(literal)
+                        .map(|text| text.len())
+                        .sum::<usize>();
+                    let end = Literal::usize_unsuffixed(offset);
+                    (quote! { let #start = #begin; }, end.into_token_stream())
+                } else {
+                    let pushes = texts.map(|element| push_text(&text, element));
+                    (
+                        quote! { let #start = #text.len(); #(#pushes)* },
+                        quote! { #text.len() },
+                    )
+                };
+                let style = highlight_style(span);
+                let on_click = on_click(span).map(|(key, handler)| {
+                    let Some(ranges) = ranges else {
+                        abort!(key, "`on_click` on a `span` requires a `rich_text` parent");
+                    };
+                    let listener =
+                        format_ident!("__listener_{}", listeners.len(), span = Span::mixed_site());
+                    listeners.push(listener.clone());
+                    let into_listener = Ident::new("__into_listener", Span::mixed_site());
+                    // Passing the handler through a generic function lets closure
+                    // parameter types be inferred from the listener signature. It
+                    // is called outside its block, so the handler doesn't see it
+                    quote! {
+                        #ranges.push(#start..#end);
+                        let #listener = ({
+                            fn #into_listener(
+                                listener: impl Fn(&mut #gpui::Window, &mut #gpui::App) + 'static,
+                            ) -> impl Fn(&mut #gpui::Window, &mut #gpui::App) + 'static {
+                                listener
+                            }
+                            #into_listener
+                        })(#handler);
+                    }
+                });
+                quote! {
+                    #pushes
+                    #highlights.push((#start..#end, #style));
+                    #on_click
+                }
+            }
+            element if is_literal => {
+                offset += literal(element).map_or(0, |text| text.len());
+                quote! {}
+            }
+            element => push_text(&text, element),
+        })
+        .collect();
+
+    (statements, listeners)
+}
+
+/// The `on_click` or `on:click` attribute of a span.
+fn on_click(span: &SpanElement) -> Option<(&Ident, &Expr)> {
+    span.attributes.iter().find_map(|attr| match attr {
+        Attribute::KeyValue { key, value } if key == "on_click" => Some((key, value)),
+        Attribute::Event {
+            event,
+            modifiers,
+            handler,
+            ..
+        } if event == "click" => {
+            if let Some(modifier) = modifiers.first() {
+                abort!(modifier, "`span` click handlers don't take modifiers");
+            }
+            Some((event, handler))
+        }
+        _ => None,
+    })
+}
+
+fn push_text(text: &Ident, element: &Element) -> TokenStream {
+    quote! { #text.push_str(::core::convert::AsRef::<str>::as_ref(&#element)); }
+}
+
+/// The `HighlightStyle` described by a span's attributes.
+fn highlight_style(span: &SpanElement) -> TokenStream {
+    let gpui = Config::current().gpui();
+    let fields = span.attributes.iter().map(|attr| match attr {
+        Attribute::KeyValue { key, .. } if key == "on_click" => quote! {},
+        Attribute::Event { event, .. } if event == "click" => quote! {},
+        Attribute::Flag(flag) => {
+            let (field, value) = match flag.to_string().as_str() {
+                "italic" => ("font_style", quote! { #gpui::FontStyle::Italic }),
+                "underline" => (
+                    "underline",
+                    quote! {
+                        #gpui::UnderlineStyle {
+                            thickness: #gpui::px(1.0),
+                            ..::core::default::Default::default()
+                        }
+                    },
+                ),
+                "line_through" => (
+                    "strikethrough",
+                    quote! {
+                        #gpui::StrikethroughStyle {
+                            thickness: #gpui::px(1.0),
+                            ..::core::default::Default::default()
+                        }
+                    },
+                ),
+                _ => unknown_attribute(flag, SPAN_FLAGS.iter().copied()),
+            };
+            let field = Ident::new(field, flag.span());
+            quote! { #field: ::core::option::Option::Some(#value), }
+        }
+        Attribute::KeyValue { key, value } => {
+            let Some((_, field)) = SPAN_ATTRIBUTES.iter().find(|(name, _)| key == name) else {
+                unknown_attribute(key, SPAN_ATTRIBUTES.iter().map(|(name, _)| *name));
+            };
+            let field = Ident::new(field, key.span());
+            quote! { #field: ::core::option::Option::Some(::core::convert::Into::into(#value)), }
+        }
+        Attribute::PseudoState { state: key, .. }
+        | Attribute::Platform { platform: key, .. }
+        | Attribute::Compound { key, .. }
+        | Attribute::Prop { key, .. }
+        | Attribute::Event { event: key, .. } => {
+            abort!(key, "`span` only supports text style attributes");
+        }
+    });
+
+    quote! {
+        #gpui::HighlightStyle {
+            #(#fields)*
+            ..::core::default::Default::default()
+        }
+    }
+}
+
+fn unknown_attribute<'a>(name: &Ident, candidates: impl IntoIterator<Item = &'a str> + Clone) -> ! {
+    if let Some(suggestion) = closest_match(&name.to_string(), candidates.clone()) {
+        abort!(
+            name,
+            "unsupported `span` attribute `{}`, did you mean `{}`?",
+            name,
+            suggestion
+        );
+    }
+    let expected = candidates.into_iter().collect::<Vec<_>>().join(", ");
+    abort!(
+        name,
+        "unsupported `span` attribute `{}`, expected one of: {}",
+        name,
+        expected
+    );
+}
@@ -0,0 +1,142 @@
+//! Splitting of huge trees into statements.
+//!
+//! A tree built as one expression is type-checked as a whole, and screens of
+//! a few thousand elements run into rustc's type length and recursion limits
+//! and report borrow errors against the entire expression. When the children
+//! of an element would make its expression larger than `split_threshold`
+//! elements (see `gpui-markup.toml`), the largest child subtrees are built in
+//! their own `let` statement as an `AnyElement`, like children next to a
+//! `bind:` element:
+//!
+//! ```text
+//! { let __parent = div(); let __child_0: AnyElement = ..; __parent.child(__child_0) }
+//! ```
+//!
+//! The statements follow the parent in source order, so the evaluation order
+//! is unchanged. Subtrees capturing nothing are hoisted into functions
+//! instead, see `hoist`.
+//!
+//! Every element measures its whole subtree, so sizes are cached while an
+//! invocation is expanded, keeping deep trees linear to expand.
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+use crate::ast::{Child, Element};
+use crate::config::Config;
+use crate::hoist::is_hoisted;
+use crate::span::{TextRun, group_text_runs};
+
+/// Which of `runs` to build in their own statement.
+pub fn split_runs(runs: &[TextRun]) -> Vec<bool> {
+    split(runs, Config::current().split_threshold()).1
+}
+
+thread_local! {
+    /// Sizes of the elements of the invocation being expanded, by address.
+    /// The tree is borrowed for the whole expansion, so addresses are stable.
+    static SIZES: RefCell<Option<HashMap<*const Element, usize>>> = const { RefCell::new(None) };
+}
+
+/// Run `f` with element sizes cached.
+pub fn with_size_cache<R>(f: impl FnOnce() -> R) -> R {
+    let previous = SIZES.replace(Some(HashMap::new()));
+    let result = f();
+    SIZES.set(previous);
+    result
+}
+
+/// The number of elements built by the expression of `element`, once its
+/// children are split.
+fn inline_size(element: &Element) -> usize {
+    let key: *const Element = element;
+    if let Some(size) = SIZES.with_borrow(|sizes| sizes.as_ref()?.get(&key).copied()) {
+        return size;
+    }
+    let size = measure(element);
+    SIZES.with_borrow_mut(|sizes| {
+        if let Some(sizes) = sizes {
+            sizes.insert(key, size);
+        }
+    });
+    size
+}
+
+fn measure(element: &Element) -> usize {
+    if is_hoisted(element) {
+        return 1;
+    }
+    match element {
+        // Children not appended one by one, so they can't be split
+        Element::Deferred(_) | Element::Span(_) | Element::RichText(_) => {
+            1 + element.children().iter().map(child_size).sum::<usize>()
+        }
+        _ => {
+            let runs = group_text_runs(element.children());
+            1 + split(&runs, Config::current().split_threshold()).0
+        }
+    }
+}
+
+fn child_size(child: &Child) -> usize {
+    match child {
+        Child::Element(element) => inline_size(element),
+        Child::Cfg { child, .. } => child_size(child),
+        Child::Spread(_) | Child::MethodChain(_) => 1,
+    }
+}
+
+/// Split the largest element children among `runs` until the rest fit in
+/// `threshold` along with their parent, returning the remaining size and
+/// which runs were split. `bind:` elements keep their type for the siblings
+/// borrowing them.
+fn split(runs: &[TextRun], threshold: usize) -> (usize, Vec<bool>) {
+    let sizes: Vec<usize> = runs
+        .iter()
+        .map(|run| match run {
+            TextRun::Child(child) => child_size(child),
+            TextRun::Styled(children) => children.len(),
+        })
+        .collect();
+    let mut total: usize = sizes.iter().sum();
+    let mut order: Vec<usize> = (0..runs.len())
+        .filter(|&index| {
+            matches!(runs[index], TextRun::Child(Child::Element(element)) if element.bound_name().is_none())
+        })
+        .collect();
+    order.sort_by_key(|&index| std::cmp::Reverse(sizes[index]));
+
+    let mut split = vec![false; runs.len()];
+    for index in order {
+        if total < threshold || sizes[index] <= 1 {
+            break;
+        }
+        split[index] = true;
+        total -= sizes[index] - 1;
+    }
+    (total, split)
+}
+
+#[cfg(test)]
+mod tests {
+    use quote::quote;
+
+    use super::*;
+    use crate::ast::Markup;
+
+    #[test]
+    fn test_split() {
+        let markup: Markup = syn::parse2(quote! {
+            div { div { a, b, c }, div { a, b, c, d, e }, "Title" }
+        })
+        .unwrap();
+        let runs = group_text_runs(markup.element.children());
+        assert_eq!(split(&runs, 100), (11, vec![false, false, false]));
+        assert_eq!(split(&runs, 8), (6, vec![false, true, false]));
+        assert_eq!(split(&runs, 4), (3, vec![true, true, false]));
+        assert_eq!(
+            with_size_cache(|| (split(&runs, 8), split(&runs, 8))),
+            ((6, vec![false, true, false]), (6, vec![false, true, false]))
+        );
+    }
+}
@@ -0,0 +1,114 @@
+//! `#![options(checked)]`: style attributes on components and expression
+//! elements asserted, in debug builds, to be given a `Styled` element.
+//!
+//! ```ignore
+//! ui! {
+//!     #![options(checked)]
+//!     Badge::new("3") @[bg: rgb(0xff0000)] {}
+//! }
+//! ```
+//!
+//! A missing method is reported by the compiler as "no method named `bg`",
+//! which doesn't say which trait the element is missing. With the option,
+//! each style attribute also passes the element to a function requiring
+//! `Styled`, spanned at the attribute, so the error names `Styled` at `bg`.
+//!
+//! Components often have builder methods named like styles, e.g. `color`,
+//! `style` or `size`, so only attributes named like the methods of `Styled`
+//! alone are asserted, and only until a `map` or `apply` may change the
+//! element type.
+
+use proc_macro2::{Span, TokenStream};
+use quote::{quote, quote_spanned};
+use syn::Ident;
+
+use crate::ast::Attribute;
+use crate::config::Config;
+use crate::options::current_options;
+
+/// Attributes, and prefixes of attributes followed by `_`, that lower to
+/// methods of `Styled` and are rarely methods of component builders.
+const STYLED_NAMES: &[&str] = &[
+    "p", "pt", "pb", "pl", "pr", "px", "py", "m", "mt", "mb", "ml", "mr", "mx", "my", "gap", "w",
+    "h", "min_w", "min_h", "max_w", "max_h", "inset", "top", "bottom", "left", "right", "bg",
+    "text", "border", "rounded", "shadow", "opacity", "flex", "items", "justify", "overflow",
+    "absolute", "relative", "grid", "font",
+];
+
+/// Whether `name` is asserted to be a method of `Styled`.
+fn is_styled(name: &str) -> bool {
+    STYLED_NAMES.iter().any(|prefix| {
+        name.strip_prefix(prefix)
+            .is_some_and(|rest| rest.is_empty() || rest.starts_with('_'))
+    })
+}
+
+/// The keys of the style attributes of an element checked for `Styled`.
+fn styled_keys(attributes: &[Attribute]) -> Vec<&Ident> {
+    attributes
+        .iter()
+        .map_while(|attr| match attr {
+            Attribute::KeyValue { key, .. } if key == "map" || key == "apply" => None,
+            Attribute::Flag(key)
+            | Attribute::KeyValue { key, .. }
+            | Attribute::Compound { key, .. } => Some(is_styled(&key.to_string()).then_some(key)),
+            _ => Some(None),
+        })
+        .flatten()
+        .collect()
+}
+
+/// `base` with a debug assertion that it is `Styled` for each style attribute
+/// in `attributes`, when `#![options(checked)]` is set.
+pub fn styled_check(base: TokenStream, attributes: &[Attribute]) -> TokenStream {
+    if !current_options().checked {
+        return base;
+    }
+    let keys = styled_keys(attributes);
+    if keys.is_empty() {
+        return base;
+    }
+    let styled = Config::current().item("Styled");
+    let element = Ident::new("__element", Span::mixed_site());
+    let check = Ident::new("__Styled", Span::mixed_site());
+    let function = Ident::new("__styled", Span::mixed_site());
+    let calls = keys.iter().map(|key| {
+        let element = Ident::new("__element", Span::mixed_site().located_at(key.span()));
+        quote_spanned! {key.span()=> #function(&#element); }
+    });
+    quote! {
+        {
+            let #element = #base;
+            #[cfg(debug_assertions)]
+            {
+                #[diagnostic::on_unimplemented(
+                    message = "`{Self}` can't be styled",
+                    label = "given a style attribute in `ui!`, but doesn't implement `Styled`",
+                    note = "style attributes call methods of `Styled`, wrap it in a `div` to style its box"
+                )]
+                trait #check: #styled {}
+                impl<T: #styled> #check for T {}
+                fn #function<T: #check>(_: &T) {}
+                #(#calls)*
+            }
+            #element
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_styled() {
+        assert!(is_styled("bg"));
+        assert!(is_styled("p_2"));
+        assert!(is_styled("rounded_md"));
+        assert!(is_styled("flex"));
+        assert!(!is_styled("color"));
+        assert!(!is_styled("style"));
+        assert!(!is_styled("size"));
+        assert!(!is_styled("pointer"));
+    }
+}
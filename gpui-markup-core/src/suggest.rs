@@ -0,0 +1,65 @@
+//! "Did you mean" suggestions for diagnostics.
+
+/// Find the candidate closest to `name`, if any is close enough to be a
+/// plausible typo.
+pub fn closest_match<'a>(
+    name: &str,
+    candidates: impl IntoIterator<Item = &'a str>,
+) -> Option<&'a str> {
+    let max_distance = (name.chars().count() / 3).max(1);
+
+    candidates
+        .into_iter()
+        .map(|candidate| (edit_distance(name, candidate), candidate))
+        .filter(|&(distance, _)| distance <= max_distance)
+        .min_by_key(|&(distance, _)| distance)
+        .map(|(_, candidate)| candidate)
+}
+
+/// Edit distance between two strings, counting a swap of adjacent characters
+/// (`widht`, `dvi`) as a single edit like an insertion or a deletion.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    // The two previous rows of the distance matrix, and the current one
+    let mut previous: Vec<usize> = vec![0; b.len() + 1];
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+
+    for (i, &ca) in a.iter().enumerate() {
+        let mut current = vec![i + 1; b.len() + 1];
+        for (j, &cb) in b.iter().enumerate() {
+            let substitution = row[j] + usize::from(ca != cb);
+            current[j + 1] = substitution.min(row[j + 1] + 1).min(current[j] + 1);
+            if i > 0 && j > 0 && ca == b[j - 1] && a[i - 1] == cb {
+                current[j + 1] = current[j + 1].min(previous[j - 1] + 1);
+            }
+        }
+        previous = std::mem::replace(&mut row, current);
+    }
+
+    row[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_edit_distance() {
+        assert_eq!(edit_distance("row", "row"), 0);
+        assert_eq!(edit_distance("rwo", "row"), 1);
+        assert_eq!(edit_distance("dvi", "div"), 1);
+        assert_eq!(edit_distance("widht", "width"), 1);
+        assert_eq!(edit_distance("abc", "ca"), 3);
+        assert_eq!(edit_distance("svgs", "svg"), 1);
+        assert_eq!(edit_distance("", "abc"), 3);
+    }
+
+    #[test]
+    fn test_closest_match() {
+        assert_eq!(closest_match("sidebar", ["sidebr", "row"]), Some("sidebr"));
+        assert_eq!(closest_match("svgs", ["div", "svg"]), Some("svg"));
+        assert_eq!(closest_match("header", ["row", "item"]), None);
+        assert_eq!(closest_match("widht", ["w", "width"]), Some("width"));
+    }
+}
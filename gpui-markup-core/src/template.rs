@@ -0,0 +1,215 @@
+//! `ui_template!`: parameterized markup fragments.
+//!
+//! Each template becomes a function returning its markup, so it is invoked
+//! inside `ui!` like any expression element, with attributes and children
+//! applied to the returned element:
+//!
+//! ```ignore
+//! ui_template! {
+//!     row(icon: AnyElement, label: SharedString) => div @[flex, gap_2] { icon, label }
+//! }
+//!
+//! ui! { div { row(icon, "Settings".into()) @[mt_2] {} } }
+//! ```
+
+use proc_macro2::TokenStream;
+use quote::{ToTokens, quote};
+use syn::parse::{Parse, ParseStream};
+use syn::punctuated::Punctuated;
+use syn::{FnArg, Generics, Ident, Result, Token, Visibility, parenthesized};
+
+use crate::ast::{Attribute, Element, Markup};
+use crate::config::Config;
+
+/// A single template: `name(params) => markup`.
+#[derive(Debug)]
+pub struct Template {
+    pub vis: Visibility,
+    pub name: Ident,
+    pub generics: Generics,
+    pub inputs: Punctuated<FnArg, Token![,]>,
+    pub markup: Markup,
+}
+
+/// The templates of a `ui_template!` invocation.
+#[derive(Debug)]
+pub struct Templates(pub Vec<Template>);
+
+impl Parse for Template {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let vis = input.parse()?;
+        let name = input.parse()?;
+        let mut generics: Generics = input.parse()?;
+
+        let content;
+        parenthesized!(content in input);
+        let inputs = Punctuated::<FnArg, Token![,]>::parse_terminated(&content)?;
+        if let Some(FnArg::Receiver(receiver)) = inputs.first() {
+            return Err(syn::Error::new_spanned(
+                receiver,
+                "templates cannot take `self`, pass the value as a parameter",
+            ));
+        }
+        generics.where_clause = input.parse()?;
+
+        input.parse::<Token![=>]>()?;
+
+        Ok(Self {
+            vis,
+            name,
+            generics,
+            inputs,
+            markup: input.parse()?,
+        })
+    }
+}
+
+impl Parse for Templates {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let mut templates = vec![];
+        while !input.is_empty() {
+            templates.push(input.parse()?);
+            if !input.is_empty() {
+                input.parse::<Token![;]>()?;
+            }
+        }
+        Ok(Self(templates))
+    }
+}
+
+/// Native elements and the gpui type they build.
+const ELEMENT_TYPES: &[(&str, &str)] = &[
+    ("div", "Div"),
+    ("h_flex", "Div"),
+    ("v_flex", "Div"),
+    ("stack", "Div"),
+    ("spacer", "Div"),
+    ("divider", "Div"),
+    ("svg", "Svg"),
+    ("uniform_list", "UniformList"),
+    ("anchored", "Anchored"),
+    ("overlay", "Anchored"),
+    ("surface", "Surface"),
+    ("list", "List"),
+];
+
+/// The return type of a template: the concrete element type of a native
+/// root, so callers can add attributes and children, including an `id`
+/// turning it into a `Stateful` element. Other roots, and native roots passed
+/// through `apply:` or `map:`, return `impl IntoElement`.
+fn return_type(markup: &Markup) -> TokenStream {
+    let into_element = Config::current().item("IntoElement");
+    let Element::Native(el) = &markup.element else {
+        return quote! { impl #into_element };
+    };
+    if let Some(definition) = &el.definition {
+        return definition.ty.to_token_stream();
+    }
+    let Some((_, ty)) = ELEMENT_TYPES.iter().find(|(name, _)| el.name == name) else {
+        return quote! { impl #into_element };
+    };
+    let mut stateful = false;
+    for attr in &el.attributes {
+        match attr {
+            Attribute::KeyValue { key, .. } if key == "apply" || key == "map" => {
+                return quote! { impl #into_element };
+            }
+            Attribute::KeyValue { key, .. } if key == "id" => stateful = true,
+            _ => {}
+        }
+    }
+    let ty = Config::current().item(ty);
+    if stateful {
+        let stateful = Config::current().item("Stateful");
+        quote! { #stateful<#ty> }
+    } else {
+        ty
+    }
+}
+
+impl ToTokens for Template {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let Self {
+            vis,
+            name,
+            generics,
+            inputs,
+            markup,
+        } = self;
+        let where_clause = &generics.where_clause;
+        let output = return_type(markup);
+        tokens.extend(quote! {
+            #vis fn #name #generics(#inputs) -> #output #where_clause {
+                #markup
+            }
+        });
+    }
+}
+
+impl ToTokens for Templates {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        for template in &self.0 {
+            template.to_tokens(tokens);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use syn::parse2;
+
+    use super::*;
+
+    fn expand(tokens: TokenStream) -> String {
+        parse2::<Templates>(tokens)
+            .unwrap()
+            .to_token_stream()
+            .to_string()
+    }
+
+    #[test]
+    fn test_template() {
+        assert_eq!(
+            expand(quote! {
+                pub row(icon: AnyElement, label: SharedString) => div @[flex] { icon, label }
+            }),
+            quote! {
+                pub fn row(icon: AnyElement, label: SharedString) -> gpui::Div {
+                    gpui::ParentElement::child(gpui::ParentElement::child(div().flex(), icon), label)
+                }
+            }
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn test_multiple_templates() {
+        let output = expand(quote! {
+            title(text: &str) => (text.to_owned()) {};
+            badge<T: IntoElement>(child: T) => Badge { child }
+        });
+        assert!(
+            output.contains(&quote! { fn title(text: &str) -> impl gpui::IntoElement }.to_string())
+        );
+        assert!(output.contains(
+            &quote! { fn badge<T: IntoElement>(child: T) -> impl gpui::IntoElement }.to_string()
+        ));
+    }
+
+    #[test]
+    fn test_stateful_template() {
+        let output = expand(quote! {
+            tab(ix: usize) => div @[id: ix, flex] {};
+            icon(path: &'static str) => svg @[path: path] {};
+            wrapped() => div @[apply: wrap] {}
+        });
+        assert!(output.contains(&quote! { -> gpui::Stateful<gpui::Div> }.to_string()));
+        assert!(output.contains(&quote! { -> gpui::Svg }.to_string()));
+        assert!(output.contains(&quote! { fn wrapped() -> impl gpui::IntoElement }.to_string()));
+    }
+
+    #[test]
+    fn test_self_parameter() {
+        assert!(parse2::<Templates>(quote! { row(&self) => div {} }).is_err());
+    }
+}
@@ -0,0 +1,233 @@
+//! Text children: string literals with `{name}` interpolation, adjacent
+//! literal concatenation, numbers, `text: expr` (or `text(expr)`) for
+//! `Display` values and `t: key` for translations.
+
+use proc_macro_error2::abort;
+use syn::parse::ParseStream;
+use syn::spanned::Spanned;
+use syn::{
+    Expr, ExprCall, ExprLit, ExprUnary, Ident, Lit, LitStr, Result, UnOp, parse_quote_spanned,
+};
+
+use crate::ast::{Child, Element};
+use crate::config::Config;
+
+/// Whether `child` is plain text: a string literal, an interpolated literal or
+/// `text: expr`.
+pub fn is_text(child: &Child) -> bool {
+    let Child::Element(Element::Expression(el)) = child else {
+        return false;
+    };
+    if !el.attributes.is_empty() || !el.children.is_empty() {
+        return false;
+    }
+    match &el.expr {
+        Expr::Lit(ExprLit {
+            lit: Lit::Str(_), ..
+        }) => true,
+        Expr::Macro(mac) => {
+            mac.mac.path.is_ident("format") || ends_with(&mac.mac.path, &["std", "format"])
+        }
+        Expr::Call(call) => {
+            matches!(call.func.as_ref(), Expr::Path(path) if ends_with(&path.path, &["SharedString", "from"]))
+        }
+        _ => false,
+    }
+}
+
+fn ends_with(path: &syn::Path, segments: &[&str]) -> bool {
+    path.segments.len() >= segments.len()
+        && path
+            .segments
+            .iter()
+            .rev()
+            .zip(segments.iter().rev())
+            .all(|(segment, name)| segment.ident == name)
+}
+
+/// Lower an expression child, joining it with any string literals that
+/// directly follow it (`"one " "two"`), dedenting multiline raw strings and
+/// rendering `text(value)` like `text: value`.
+pub fn text_child(expr: Expr, input: ParseStream) -> Result<Expr> {
+    if let Some(text) = text_call(&expr) {
+        return Ok(text);
+    }
+    let Expr::Lit(ExprLit {
+        lit: Lit::Str(first),
+        ..
+    }) = &expr
+    else {
+        return Ok(text_literal(expr));
+    };
+    if !input.peek(LitStr) && !is_multiline_raw(first) {
+        return Ok(text_literal(expr));
+    }
+
+    let mut text = literal_text(first);
+    while input.peek(LitStr) {
+        text.push_str(&literal_text(&input.parse()?));
+    }
+
+    let lit = LitStr::new(&text, first.span());
+    Ok(text_literal(parse_quote_spanned!(lit.span()=> #lit)))
+}
+
+/// `text(value)`, the call form of `text: value`. Only a bare `text` call
+/// with a single argument is taken over, other calls are expression elements.
+fn text_call(expr: &Expr) -> Option<Expr> {
+    let Expr::Call(ExprCall { func, args, .. }) = expr else {
+        return None;
+    };
+    let bare = matches!(func.as_ref(), Expr::Path(path) if path.qself.is_none() && path.path.is_ident("text"));
+    (bare && args.len() == 1).then(|| display_text(&args[0]))
+}
+
+fn is_multiline_raw(lit: &LitStr) -> bool {
+    lit.token().to_string().starts_with('r') && lit.value().contains('\n')
+}
+
+/// The value of a string literal, dedented if it is a multiline raw string.
+fn literal_text(lit: &LitStr) -> String {
+    if is_multiline_raw(lit) {
+        dedent(&lit.value())
+    } else {
+        lit.value()
+    }
+}
+
+/// Strip the line break after the opening quote, the indentation before the
+/// closing quote and the indentation common to all non-blank lines.
+pub fn dedent(text: &str) -> String {
+    let text = text.strip_prefix('\n').unwrap_or(text);
+    let text = text.trim_end_matches([' ', '\t']);
+    let text = text.strip_suffix('\n').unwrap_or(text);
+
+    let indent = text
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| line.len() - line.trim_start().len())
+        .min()
+        .unwrap_or(0);
+
+    text.lines()
+        .map(|line| line.get(indent..).unwrap_or_else(|| line.trim_start()))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Lower literal text children: a string with `{name}` placeholders becomes a
+/// `format!` call, any other string has its `{{` and `}}` unescaped and a
+/// number, negative or not, becomes a string literal.
+/// Other expressions are returned unchanged.
+pub fn text_literal(expr: Expr) -> Expr {
+    let (sign, lit) = match &expr {
+        Expr::Lit(ExprLit { lit, .. }) => ("", lit),
+        Expr::Unary(ExprUnary {
+            op: UnOp::Neg(_),
+            expr: operand,
+            ..
+        }) => match &**operand {
+            Expr::Lit(ExprLit {
+                lit: lit @ (Lit::Int(_) | Lit::Float(_)),
+                ..
+            }) => ("-", lit),
+            _ => return expr,
+        },
+        _ => return expr,
+    };
+    let digits = match lit {
+        Lit::Str(lit) if has_placeholder(&lit.value()) => return format_call(lit),
+        Lit::Str(lit) => {
+            // Without a `format!` call escaped braces would be shown as
+            // written, `"{{x}}"` renders `{x}` either way
+            let text = lit.value().replace("{{", "{").replace("}}", "}");
+            let lit = LitStr::new(&text, lit.span());
+            return parse_quote_spanned!(lit.span()=> #lit);
+        }
+        Lit::Int(lit) => lit.base10_digits(),
+        Lit::Float(lit) => lit.base10_digits(),
+        _ => return expr,
+    };
+    let span = expr.span();
+    let text = LitStr::new(&format!("{sign}{digits}"), span);
+    parse_quote_spanned!(span=> #text)
+}
+
+/// `t: key` looks up a translation through the configured `i18n` function.
+pub fn translated_text(key: &Ident, value: &Expr) -> Expr {
+    let Some(i18n) = Config::current().i18n() else {
+        abort!(
+            key,
+            "`t:` requires an `i18n` function in `{}`, e.g. `i18n = \"crate::i18n::t\"`",
+            crate::config::FILE_NAME
+        );
+    };
+    parse_quote_spanned!(value.span()=> #i18n(#value))
+}
+
+/// `text: expr` (or `text(expr)`) renders any `Display` value as a
+/// `SharedString`.
+pub fn display_text(value: &Expr) -> Expr {
+    let gpui = Config::current().gpui();
+    parse_quote_spanned!(value.span()=>
+        #gpui::SharedString::from(::std::string::ToString::to_string(&(#value)))
+    )
+}
+
+fn format_call(lit: &LitStr) -> Expr {
+    parse_quote_spanned!(lit.span()=> ::std::format!(#lit))
+}
+
+/// Whether `text` has a placeholder starting with an identifier (`{name}`,
+/// `{name:?}`). Escaped braces and empty `{}` are left alone, as there are no
+/// arguments to fill them with.
+fn has_placeholder(text: &str) -> bool {
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c != '{' {
+            continue;
+        }
+        match chars.peek() {
+            Some('{') => {
+                chars.next();
+            }
+            Some(&next) if next == '_' || next.is_alphabetic() => return true,
+            _ => {}
+        }
+    }
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use quote::ToTokens;
+
+    use super::*;
+
+    #[test]
+    fn test_number_literals() {
+        let text = |expr: Expr| text_literal(expr).into_token_stream().to_string();
+        assert_eq!(text(syn::parse_quote!(42)), "\"42\"");
+        assert_eq!(text(syn::parse_quote!(1.5f32)), "\"1.5\"");
+        assert_eq!(text(syn::parse_quote!(-1)), "\"-1\"");
+        assert_eq!(text(syn::parse_quote!(-2.5)), "\"-2.5\"");
+        assert_eq!(text(syn::parse_quote!(true)), "true");
+    }
+
+    #[test]
+    fn test_dedent() {
+        let text = "\n    Line one\n      indented\n\n    Line two\n    ";
+        assert_eq!(dedent(text), "Line one\n  indented\n\nLine two");
+        assert_eq!(dedent("no indent\n  second"), "no indent\n  second");
+    }
+
+    #[test]
+    fn test_has_placeholder() {
+        assert!(has_placeholder("Hello, {name}!"));
+        assert!(has_placeholder("{count:>3}"));
+        assert!(has_placeholder("{{literal}} {value}"));
+        assert!(!has_placeholder("Hello, world!"));
+        assert!(!has_placeholder("{{literal}}"));
+        assert!(!has_placeholder("{} and { }"));
+    }
+}
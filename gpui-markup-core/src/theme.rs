@@ -0,0 +1,108 @@
+//! Theme tokens in attribute values: `bg: $surface`.
+//!
+//! `$name` is rewritten to a field of the theme, resolved by the `theme`
+//! expression from `gpui-markup.toml` (`cx.theme()` by default), so
+//! `bg: $surface` becomes `bg: cx.theme().surface`.
+
+use proc_macro2::{Delimiter, Group, Punct, Spacing, TokenStream, TokenTree};
+use quote::quote_spanned;
+
+use crate::config::Config;
+
+/// Rewrite `$name` tokens in `tokens` (including nested groups) into theme
+/// field accesses.
+pub fn expand_theme_tokens(tokens: TokenStream) -> TokenStream {
+    let mut output = TokenStream::new();
+    let mut tokens = tokens.into_iter().peekable();
+
+    while let Some(tt) = tokens.next() {
+        match tt {
+            TokenTree::Group(group) => {
+                let mut expanded =
+                    Group::new(group.delimiter(), expand_theme_tokens(group.stream()));
+                expanded.set_span(group.span());
+                output.extend([TokenTree::Group(expanded)]);
+            }
+            TokenTree::Punct(punct) if punct.as_char() == '$' => {
+                let Some(TokenTree::Ident(name)) = tokens.peek().cloned() else {
+                    output.extend([TokenTree::Punct(punct)]);
+                    continue;
+                };
+                tokens.next();
+
+                // An invisible group keeps the resolver's precedence without
+                // parentheses that could trip `unused_parens`
+                let span = punct
+                    .span()
+                    .join(name.span())
+                    .unwrap_or_else(|| name.span());
+                let mut theme =
+                    Group::new(Delimiter::None, respan(Config::current().theme(), span));
+                theme.set_span(span);
+                let mut dot = Punct::new('.', Spacing::Alone);
+                dot.set_span(span);
+                output.extend(quote_spanned!(span=> #theme #dot #name));
+            }
+            tt => output.extend([tt]),
+        }
+    }
+
+    output
+}
+
+/// Give every token of `tokens` the span `span`, so errors in them point
+/// there, like a resolver at the `$name` it replaces.
+pub fn respan(tokens: TokenStream, span: proc_macro2::Span) -> TokenStream {
+    tokens
+        .into_iter()
+        .map(|mut tt| {
+            if let TokenTree::Group(group) = &tt {
+                let mut respanned = Group::new(group.delimiter(), respan(group.stream(), span));
+                respanned.set_span(span);
+                return TokenTree::Group(respanned);
+            }
+            tt.set_span(span);
+            tt
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use quote::quote;
+
+    use super::*;
+
+    fn expand(tokens: TokenStream) -> String {
+        let expanded = expand_theme_tokens(tokens);
+        // Invisible groups print as plain tokens once re-parsed
+        syn::parse2::<syn::Expr>(quote! { [#expanded] })
+            .map(|expr| quote!(#expr).to_string())
+            .unwrap()
+    }
+
+    #[test]
+    fn test_theme_token() {
+        assert_eq!(
+            expand(quote! { $surface }),
+            quote! { [cx.theme().surface] }.to_string()
+        );
+    }
+
+    #[test]
+    fn test_nested_theme_token() {
+        assert_eq!(
+            expand(quote! { opacity(0.5, $border) }),
+            quote! { [opacity(0.5, cx.theme().border)] }.to_string()
+        );
+    }
+
+    #[test]
+    fn test_lone_dollar_is_kept() {
+        let input = quote! { $ };
+        assert_eq!(
+            expand_theme_tokens(input.clone()).to_string(),
+            input.to_string()
+        );
+    }
+}
@@ -0,0 +1,122 @@
+//! Tooltip sugar on native elements:
+//!
+//! ```ignore
+//! ui! { div("copy") @[tooltip: "Copy path"] { .. } }
+//! ui! { div("save") @[tooltip: ui! { div @[p_2] { "Save", kbd } }] { .. } }
+//! ```
+//!
+//! gpui takes a builder returning an `AnyView`, so the element form is built
+//! by a small view re-rendering it each time the tooltip shows. The string
+//! form calls the `tooltip` helper from `gpui-markup.toml` or an enabled
+//! preset (`ui::Tooltip::text` for Zed's `ui` crate), and falls back to the
+//! text in a plain dark bubble. Any other value is passed through as the
+//! builder.
+
+use proc_macro2::{Span, TokenStream};
+use quote::{quote, quote_spanned};
+use syn::spanned::Spanned;
+use syn::{Expr, ExprLit, Ident, Lit};
+
+use crate::ast::Attribute;
+use crate::config::Config;
+use crate::preset::tooltip_helper;
+
+/// Rewrite `tooltip: "text"` and `tooltip: ui! { .. }` into tooltip builders.
+pub fn expand_tooltips(attributes: &mut [Attribute]) {
+    for attr in attributes {
+        match attr {
+            Attribute::KeyValue { key, value } if key == "tooltip" => {
+                if let Some(builder) = tooltip_builder(value) {
+                    *value = Expr::Verbatim(builder);
+                }
+            }
+            Attribute::Platform { attributes, .. } => expand_tooltips(attributes),
+            _ => {}
+        }
+    }
+}
+
+fn tooltip_builder(value: &Expr) -> Option<TokenStream> {
+    match value {
+        Expr::Lit(ExprLit {
+            lit: Lit::Str(text),
+            ..
+        }) => Some(
+            Config::current()
+                .tooltip()
+                .or_else(tooltip_helper)
+                .map_or_else(
+                    || {
+                        let gpui = Config::current().gpui();
+                        let styled = Config::current().item("Styled");
+                        let parent_element = Config::current().item("ParentElement");
+                        view_builder(
+                            text.span(),
+                            &quote! {
+                                {
+                                    use #styled as _;
+                                    use #parent_element as _;
+                                    #gpui::div()
+                                        .px_2()
+                                        .py_1()
+                                        .rounded_md()
+                                        .shadow_md()
+                                        .bg(#gpui::black().opacity(0.85))
+                                        .text_color(#gpui::white())
+                                        .text_sm()
+                                        .child(#text)
+                                }
+                            },
+                        )
+                    },
+                    |helper| quote_spanned! {text.span()=> #helper(#text) },
+                ),
+        ),
+        Expr::Macro(mac)
+            if mac
+                .mac
+                .path
+                .segments
+                .last()
+                .is_some_and(|s| s.ident == "ui") =>
+        {
+            Some(view_builder(mac.span(), &quote! { #value }))
+        }
+        _ => None,
+    }
+}
+
+/// A builder creating a view that renders `element` on each show, spanned at
+/// the tooltip value.
+///
+/// The builder is made by a function called outside the block declaring it
+/// and the view, so `element` doesn't see their items.
+fn view_builder(span: Span, element: &TokenStream) -> TokenStream {
+    let gpui = Config::current().gpui();
+    let into_element = Config::current().item("IntoElement");
+    let view = Ident::new("__TooltipView", Span::mixed_site());
+    let function = Ident::new("__tooltip_view", Span::mixed_site());
+    quote_spanned! {span=>
+        ({
+            struct #view(::std::rc::Rc<dyn Fn() -> #gpui::AnyElement>);
+            impl #gpui::Render for #view {
+                fn render(
+                    &mut self,
+                    _: &mut #gpui::Window,
+                    _: &mut #gpui::Context<Self>,
+                ) -> impl #into_element {
+                    (self.0)()
+                }
+            }
+            fn #function(
+                render: ::std::rc::Rc<dyn Fn() -> #gpui::AnyElement>,
+            ) -> impl Fn(&mut #gpui::Window, &mut #gpui::App) -> #gpui::AnyView + 'static {
+                move |_, cx| {
+                    let render = ::std::rc::Rc::clone(&render);
+                    #gpui::AnyView::from(#gpui::AppContext::new(cx, |_| #view(render)))
+                }
+            }
+            #function
+        })(::std::rc::Rc::new(move || #into_element::into_any_element(#element)))
+    }
+}
@@ -0,0 +1,123 @@
+//! Unit literals in attribute values: `200px`, `1.5rem`, `50%`.
+//!
+//! Rust's lexer already accepts arbitrary suffixes on numeric literals, so
+//! `200px` and `1.5rem` arrive as single literal tokens. `50%` arrives as a
+//! literal followed by a `%` punct, which is only treated as a unit when it
+//! ends the value (otherwise it is the remainder operator).
+
+use proc_macro2::{Group, Literal, TokenStream, TokenTree};
+use quote::quote_spanned;
+use syn::Lit;
+
+use crate::config::Config;
+
+/// Rewrite unit literals in `tokens` (including nested groups) into gpui
+/// length constructors.
+pub fn expand_units(tokens: TokenStream) -> TokenStream {
+    let mut output = TokenStream::new();
+    let mut tokens = tokens.into_iter();
+
+    while let Some(tt) = tokens.next() {
+        match tt {
+            TokenTree::Group(group) => {
+                let mut expanded = Group::new(group.delimiter(), expand_units(group.stream()));
+                expanded.set_span(group.span());
+                output.extend([TokenTree::Group(expanded)]);
+            }
+            TokenTree::Literal(literal) => {
+                let is_percent = is_percent_sign(tokens.clone());
+                match expand_literal(&literal, is_percent) {
+                    Some(expanded) => {
+                        if is_percent {
+                            tokens.next();
+                        }
+                        output.extend(expanded);
+                    }
+                    None => output.extend([TokenTree::Literal(literal)]),
+                }
+            }
+            tt => output.extend([tt]),
+        }
+    }
+
+    output
+}
+
+/// Whether `tokens` start with a `%` that ends the value (`50%` rather than
+/// `50 % n`).
+fn is_percent_sign(mut tokens: impl Iterator<Item = TokenTree>) -> bool {
+    let is_percent =
+        |tt: &TokenTree| matches!(tt, TokenTree::Punct(punct) if punct.as_char() == '%');
+    let is_comma = |tt: &TokenTree| matches!(tt, TokenTree::Punct(punct) if punct.as_char() == ',');
+
+    tokens.next().is_some_and(|tt| is_percent(&tt)) && tokens.next().is_none_or(|tt| is_comma(&tt))
+}
+
+/// Expand a single numeric literal, if it carries a unit.
+fn expand_literal(literal: &Literal, is_percent: bool) -> Option<TokenStream> {
+    let (value, suffix) = match Lit::new(literal.clone()) {
+        Lit::Int(lit) => (lit.base10_parse::<f32>().ok()?, lit.suffix().to_owned()),
+        Lit::Float(lit) => (lit.base10_parse::<f32>().ok()?, lit.suffix().to_owned()),
+        _ => return None,
+    };
+
+    let gpui = Config::current().gpui();
+    let span = literal.span();
+    let (function, value) = match suffix.as_str() {
+        "px" => (quote_spanned!(span=> #gpui::px), value),
+        "rem" => (quote_spanned!(span=> #gpui::rems), value),
+        "" if is_percent => (quote_spanned!(span=> #gpui::relative), value / 100.0),
+        _ => return None,
+    };
+
+    let mut value = Literal::f32_unsuffixed(value);
+    value.set_span(span);
+    Some(quote_spanned!(span=> #function(#value)))
+}
+
+#[cfg(test)]
+mod tests {
+    use quote::quote;
+
+    use super::*;
+
+    fn expand(tokens: TokenStream) -> String {
+        expand_units(tokens).to_string()
+    }
+
+    #[test]
+    fn test_px() {
+        assert_eq!(
+            expand(quote! { w: 200px }),
+            quote! { w: gpui::px(200.0) }.to_string()
+        );
+    }
+
+    #[test]
+    fn test_rem() {
+        assert_eq!(
+            expand(quote! { text_size: 1.5rem }),
+            quote! { text_size: gpui::rems(1.5) }.to_string()
+        );
+    }
+
+    #[test]
+    fn test_percent() {
+        assert_eq!(
+            expand(quote! { h: 50%, w: (12.5%) }),
+            quote! { h: gpui::relative(0.5), w: (gpui::relative(0.125)) }.to_string()
+        );
+    }
+
+    #[test]
+    fn test_remainder_is_kept() {
+        let input = quote! { when: (count % 2 == 0, f), w: px(10 % 3) };
+        assert_eq!(expand(input.clone()), input.to_string());
+    }
+
+    #[test]
+    fn test_other_suffixes_are_kept() {
+        let input = quote! { id: 1usize, opacity: 0.5f32 };
+        assert_eq!(expand(input.clone()), input.to_string());
+    }
+}
@@ -0,0 +1,523 @@
+//! The procedural macros of gpui-markup, re-exported by the `gpui-markup`
+//! crate. Depend on that one instead.
+
+use gpui_markup_core::{
+    ComponentDecl, Markup, MarkupFn, MarkupVec, RenderImpl, StyleMixin, Templates,
+    derive_base_component, derive_interactive_component, derive_markup, derive_parent_component,
+    derive_props, derive_styled_component, expand_component, expand_declaration, expand_render,
+    expression_errors, partial_expansion,
+};
+use proc_macro::TokenStream;
+use proc_macro_error2::proc_macro_error;
+use quote::quote;
+use syn::{DeriveInput, ItemFn, parse_macro_input};
+
+/// Like `parse_macro_input!`, for macros used as an expression: every syntax
+/// error is reported, see `expression_errors`. Markup with errors is also
+/// sketched for IDE completion, see `partial_expansion`.
+macro_rules! parse_expression_input {
+    ($input:ident as $ty:ty) => {
+        match syn::parse::<$ty>($input) {
+            Ok(parsed) => parsed,
+            Err(error) => return expression_errors(&error).into(),
+        }
+    };
+    ($input:ident as markup $ty:ty) => {{
+        let tokens = $input.clone();
+        match syn::parse::<$ty>($input) {
+            Ok(parsed) => parsed,
+            Err(error) => return partial_expansion(&error, tokens.into()).into(),
+        }
+    }};
+}
+
+/// A declarative markup macro for building GPUI UIs.
+///
+/// # Syntax
+///
+/// ## Basic Elements
+///
+/// ```ignore
+/// ui! { div {} }                        // -> div()
+/// ui! { div @[flex] {} }                // -> div().flex()
+/// ui! { div @[w: px(200.0)] {} }        // -> div().w(px(200.0))
+/// ui! { div @[w: 200px, h: 50%] {} }    // -> div().w(gpui::px(200.0)).h(gpui::relative(0.5))
+/// ui! { div @[size: (a, b)] {} }        // -> div().w(a).h(b)
+/// ui! { div @[rounded: md] {} }         // -> div().rounded_md()
+/// ui! { div @[grid: (cols: 3)] {} }     // -> div().grid().grid_cols(3)
+/// ui! { div @[id: "a", scroll: (y, handle)] {} }
+/// // -> div().id("a").overflow_y_scroll().track_scroll(&handle)
+/// ui! { Header {} }                     // -> Header::new()
+/// ui! { Button("Save") @[disabled: busy] {} }
+/// // -> Button::new("Save").disabled(busy)
+/// ui! { ui::Button {} }                 // -> ui::Button::new()
+/// ui! { List::<Entry> {} }              // -> List::<Entry>::new()
+/// ui! { Button @[:size: Large, :disabled] {} }
+/// // -> Button::new().size(Large).disabled()   (`:props` call builder methods)
+/// ```
+///
+/// ## Platform-Conditional Attributes
+///
+/// ```ignore
+/// ui! { div @[macos: { pt: px(28.0) }] {} }
+/// // -> div().when(cfg!(target_os = "macos"), |element| element.pt(px(28.0)))
+/// ```
+///
+/// ## Pseudo-States
+///
+/// ```ignore
+/// ui! { div @hover[bg: black()] {} }         // -> div().hover(|style| style.bg(black()))
+/// ui! { div().id("a") @active[opacity: 0.5] {} }
+///                                           // -> div().id("a").active(|style| style.opacity(0.5))
+///
+/// // Group styles: `group` on an ancestor, `group_hover`/`group_active` below
+/// ui! { div @[group: "row"] { div @group_hover("row")[visible] {} } }
+///
+/// // Forward styles to a component's base element, see `BaseComponent`
+/// ui! { Badge @base[mt: 2] {} }              // -> Badge::new().map_base(|style| style.mt_2())
+/// ```
+///
+/// ## Events
+///
+/// ```ignore
+/// ui! { div @[id: "a", on:click: handler] {} }  // -> div().id("a").on_click(handler)
+/// ui! { div @[on:mouse_down.right: handler] {} }
+/// // -> div().on_mouse_down(gpui::MouseButton::Right, handler)
+/// ui! { div @[on:key_down.capture: handler] {} }  // -> div().capture_key_down(handler)
+/// ui! { div @[key_context: "Editor", on:key("cmd-s"): handler] {} }
+/// // -> div().key_context("Editor").on_key_down(..), calling `handler` for `cmd-s`
+/// ui! { div @[on:action(SaveFile): handler] {} }  // -> div().on_action::<SaveFile>(handler)
+/// ui! { div @[id: "a", action: SaveFile] {} }
+/// // -> div().id("a").on_click(..), dispatching `SaveFile`
+/// ui! { div @[id: "a", drag: (tab, |tab| preview(tab)), on:drop::<Tab>: handler] {} }
+/// // -> div().id("a").on_drag(tab, ..).on_drop::<Tab>(handler)
+/// ui! { div @[focus: handle, focus_trap] {} }
+/// // -> div().track_focus(&handle).tab_group().capture_key_down(..), keeping Tab inside
+///
+/// // `#![listener(cx)]` wraps `self.method` handlers in `cx.listener`
+/// ui! { #![listener(cx)] div @[id: "a", on:click: self.save] {} }
+/// // -> div().id("a").on_click(cx.listener(Self::save))
+/// ```
+///
+/// ## Children
+///
+/// ```ignore
+/// // Comma-separated children
+/// ui! {
+///     div {
+///         "First",
+///         "Second",
+///     }
+/// }
+/// // -> gpui::ParentElement::child(gpui::ParentElement::child(div(), "First"), "Second")
+///
+/// // `{name}` placeholders in string literals are formatted
+/// ui! { div { "Hello, {name}!" } }
+/// // -> gpui::ParentElement::child(div(), ::std::format!("Hello, {name}!"))
+///
+/// // Numbers and `Display` values
+/// ui! { div { 42, text: count } }
+/// // -> gpui::ParentElement::child(gpui::ParentElement::child(div(), "42"), gpui::SharedString::from(count.to_string()))
+/// ```
+///
+/// ## Styled Text
+///
+/// ```ignore
+/// // Text and `span` children are merged into one `StyledText`
+/// ui! { div { "Press ", span @[font_weight: FontWeight::BOLD] { "Enter" } } }
+/// // -> gpui::ParentElement::child(div(), gpui::StyledText::new(..).with_highlights(..))
+/// ```
+///
+/// ## Conditional Compilation
+///
+/// Children can be gated with `#[cfg(...)]`:
+///
+/// ```ignore
+/// ui! {
+///     div {
+///         #[cfg(debug_assertions)]
+///         div { "Debug overlay" },
+///     }
+/// }
+/// ```
+///
+/// ## Spread Children
+///
+/// Use `..expr` to spread an iterable as children:
+///
+/// ```ignore
+/// let items: Vec<Div> = vec![div(), div()];
+///
+/// ui! {
+///     div {
+///         ..items,
+///     }
+/// }
+/// // -> gpui::ParentElement::children(div(), items)
+///
+/// // Can be mixed with regular children
+/// ui! {
+///     div {
+///         "Header",
+///         ..items,
+///         "Footer",
+///     }
+/// }
+/// // -> gpui::ParentElement::child(
+/// //      gpui::ParentElement::children(
+/// //        gpui::ParentElement::child(div(), "Header"),
+/// //        items
+/// //      ),
+/// //      "Footer"
+/// //    )
+/// ```
+///
+/// ## Method Chains
+///
+/// Use `.method(args)` to insert method calls at any position.
+/// Supports method chains and generics:
+///
+/// ```ignore
+/// ui! {
+///     div {
+///         "static child",
+///         .when(condition, |d| d.child("dynamic")),
+///         .flex().gap_2(),
+///         .map::<Div, _>(|d| d),
+///     }
+/// }
+/// ```
+///
+/// ## Comments
+///
+/// Use standard Rust comments (`//` or `/* */`) inside `ui!`.
+///
+/// ## Expression Elements
+///
+/// Any expression can be used as an element (braces required at top level):
+///
+/// ```ignore
+/// ui! { Button::new("Click") {} }              // -> Button::new("Click")
+/// ui! { Button::new("Click") @[style: Primary] {} }
+///                                              // -> Button::new("Click").style(Primary)
+/// ui! {
+///     div().flex() @[flex_col] {
+///         "Content",
+///     }
+/// }
+/// // -> gpui::ParentElement::child(div().flex().flex_col(), "Content")
+///
+/// // Parentheses for complex expressions (braces optional)
+/// ui! { (a + b) }                              // -> a + b
+/// ```
+///
+/// **Why braces at top level?** The `ui!` macro builds a component tree.
+/// Braces declare "this is a UI element" - they mark it as a tree node,
+/// trigger implicit `::new()` for components, and provide a place for
+/// attributes and children.
+///
+/// ## Multi-value Attributes
+///
+/// Use tuples for attributes with multiple arguments:
+///
+/// ```ignore
+/// ui! { div @[when: (condition, |d| d.flex())] {} }
+/// // -> div().when(condition, |d| d.flex())
+/// ```
+#[proc_macro]
+#[proc_macro_error]
+pub fn ui(input: TokenStream) -> TokenStream {
+    let markup = parse_expression_input!(input as markup Markup);
+    let output = quote! { #markup };
+    output.into()
+}
+
+/// Wrap markup in a `move` closure taking the given parameters.
+///
+/// Useful where gpui wants an element builder, such as list items, pickers
+/// and tooltips:
+///
+/// ```ignore
+/// let row = ui_fn!(|item: &FileEntry| div @[flex] { item.name.clone() });
+/// // -> move |item: &FileEntry| gpui::ParentElement::child(div().flex(), item.name.clone())
+/// ```
+#[proc_macro]
+#[proc_macro_error]
+pub fn ui_fn(input: TokenStream) -> TokenStream {
+    let markup = parse_expression_input!(input as MarkupFn);
+    quote! { #markup }.into()
+}
+
+/// Build a `Vec<AnyElement>` from a list of children, for APIs taking child
+/// collections directly.
+///
+/// Entries are written like the children of an element, including text,
+/// `..spread` and `#[cfg(...)]` gated entries:
+///
+/// ```ignore
+/// let items: Vec<AnyElement> = ui_vec! {
+///     div @[flex] { "First" },
+///     "Second",
+///     ..more,
+/// };
+/// ```
+#[proc_macro]
+#[proc_macro_error]
+pub fn ui_vec(input: TokenStream) -> TokenStream {
+    let markup = parse_expression_input!(input as markup MarkupVec);
+    quote! { #markup }.into()
+}
+
+/// Define parameterized markup fragments, separated by `;`.
+///
+/// Each template becomes a function returning its markup. Templates rooted
+/// in a native element return its type (`gpui::Div`, or
+/// `gpui::Stateful<gpui::Div>` with an `id`), so callers can add attributes
+/// and children when invoking them inside `ui!`:
+///
+/// ```ignore
+/// ui_template! {
+///     row(icon: AnyElement, label: SharedString) => div @[flex, gap_2] { icon, label };
+///     pub(crate) title(text: &'static str) => div @[text_xl] { text }
+/// }
+///
+/// ui! { div { row(icon, "Settings".into()) @[mt_2] { "extra" } } }
+/// ```
+#[proc_macro]
+#[proc_macro_error]
+pub fn ui_template(input: TokenStream) -> TokenStream {
+    let templates = parse_macro_input!(input as Templates);
+    quote! { #templates }.into()
+}
+
+/// Build a reusable `StyleRefinement` from style attributes, applied to
+/// elements with `use:`:
+///
+/// ```ignore
+/// let card = style![rounded_md, border_1, p: px(12.0)];
+///
+/// ui! { div @[use: card, flex] {} }
+/// // -> div().map(|mut element| { Refineable::refine(element.style(), &card); element }).flex()
+/// ```
+#[proc_macro]
+#[proc_macro_error]
+pub fn style(input: TokenStream) -> TokenStream {
+    let mixin = parse_expression_input!(input as StyleMixin);
+    quote! { #mixin }.into()
+}
+
+/// Make a component usable as a `ui!` tag without an inherent `new()`.
+///
+/// Component tags call `::new()`. This derive adds a hidden `new()` calling
+/// the constructor named by `#[markup(constructor = "...")]`, or
+/// `Default::default()` without one:
+///
+/// ```ignore
+/// #[derive(IntoElement, Markup)]
+/// #[markup(constructor = "builder")]
+/// struct Toolbar { .. }
+///
+/// ui! { Toolbar {} }  // -> Toolbar::new(), i.e. Toolbar::builder()
+/// ```
+#[proc_macro_derive(Markup, attributes(markup))]
+pub fn derive(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    derive_markup(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Generate `new()` and a setter per field of a component struct.
+///
+/// Setters take `impl Into<T>`, or `T` itself for primitives. `Option<T>`
+/// fields take the inner type and wrap it in `Some`. `#[prop(default = ...)]`
+/// sets the initial value (`Default::default()` otherwise) and `#[prop(skip)]`
+/// leaves a field without a setter:
+///
+/// ```ignore
+/// #[derive(IntoElement, Props)]
+/// struct Badge {
+///     label: SharedString,
+///     icon: Option<IconName>,
+///     #[prop(skip, default = div())]
+///     base: Div,
+/// }
+///
+/// ui! { Badge @[:label: "Inbox", :icon: IconName::Mail] {} }
+/// // -> Badge::new().label("Inbox").icon(IconName::Mail)
+/// ```
+#[proc_macro_derive(Props, attributes(prop))]
+pub fn props(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    derive_props(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Implement `ParentElement` for a component by extending its `children`
+/// field, or the field marked `#[children]`.
+///
+/// The field can be any collection of `AnyElement` implementing `Extend`,
+/// typically a `SmallVec` or `Vec`, and is passed on to an element in
+/// `render`:
+///
+/// ```ignore
+/// #[derive(IntoElement, ParentComponent)]
+/// struct Card {
+///     children: SmallVec<[AnyElement; 2]>,
+/// }
+///
+/// impl RenderOnce for Card {
+///     fn render(self, _: &mut Window, _: &mut App) -> impl IntoElement {
+///         ui! { div @[p_4] { ..self.children } }
+///     }
+/// }
+///
+/// ui! { Card { "Title", "Body" } }
+/// ```
+#[proc_macro_derive(ParentComponent, attributes(children))]
+pub fn parent_component(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    derive_parent_component(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Implement `Styled` for a component by delegating to its `base` field, or
+/// the field marked `#[base]`, so `@[...]` style attributes apply to it:
+///
+/// ```ignore
+/// #[derive(IntoElement, StyledComponent)]
+/// struct Header {
+///     base: Div,
+/// }
+///
+/// ui! { Header @[flex, p_4] {} }
+/// ```
+#[proc_macro_derive(StyledComponent, attributes(base))]
+pub fn styled_component(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    derive_styled_component(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Implement `InteractiveElement` for a component by delegating to its `base`
+/// field, or the field marked `#[base]`, so `on:...` listeners apply to it.
+///
+/// `#[interactive(stateful)]` also implements `StatefulInteractiveElement`,
+/// for a base that is one (e.g. `Stateful<Div>`):
+///
+/// ```ignore
+/// #[derive(IntoElement, InteractiveComponent)]
+/// #[interactive(stateful)]
+/// struct Row {
+///     base: Stateful<Div>,
+/// }
+///
+/// ui! { Row @[on:click: handler] {} }
+/// ```
+#[proc_macro_derive(InteractiveComponent, attributes(base, interactive))]
+pub fn interactive_component(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    derive_interactive_component(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Add a `map_base` method transforming a component's `base` field, or the
+/// field marked `#[base]`, so callers can forward style attributes to it
+/// with `@base[...]`:
+///
+/// ```ignore
+/// #[derive(IntoElement, BaseComponent)]
+/// struct Badge {
+///     base: Div,
+/// }
+///
+/// ui! { Badge @base[mt: 2, w: px(120.0)] {} }
+/// // -> Badge::new().map_base(|style| style.mt_2().w(px(120.0)))
+/// ```
+#[proc_macro_derive(BaseComponent, attributes(base))]
+pub fn base_component(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    derive_base_component(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Turn a function into a component usable as a `ui!` tag.
+///
+/// The function becomes a `RenderOnce` struct named in `PascalCase`, with a
+/// `new()` and one builder method per parameter, as generated by
+/// `#[derive(Props)]`: setters take `impl Into<T>`, or `T` itself for
+/// primitives, and `#[prop(default = ...)]` sets the initial value
+/// (`Default::default()` otherwise). Parameters `window` and `cx` are bound to
+/// the arguments of `render`. Doc comments and `#[cfg(...)]` attributes are
+/// kept, other attributes of the function are dropped:
+///
+/// ```ignore
+/// #[component]
+/// fn badge(label: SharedString, count: usize) -> impl IntoElement {
+///     ui! { div { label, count.to_string() } }
+/// }
+///
+/// ui! { Badge @[:label: "Inbox", :count: 3] {} }
+/// ```
+#[proc_macro_attribute]
+pub fn component(args: TokenStream, input: TokenStream) -> TokenStream {
+    let item = parse_macro_input!(input as ItemFn);
+    expand_component(&args.into(), &item)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Declare a component extending a native element (`div` or `svg`).
+///
+/// The struct gets a `base` field holding the element, a `new()` and setters
+/// as with `#[derive(Props)]`, `Styled`, `InteractiveElement` and `map_base`
+/// delegating to `base`, `ParentElement` when the element takes children, and
+/// a `RenderOnce` implementation running the `render` block:
+///
+/// ```ignore
+/// define_component! {
+///     pub struct Badge extends div {
+///         label: SharedString,
+///         #[prop(default = 1)]
+///         count: usize,
+///     }
+///
+///     render(self, window, cx) {
+///         ui! { self.base @[flex, gap_1] { self.label, self.count.to_string() } }
+///     }
+/// }
+///
+/// ui! { Badge @[:label: "Inbox", :count: 3, p_2] {} }
+/// ```
+#[proc_macro]
+pub fn define_component(input: TokenStream) -> TokenStream {
+    let decl = parse_macro_input!(input as ComponentDecl);
+    expand_declaration(&decl)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Implement `Render` for a view whose body is pure markup.
+///
+/// The closure takes `self`, optionally followed by names for the window and
+/// the context, and its body becomes the `render` method:
+///
+/// ```ignore
+/// render! {
+///     struct = Counter,
+///     |self, window, cx| ui! { div { "Count:", text: self.count } }
+/// }
+/// // -> impl Render for Counter {
+/// //        fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement { .. }
+/// //    }
+/// ```
+#[proc_macro]
+pub fn render(input: TokenStream) -> TokenStream {
+    let render = parse_macro_input!(input as RenderImpl);
+    expand_render(&render).into()
+}
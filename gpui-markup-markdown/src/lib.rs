@@ -0,0 +1,324 @@
+//! The Markdown subset of gpui-markup's `markdown: source` children.
+//!
+//! Supports `#` headings, paragraphs, `-`/`*` and numbered lists, fenced code
+//! blocks, and inline `**strong**`, `*emphasis*`, `` `code` `` and
+//! `[links](url)`.
+//!
+//! The `ui!` macro renders string literals at compile time with this parser.
+//! Other sources are dedented and parsed by it when the element is built,
+//! through the re-export in `gpui-markup`.
+
+use std::ops::Range;
+
+/// A range of inline text and every style covering it.
+pub type StyledRange = (Range<usize>, Vec<Inline>);
+
+/// A block of a Markdown document.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Block {
+    /// A `#` heading and its level, from 1 to 6.
+    Heading(usize, String),
+    /// Consecutive lines of text.
+    Paragraph(String),
+    /// Consecutive list items.
+    List {
+        /// Whether the items are numbered.
+        ordered: bool,
+        /// The text of each item.
+        items: Vec<String>,
+    },
+    /// The contents of a fenced code block.
+    Code(String),
+}
+
+/// A style of inline text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Inline {
+    /// `**strong**`
+    Strong,
+    /// `*emphasis*`
+    Emphasis,
+    /// `` `code` ``
+    Code,
+    /// `[label](url)`
+    Link,
+}
+
+/// Strip the line break after the opening quote, the indentation before the
+/// closing quote and the indentation common to all non-blank lines.
+///
+/// Source indented along with the code around it then reads as written.
+#[must_use]
+pub fn dedent(source: &str) -> String {
+    let source = source.strip_prefix('\n').unwrap_or(source);
+    let source = source.trim_end_matches([' ', '\t']);
+    let source = source.strip_suffix('\n').unwrap_or(source);
+
+    let indent = source
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| line.len() - line.trim_start().len())
+        .min()
+        .unwrap_or(0);
+
+    source
+        .lines()
+        .map(|line| line.get(indent..).unwrap_or_else(|| line.trim_start()))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Split Markdown source into blocks.
+#[must_use]
+pub fn parse_blocks(source: &str) -> Vec<Block> {
+    let mut blocks = vec![];
+    let mut lines = source.lines().peekable();
+
+    while let Some(line) = lines.next() {
+        let trimmed = line.trim();
+        if trimmed.is_empty() {
+            continue;
+        }
+
+        if trimmed.starts_with("```") {
+            let code: Vec<_> = lines
+                .by_ref()
+                .take_while(|line| !line.trim().starts_with("```"))
+                .collect();
+            blocks.push(Block::Code(code.join("\n")));
+        } else if let Some((level, text)) = heading(trimmed) {
+            blocks.push(Block::Heading(level, text.to_owned()));
+        } else if let Some((ordered, item)) = list_item(trimmed) {
+            let mut items = vec![item.to_owned()];
+            while let Some((_, item)) = lines
+                .peek()
+                .and_then(|line| list_item(line.trim()))
+                .filter(|&(next_ordered, _)| next_ordered == ordered)
+            {
+                items.push(item.to_owned());
+                lines.next();
+            }
+            blocks.push(Block::List { ordered, items });
+        } else {
+            let mut paragraph = trimmed.to_owned();
+            while let Some(line) = lines.peek().map(|line| line.trim()) {
+                if line.is_empty()
+                    || line.starts_with("```")
+                    || heading(line).is_some()
+                    || list_item(line).is_some()
+                {
+                    break;
+                }
+                paragraph.push(' ');
+                paragraph.push_str(line);
+                lines.next();
+            }
+            blocks.push(Block::Paragraph(paragraph));
+        }
+    }
+
+    blocks
+}
+
+fn heading(line: &str) -> Option<(usize, &str)> {
+    let level = line.bytes().take_while(|&b| b == b'#').count();
+    let text = line[level..].strip_prefix(' ')?;
+    (1..=6).contains(&level).then(|| (level, text.trim()))
+}
+
+/// A list item, and whether it belongs to an ordered list.
+fn list_item(line: &str) -> Option<(bool, &str)> {
+    if let Some(item) = ["- ", "* ", "+ "]
+        .iter()
+        .find_map(|marker| line.strip_prefix(marker))
+    {
+        return Some((false, item.trim()));
+    }
+    let digits = line.bytes().take_while(u8::is_ascii_digit).count();
+    let item = line[digits..].strip_prefix(". ").filter(|_| digits > 0)?;
+    Some((true, item.trim()))
+}
+
+/// The plain text of inline Markdown, and its styled ranges.
+///
+/// The ranges are sorted and don't overlap, each with every style covering it.
+#[must_use]
+pub fn parse_styled(source: &str) -> (String, Vec<StyledRange>) {
+    let mut text = String::new();
+    let mut styles = vec![];
+    parse_inline(source, &mut text, &mut styles);
+    let styles = flatten(&styles);
+    (text, styles)
+}
+
+/// Parse inline markup in `source`, appending the plain text to `text` and
+/// the styled ranges of it to `styles`.
+fn parse_inline(source: &str, text: &mut String, styles: &mut Vec<(Range<usize>, Inline)>) {
+    let mut rest = source;
+
+    while let Some(c) = rest.chars().next() {
+        if let Some(escaped) = rest.strip_prefix('\\').and_then(|rest| rest.chars().next()) {
+            text.push(escaped);
+            rest = &rest[1 + escaped.len_utf8()..];
+            continue;
+        }
+
+        let delimited = [
+            ("**", Inline::Strong),
+            ("*", Inline::Emphasis),
+            ("`", Inline::Code),
+        ]
+        .into_iter()
+        .find_map(|(marker, style)| {
+            let inner = rest.strip_prefix(marker)?;
+            let end = inner.find(marker).filter(|&end| end > 0)?;
+            let content = &inner[..end];
+            // `2 * 3` is not emphasis: the content must hug the markers
+            if style != Inline::Code
+                && (content.starts_with(char::is_whitespace)
+                    || content.ends_with(char::is_whitespace))
+            {
+                return None;
+            }
+            Some((style, content, &inner[end + marker.len()..]))
+        });
+        if let Some((style, inner, after)) = delimited {
+            let start = text.len();
+            if style == Inline::Code {
+                text.push_str(inner);
+            } else {
+                parse_inline(inner, text, styles);
+            }
+            styles.push((start..text.len(), style));
+            rest = after;
+            continue;
+        }
+
+        if let Some((label, after)) = link(rest) {
+            let start = text.len();
+            parse_inline(label, text, styles);
+            styles.push((start..text.len(), Inline::Link));
+            rest = after;
+            continue;
+        }
+
+        text.push(c);
+        rest = &rest[c.len_utf8()..];
+    }
+}
+
+/// `[label](url)`, returning the label and the text after the link.
+fn link(source: &str) -> Option<(&str, &str)> {
+    let inner = source.strip_prefix('[')?;
+    let close = inner.find("](")?;
+    let after_url = inner[close + 2..].find(')')?;
+    Some((&inner[..close], &inner[close + 2 + after_url + 1..]))
+}
+
+/// Split possibly nested styled ranges into non-overlapping, sorted ranges,
+/// each with every style that covers it.
+fn flatten(styles: &[(Range<usize>, Inline)]) -> Vec<StyledRange> {
+    let mut boundaries: Vec<usize> = styles
+        .iter()
+        .flat_map(|(range, _)| [range.start, range.end])
+        .collect();
+    boundaries.sort_unstable();
+    boundaries.dedup();
+
+    boundaries
+        .windows(2)
+        .filter_map(|window| {
+            let range = window[0]..window[1];
+            let covering: Vec<Inline> = styles
+                .iter()
+                .filter(|(styled, _)| styled.start <= range.start && range.end <= styled.end)
+                .map(|&(_, style)| style)
+                .collect();
+            (!covering.is_empty()).then_some((range, covering))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn inline(source: &str) -> (String, Vec<(Range<usize>, Inline)>) {
+        let mut text = String::new();
+        let mut styles = vec![];
+        parse_inline(source, &mut text, &mut styles);
+        (text, styles)
+    }
+
+    #[test]
+    fn test_dedent() {
+        let source = "\n    # Title\n\n    ```\n      indented\n    ```\n    ";
+        assert_eq!(dedent(source), "# Title\n\n```\n  indented\n```");
+    }
+
+    #[test]
+    fn test_parse_blocks() {
+        let source =
+            "# Title\n\nSome text\ncontinued.\n\n- one\n- two\n\n1. first\n\n```\nlet x = 1;\n```";
+        assert_eq!(
+            parse_blocks(source),
+            [
+                Block::Heading(1, "Title".to_owned()),
+                Block::Paragraph("Some text continued.".to_owned()),
+                Block::List {
+                    ordered: false,
+                    items: vec!["one".to_owned(), "two".to_owned()],
+                },
+                Block::List {
+                    ordered: true,
+                    items: vec!["first".to_owned()],
+                },
+                Block::Code("let x = 1;".to_owned()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_inline() {
+        assert_eq!(
+            inline("a **b *c* d** `e` [f](https://example.com)"),
+            (
+                "a b c d e f".to_owned(),
+                vec![
+                    (4..5, Inline::Emphasis),
+                    (2..7, Inline::Strong),
+                    (8..9, Inline::Code),
+                    (10..11, Inline::Link),
+                ]
+            )
+        );
+        assert_eq!(inline(r"2 * 3 \* 4"), ("2 * 3 * 4".to_owned(), vec![]));
+    }
+
+    #[test]
+    fn test_parse_styled() {
+        assert_eq!(
+            parse_styled("a **b *c* d**"),
+            (
+                "a b c d".to_owned(),
+                vec![
+                    (2..4, vec![Inline::Strong]),
+                    (4..5, vec![Inline::Emphasis, Inline::Strong]),
+                    (5..7, vec![Inline::Strong]),
+                ]
+            )
+        );
+    }
+
+    #[test]
+    fn test_flatten() {
+        let styles = [(2..5, Inline::Strong), (4..5, Inline::Emphasis)];
+        assert_eq!(
+            flatten(&styles),
+            [
+                (2..4, vec![Inline::Strong]),
+                (4..5, vec![Inline::Strong, Inline::Emphasis]),
+            ]
+        );
+    }
+}
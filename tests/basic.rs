@@ -1,8 +1,12 @@
 //! Basic syntax tests for gpui-markup.
 
 use gpui::prelude::FluentBuilder;
-use gpui::{FontWeight, InteractiveElement, Styled, deferred, div, px};
-use gpui_markup::ui;
+use gpui::{
+    Div, FontWeight, InteractiveElement, ListAlignment, ListSizingBehavior, ListState,
+    ParentElement, StatefulInteractiveElement, Styled, canvas, deferred, div, fill, list, px,
+    relative, svg, uniform_list,
+};
+use gpui_markup::{style, ui, ui_fn, ui_template, ui_vec};
 
 #[test]
 fn test_empty_div() {
@@ -19,6 +23,287 @@ fn test_div_with_value_attributes() {
     let _ = ui! { div @[w: px(200.0), h: px(100.0)] {} };
 }
 
+#[test]
+fn test_div_with_unit_literals() {
+    let _ = ui! {
+        div @[w: 200px, h: 50%, text_size: 1.5rem, mt: -4px] {}
+    };
+}
+
+#[test]
+fn test_div_with_size() {
+    let _ = ui! {
+        div @[size: px(24.0)] {
+            div @[size: (relative(1.0), px(100.0))] {},
+        }
+    };
+}
+
+#[test]
+fn test_div_with_font() {
+    let _ = ui! {
+        div @[font: ("Zed Mono", px(13.0), FontWeight::MEDIUM)] {
+            div @[font: ("Zed Mono",)] {},
+            div @[font: (_, px(12.0))] {},
+        }
+    };
+}
+
+#[test]
+fn test_div_with_edge_shorthands() {
+    let _ = ui! {
+        div @[p: (px(8.0), px(16.0)), m: (px(1.0), px(2.0), px(3.0), px(4.0))] {
+            div @[p: (px(1.0), px(2.0), px(3.0)), m: px(4.0)] {},
+        }
+    };
+}
+
+#[test]
+fn test_div_with_border_shorthand() {
+    let color = gpui::red();
+    let _ = ui! {
+        div @[border: (1., color)] {
+            div @[border_b: (px(3.0), color), border_x: (2, gpui::blue())] {},
+        }
+    };
+}
+
+#[test]
+fn test_div_with_keyword_values() {
+    let radius = px(3.0);
+    let _ = ui! {
+        div @[rounded: md, shadow: lg] {
+            div @[rounded_t: 2xl, rounded_b: radius, shadow: none] {},
+        }
+    };
+}
+
+#[test]
+fn test_div_with_spacing_scale() {
+    let _ = ui! {
+        div @[gap: 2, p: 4, gap_x: 0.5] {
+            div @[m: (1, 2.5), py: 96] {},
+        }
+    };
+}
+
+#[test]
+fn test_div_with_grid_shorthand() {
+    let rows = 2;
+    let _ = ui! {
+        div @[grid: (cols: 3, rows: rows, gap: 2)] {
+            div @[grid_cols: 2] {},
+        }
+    };
+}
+
+#[test]
+fn test_div_with_position_shorthand() {
+    let _ = ui! {
+        div @[relative: (size: px(32.0))] {
+            div @[absolute: (top: px(0.0), right: px(8.0))] {},
+            div @[absolute: (inset: 0)] {},
+        }
+    };
+}
+
+#[test]
+fn test_div_with_attribute_aliases() {
+    let _ = ui! {
+        div @[width: px(200.0), height: px(100.0), padding: 2, margin: px(4.0)] @[
+            background: gpui::white(),
+            color: gpui::black(),
+        ] {}
+    };
+}
+
+#[test]
+fn test_div_with_joined_text() {
+    let _ = ui! {
+        div {
+            "This sentence is long enough "
+            "to be split across lines.",
+            r"
+                First paragraph.
+
+                Second paragraph.
+            ",
+        }
+    };
+}
+
+#[test]
+fn test_div_with_spans() {
+    let key = "Enter";
+    let _ = ui! {
+        div {
+            "Press ",
+            span @[font_weight: FontWeight::BOLD, underline] { "{key}" },
+            " to continue, or ",
+            span @[italic, color: gpui::red()] { "Escape" },
+        }
+    };
+}
+
+#[test]
+fn test_event_attributes() {
+    let _ = ui! {
+        div @[
+            id: "button",
+            on:click: |_, _, _| {},
+            on:hover: |hovered, _, _| assert!(*hovered),
+            on:mouse_down: |_, _, _| {},
+            on:scroll_wheel: |_, window, _| window.refresh(),
+            on:key_down: |event, _, _| assert!(!event.keystroke.key.is_empty()),
+        ] {
+            svg @[on:mouse_move: |_, _, _| {}] {},
+        }
+    };
+}
+
+#[test]
+fn test_event_modifiers() {
+    let _ = ui! {
+        div @[
+            id: "row",
+            on:click.double: |event, _, _| assert_eq!(event.click_count(), 2),
+            on:click.right: |event, _, _| assert!(event.is_right_click()),
+            on:mouse_down.middle: |_, _, _| {},
+            on:mouse_up.left.double: |event, _, _| assert_eq!(event.click_count, 2),
+        ] {}
+    };
+}
+
+#[test]
+fn test_capture_modifier() {
+    let _ = ui! {
+        div @[
+            on:mouse_down.capture: |_, _, _| {},
+            on:mouse_up.capture.right.double: |event, _, _| assert_eq!(event.click_count, 2),
+            on:key_down.capture: |event, _, _| assert!(!event.keystroke.key.is_empty()),
+            on:key_up.capture: |_, _, _| {},
+        ] {}
+    };
+}
+
+#[test]
+fn test_key_bindings() {
+    let _ = ui! {
+        div @[
+            key_context: "Editor",
+            on:key("cmd-s"): |event, _, _| assert_eq!(event.keystroke.key, "s"),
+            on:key("ctrl-shift-tab"): |_, _, _| {},
+            on:key("escape").capture: |_, _, _| {},
+        ] {}
+    };
+}
+
+/// `actions!` derives `PartialEq` without `Eq`
+#[test]
+#[allow(clippy::derive_partial_eq_without_eq)]
+fn test_actions() {
+    gpui::actions!(basic, [SaveFile, CloseFile]);
+
+    let _ = ui! {
+        div @[
+            key_context: "Editor",
+            on:action(SaveFile): |_, _, _| {},
+            on:action(CloseFile).capture: |action, _, _| assert_eq!(*action, CloseFile),
+        ] {
+            div @[id: "save", action: SaveFile] { "Save" },
+        }
+    };
+}
+
+#[derive(Clone)]
+struct DraggedTab(usize);
+
+#[test]
+fn test_drag_and_drop() {
+    let _ = ui! {
+        div @[
+            id: "tab",
+            drag: (DraggedTab(0), |tab| div().child(format!("Tab {}", tab.0))),
+            on:drop::<DraggedTab>: |tab, _, _| assert_eq!(tab.0, 0),
+            on:drag_move::<DraggedTab>: |event, _, cx| assert_eq!(event.drag(cx).0, 0),
+            drag_over::<DraggedTab>: |style, _, _, _| style.bg(gpui::blue()),
+        ] {}
+    };
+}
+
+#[test]
+fn test_scroll() {
+    let handle = gpui::ScrollHandle::new();
+    let _ = ui! {
+        div @[id: "list", scroll: (y, handle)] {
+            div @[id: "row", scroll: x] {},
+            div @[id: "grid", scroll: (both, handle)] {},
+        }
+    };
+}
+
+#[test]
+fn test_rich_text() {
+    let url = "https://docs.rs/gpui-markup";
+    let _ = ui! {
+        div {
+            rich_text @[id: "log"] {
+                "See ",
+                span @[color: gpui::blue(), on_click: move |_, cx| cx.open_url(url)] { "the docs" },
+                " or ",
+                span @[underline, on_click: |window, _| window.refresh()] { "reload" },
+            },
+        }
+    };
+}
+
+#[test]
+fn test_div_with_links() {
+    let number = 42;
+    let _ = ui! {
+        div {
+            link("Documentation", "https://docs.rs/gpui-markup") @[text_color: gpui::blue()] {},
+            link("Issue #{number}", format!("https://github.com/so1ve/gpui-markup/issues/{number}")),
+        }
+    };
+}
+
+#[cfg(feature = "markdown")]
+#[test]
+fn test_div_with_markdown() {
+    let _ = ui! {
+        div {
+            markdown: "
+                # Title
+
+                Some **bold** and *italic* text with `code`.
+
+                1. first
+                2. second
+
+                ```
+                let x = 1;
+                ```
+            ",
+        }
+    };
+}
+
+#[cfg(feature = "markdown")]
+#[test]
+fn test_div_with_runtime_markdown() {
+    const NOTES: &str = "
+        # Notes
+
+        - **New:** runtime sources
+    ";
+    let _ = ui! {
+        div {
+            markdown: NOTES,
+        }
+    };
+}
+
 #[test]
 fn test_div_with_mixed_attributes() {
     let _ = ui! {
@@ -75,6 +360,39 @@ fn test_hover_attribute() {
     };
 }
 
+#[test]
+fn test_pseudo_state_blocks() {
+    let _ = ui! {
+        div().id("button")
+            @[bg: gpui::white()]
+            @hover[bg: gpui::black(), cursor_pointer]
+            @active[opacity: 0.5]
+        {}
+    };
+}
+
+#[test]
+fn test_group_hover() {
+    let _ = ui! {
+        div().id("row") @[group: "row"] {
+            div @group_hover("row")[visible] @[invisible] {},
+            div().id("label") @group_active("row")[opacity: 0.5] {},
+        }
+    };
+}
+
+#[test]
+fn test_platform_attributes() {
+    let _ = ui! {
+        div @[
+            flex,
+            macos: { pt: px(28.0), rounded_md },
+            windows: { pt: px(8.0) },
+            linux: {},
+        ] {}
+    };
+}
+
 #[test]
 fn test_when_attribute() {
     let active = true;
@@ -138,3 +456,471 @@ fn test_deferred() {
         }
     };
 }
+
+#[test]
+fn test_cfg_children() {
+    let _ = ui! {
+        div {
+            "Always",
+            #[cfg(debug_assertions)]
+            div { "Debug overlay" },
+            #[cfg(any())]
+            div { "Never" },
+            #[cfg(not(any()))]
+            .flex(),
+        }
+    };
+}
+
+#[test]
+fn test_ui_fn() {
+    struct FileEntry {
+        name: gpui::SharedString,
+    }
+
+    fn render_rows(rows: &[FileEntry], row: impl Fn(&FileEntry) -> gpui::Div) -> Vec<gpui::Div> {
+        rows.iter().map(row).collect()
+    }
+
+    let selected = 1;
+    let rows = [
+        FileEntry {
+            name: "a.rs".into(),
+        },
+        FileEntry {
+            name: "b.rs".into(),
+        },
+    ];
+    let _ = render_rows(
+        &rows,
+        ui_fn!(|entry: &FileEntry| div @[flex, when: (selected == 1, |d| d.font_weight(FontWeight::BOLD))] {
+            entry.name.clone(),
+        }),
+    );
+    let placeholder = ui_fn!(|| div { "Empty" });
+    let _ = placeholder();
+}
+
+#[test]
+fn test_ui_vec() {
+    let more = vec![div(), div()];
+    let items: Vec<gpui::AnyElement> = ui_vec! {
+        div @[flex] { "First" },
+        "Second",
+        #[cfg(any())]
+        div { "Never" },
+        ..more,
+    };
+    assert_eq!(items.len(), 4);
+}
+
+ui_template! {
+    row(icon: gpui::AnyElement, label: gpui::SharedString) => div @[flex, gap_2] {
+        icon,
+        label,
+    };
+    title(text: &'static str) => div @[font_weight: FontWeight::BOLD] { text }
+}
+
+#[test]
+fn test_ui_template() {
+    let icon = gpui::IntoElement::into_any_element(div());
+    let _ = ui! {
+        div {
+            title("Settings") {},
+            row(icon, "General".into()) @[mt_2, id: "general"] {
+                "extra",
+            },
+        }
+    };
+}
+
+#[test]
+fn test_style_mixins() {
+    let card = style![rounded_md, border_1, p: px(12.0), width: relative(0.5)];
+    let elevated = style![shadow_md];
+    let _ = ui! {
+        div @[use: card, flex] {
+            div @[use: (card, elevated)] {},
+        }
+    };
+}
+
+#[test]
+fn test_style_attribute() {
+    let refinement = style![p_2].text_color(gpui::red());
+    let _ = ui! { div @[style: refinement, flex] {} };
+}
+
+#[test]
+fn test_apply() {
+    fn with_extras(el: Div) -> Div {
+        el.p_2()
+    }
+    let _ = ui! {
+        div @[apply: with_extras, flex, apply: (with_extras, |el: Div| el.mt_2())] {}
+    };
+}
+
+#[test]
+fn test_bind() {
+    fn caption(header: &Div) -> &'static str {
+        let _ = header;
+        "Caption"
+    }
+    let _ = ui! {
+        div {
+            div @[bind: header, flex] { "Header" },
+            div { caption(&header) },
+        }
+    };
+}
+
+#[test]
+fn test_canvas() {
+    let _ = ui! {
+        div {
+            canvas @[size_full] {
+                prepaint: |bounds, _window, _cx| bounds,
+                paint: |_, bounds, window, _cx| window.paint_quad(fill(bounds, gpui::red())),
+            },
+            canvas { paint: |_, (), _, _| {} },
+        }
+    };
+}
+
+#[test]
+fn test_list() {
+    let state = ListState::new(10, ListAlignment::Bottom, px(200.0));
+    let _ = ui! {
+        div {
+            list(state) @[size_full, sizing_behavior: ListSizingBehavior::Infer] {
+                |ix, _window, _cx| ui! { div { "Message {ix}" } }
+            },
+            list @[state: state, flex_grow] { |_, _, _| "Item" },
+        }
+    };
+}
+
+#[test]
+fn test_uniform_list() {
+    let names = ["a.rs", "b.rs", "c.rs"];
+    let _ = ui! {
+        uniform_list("files", names.len()) @[size_full]
+            |range, _window, _cx| {
+                ..names[range].iter().map(|name| ui! { div { *name } }),
+            }
+    };
+}
+
+#[test]
+fn test_overlay() {
+    let position = gpui::point(px(10.0), px(20.0));
+    let _ = ui! {
+        div {
+            overlay @[position: position, anchor: gpui::Corner::TopRight, fit_mode: snap_to_window] {
+                div { "Menu" },
+            },
+            overlay @[fit_mode: gpui::AnchoredFitMode::SnapToWindowWithMargin(px(8.0).into())] {},
+        }
+    };
+}
+
+#[test]
+fn test_empty() {
+    let loading = false;
+    let _ = ui! {
+        div {
+            (if loading {
+                gpui::IntoElement::into_any_element(ui! { div { "Loading" } })
+            } else {
+                gpui::IntoElement::into_any_element(ui! { empty {} })
+            }),
+            empty {},
+        }
+    };
+}
+
+#[test]
+fn test_stateful_div() {
+    let ix = 3_usize;
+    let _: gpui::Stateful<Div> = ui! {
+        div("toolbar") @[flex, overflow_y_scroll] {
+            div id(("row", ix)) @[on_click: |_, _, _| {}] { "Row" },
+        }
+    };
+}
+
+#[test]
+fn test_keyed_ids() {
+    let rows = [1_usize, 2, 3];
+    let _: Div = ui! {
+        div {
+            ..rows.iter().map(|&row| ui! {
+                div @[key: row, on:click: |_, _, _| {}] { "Row" }
+            }),
+            div @[apply: |d| d.id("apply"), on:click: |_, _, _| {}] {},
+        }
+    };
+}
+
+#[test]
+fn test_flex_elements() {
+    let _: Div = ui! {
+        v_flex @[gap: px(8.0)] {
+            h_flex @[items_center] { "Title" },
+            h_flex @[id: "actions", on:click: |_, _, _| {}] {},
+        }
+    };
+}
+
+#[test]
+fn test_layout_elements() {
+    struct Theme {
+        border: gpui::Hsla,
+    }
+    struct Context {
+        theme: Theme,
+    }
+    impl Context {
+        const fn theme(&self) -> &Theme {
+            &self.theme
+        }
+    }
+
+    let cx = Context {
+        theme: Theme {
+            border: gpui::black(),
+        },
+    };
+    let _: Div = ui! {
+        v_flex {
+            stack @[size_full] {
+                div { "Background" },
+                div @[bottom_0] { "Caption" },
+            },
+            divider {},
+            h_flex { "Left", spacer {}, divider @[vertical] {}, "Right" },
+        }
+    };
+}
+
+#[test]
+fn test_portal() {
+    let _ = |window: &mut gpui::Window| {
+        ui! {
+            div {
+                portal(window) @[priority: 10, flex, items_center, justify_center] {
+                    div @[p: 4] { "Modal" },
+                },
+                portal(window) { "Toast" },
+            }
+        }
+    };
+    let _ = |window: &mut gpui::Window| ui! { portal(window) { "Toast" } };
+}
+
+#[test]
+fn test_deferred_priority() {
+    let _ = ui! {
+        div {
+            deferred @[priority: 10] {
+                div { "Deferred content" },
+            },
+        }
+    };
+}
+
+#[test]
+fn test_custom_elements() {
+    mod my_ui {
+        use gpui::{Div, Styled, div};
+
+        pub fn row() -> Div {
+            div().flex().flex_row()
+        }
+    }
+
+    let _: Div = ui! {
+        #![elements(row = my_ui::row -> gpui::Div)]
+        row @[gap_2] {
+            row {},
+            "Saved",
+        }
+    };
+}
+
+#[test]
+fn test_tooltips() {
+    let _ = ui! {
+        div("copy") @[tooltip: "Copy path"] {
+            div("save") @[tooltip: ui! { div @[p_2] { "Save" } }] {},
+        }
+    };
+}
+
+#[test]
+fn test_boxed_children() {
+    let _ = ui! {
+        #![options(boxed_children)]
+        div {
+            "Title",
+            div { "Body" },
+            div { "A", "B", "C", "D" },
+        }
+    };
+}
+
+#[test]
+fn test_static_subtree() {
+    let query = "gpui";
+    let _ = ui! {
+        div {
+            div @[flex, gap: 2, p: 16px] {
+                div @[text_color: gpui::rgb(0x0088_8888)] { "No results" },
+                "Try another search",
+            },
+            div { "Query: {query}" },
+        }
+    };
+}
+
+#[test]
+fn test_linear() {
+    let width = px(200.0);
+    let _ = ui! {
+        #![options(linear)]
+        div @[flex, w: width] {
+            "Title",
+            div { "Body" },
+            #[cfg(debug_assertions)]
+            div { "Debug" },
+        }
+    };
+}
+
+#[test]
+fn test_constant_conditions() {
+    let _: Div = ui! {
+        div @[
+            when: (true, Styled::flex),
+            when: (false, Styled::hidden),
+            when_else: (false, Styled::p_1, Styled::p_2),
+        ] {}
+    };
+}
+
+#[test]
+fn test_memo() {
+    let _ = |window: &mut gpui::Window, cx: &mut gpui::App| {
+        let version = 3_usize;
+        let points = [1.0_f32, 2.0, 3.0];
+        ui! {
+            div {
+                memo(version) @[h: px(320.0)] {
+                    div { (format!("{} points", points.len())) },
+                },
+                memo(version, ("row", 1_usize)) { div { "Row" } },
+            }
+        }
+    };
+}
+
+#[test]
+fn test_lazy() {
+    let expanded = false;
+    let label = String::from("Details");
+    let details = ui! { lazy { div { (label.clone()) } } };
+    let _ = ui! {
+        div @[when: (expanded, move |this| gpui::ParentElement::child(this, details))] {}
+    };
+}
+
+#[test]
+fn test_expression_element_children() {
+    let _: Div = ui! {
+        div().flex() @[gap_2] {
+            "Title",
+            (div()) { "Nested" },
+        }
+    };
+}
+
+#[test]
+fn test_checked() {
+    let _: Div = ui! {
+        #![options(checked)]
+        div @[flex] {
+            (div()) @[bg: gpui::red(), p_2, size: px(16.)] {},
+        }
+    };
+}
+
+#[test]
+fn test_children_without_commas() {
+    let _: Div = ui! {
+        div @[flex, flex_col] {
+            div { "Title" }
+            div @[flex_1] {}
+            "Footer",
+        }
+    };
+}
+
+#[test]
+fn test_attribute_evaluation_order() {
+    let order = std::cell::RefCell::new(vec![]);
+    let record = |name: &'static str| {
+        order.borrow_mut().push(name);
+        px(1.0)
+    };
+    let _ = ui! {
+        div @[
+            w: record("w"),
+            id: "tab",
+            drag: (record("drag"), |_: &gpui::Pixels| div()),
+            size: record("size"),
+        ] {
+            div @[h: record("child")] {}
+        }
+    };
+    assert_eq!(*order.borrow(), ["w", "drag", "size", "child"]);
+}
+
+#[test]
+fn test_helpers_do_not_shadow_user_items() {
+    fn __into_listener() -> impl Fn(&gpui::ClickEvent, &mut gpui::Window, &mut gpui::App) {
+        |_, _, _| {}
+    }
+    fn __into_child() -> &'static str {
+        "Child"
+    }
+    let _ = ui! { div @[id: "a", on:click.right: __into_listener()] {} };
+    let _ = ui! {
+        #![options(checked)]
+        div { lazy { div { __into_child() } } }
+    };
+}
+
+#[test]
+fn test_inferred_attribute_values() {
+    let gap: u16 = 4;
+    let _ = ui! {
+        div @[opacity: Default::default(), gap: px(gap.into()), flex_grow] {}
+    };
+}
+
+#[test]
+fn test_locals_named_like_tags() {
+    let (span, list, header, divider, empty, spacer, stack) = ("a", "b", "c", "d", "e", "f", "g");
+    let (surface, canvas, overlay, portal, memo, lazy, rich_text) =
+        ("h", "i", "j", "k", "l", "m", "n");
+    let (h_flex, v_flex, uniform_list) = ("o", "p", "q");
+    let _: Div = ui! {
+        div {
+            span, list, header, divider, empty, spacer, stack,
+            surface, canvas, overlay, portal, memo, lazy, rich_text,
+            h_flex, v_flex, uniform_list,
+        }
+    };
+}
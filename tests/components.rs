@@ -1,7 +1,14 @@
 //! Component tests for gpui-markup.
 
-use gpui::{App, IntoElement, ParentElement, RenderOnce, Styled, Window, div, px};
-use gpui_markup::ui;
+use gpui::prelude::FluentBuilder;
+use gpui::{
+    App, ClickEvent, Context, FocusHandle, InteractiveElement, IntoElement, ParentElement, Render,
+    RenderOnce, StatefulInteractiveElement, Styled, Window, div, px,
+};
+use gpui_markup::{
+    BaseComponent, InteractiveComponent, Markup, ParentComponent, Props, StyledComponent,
+    component, define_component, render, style, ui,
+};
 
 #[derive(IntoElement)]
 struct Header {
@@ -60,6 +67,23 @@ impl RenderOnce for Container {
     }
 }
 
+#[derive(IntoElement)]
+struct Label {
+    text: gpui::SharedString,
+}
+
+impl Label {
+    fn new(text: impl Into<gpui::SharedString>) -> Self {
+        Self { text: text.into() }
+    }
+}
+
+impl RenderOnce for Label {
+    fn render(self, _window: &mut Window, _cx: &mut App) -> impl IntoElement {
+        div().child(self.text)
+    }
+}
+
 /// Component as root element (calls `::new()` implicitly)
 #[test]
 fn test_simple_component() {
@@ -130,3 +154,408 @@ fn test_component_with_attributes() {
         Header @[flex] {}
     };
 }
+
+/// Constructor arguments in the tag are passed to `::new()`
+#[test]
+fn test_component_with_arguments() {
+    let name = "world";
+    let _ = ui! {
+        div {
+            Label("Save") {},
+            Label(format!("Hello, {name}!")) {},
+        }
+    };
+}
+
+mod widgets {
+    // The components are private to the test crate, so `pub` can't re-export them
+    #[allow(clippy::redundant_pub_crate)]
+    pub(super) use super::{Footer, Label};
+}
+
+/// Path-qualified component tags
+#[test]
+fn test_path_components() {
+    let _ = ui! {
+        div {
+            widgets::Footer {},
+            self::widgets::Label("Save") {},
+            crate::Header @[flex] {},
+        }
+    };
+}
+
+#[derive(IntoElement)]
+struct List<T: 'static> {
+    items: Vec<T>,
+}
+
+impl<T: 'static> List<T> {
+    const fn new() -> Self {
+        Self { items: Vec::new() }
+    }
+
+    fn item(mut self, item: T) -> Self {
+        self.items.push(item);
+        self
+    }
+}
+
+impl<T: 'static> RenderOnce for List<T> {
+    fn render(self, _window: &mut Window, _cx: &mut App) -> impl IntoElement {
+        div().child(self.items.len().to_string())
+    }
+}
+
+/// Generic component tags carry the turbofish into `::new()`
+#[test]
+fn test_generic_components() {
+    let _ = ui! {
+        div {
+            List::<usize> @[item: 1, item: 2] {},
+            widgets::Footer {},
+        }
+    };
+}
+
+#[derive(IntoElement, Markup)]
+#[markup(constructor = "builder")]
+struct Toolbar {
+    base: gpui::Div,
+}
+
+impl Toolbar {
+    fn builder() -> Self {
+        Self { base: div().flex() }
+    }
+}
+
+impl RenderOnce for Toolbar {
+    fn render(self, _window: &mut Window, _cx: &mut App) -> impl IntoElement {
+        self.base
+    }
+}
+
+#[derive(Default, IntoElement, Markup)]
+struct Spacer;
+
+impl RenderOnce for Spacer {
+    fn render(self, _window: &mut Window, _cx: &mut App) -> impl IntoElement {
+        div().flex_1()
+    }
+}
+
+/// `#[derive(Markup)]` provides `::new()` from another constructor or `Default`
+#[test]
+fn test_derived_constructor() {
+    let _ = ui! {
+        div {
+            Toolbar {},
+            Spacer {},
+        }
+    };
+}
+
+#[derive(Clone, Copy)]
+enum BadgeSize {
+    Small,
+}
+
+#[derive(IntoElement)]
+struct Badge {
+    base: gpui::Div,
+    small: bool,
+    outlined: bool,
+}
+
+impl Badge {
+    fn new() -> Self {
+        Self {
+            base: div(),
+            small: false,
+            outlined: false,
+        }
+    }
+
+    /// Overlaps the `size` method of `Styled`
+    const fn size(mut self, size: BadgeSize) -> Self {
+        self.small = matches!(size, BadgeSize::Small);
+        self
+    }
+
+    const fn outlined(mut self) -> Self {
+        self.outlined = true;
+        self
+    }
+}
+
+impl Styled for Badge {
+    fn style(&mut self) -> &mut gpui::StyleRefinement {
+        self.base.style()
+    }
+}
+
+impl RenderOnce for Badge {
+    fn render(self, _window: &mut Window, _cx: &mut App) -> impl IntoElement {
+        self.base
+    }
+}
+
+/// Keys are called as written on components, so the `Styled` method a setter
+/// overlaps is reached through a mixin
+#[test]
+fn test_component_props() {
+    let _ = ui! {
+        Badge @[:size: BadgeSize::Small, :outlined, use: style![size: px(16.0)], flex] {}
+    };
+}
+
+#[derive(IntoElement, Props)]
+struct Chip {
+    label: gpui::SharedString,
+    count: Option<usize>,
+    #[prop(default = true)]
+    closable: bool,
+    #[prop(skip, default = div())]
+    base: gpui::Div,
+}
+
+impl RenderOnce for Chip {
+    fn render(self, _window: &mut Window, _cx: &mut App) -> impl IntoElement {
+        let count = self.count.map(|count| count.to_string());
+        ui! {
+            self.base @[flex, when_some: (count, ParentElement::child)] {
+                self.label,
+            }
+        }
+    }
+}
+
+/// `#[derive(Props)]` setters convert with `Into` and wrap `Option` fields
+#[test]
+fn test_derived_props() {
+    let _ = ui! {
+        div {
+            Chip @[:label: "Rust", :count: 3, :closable: false] {},
+            Chip @[:label: "gpui"] {},
+        }
+    };
+}
+
+#[derive(IntoElement, ParentComponent)]
+struct Card {
+    title: gpui::SharedString,
+    children: Vec<gpui::AnyElement>,
+}
+
+impl Card {
+    fn new(title: impl Into<gpui::SharedString>) -> Self {
+        Self {
+            title: title.into(),
+            children: Vec::new(),
+        }
+    }
+}
+
+impl RenderOnce for Card {
+    fn render(self, _window: &mut Window, _cx: &mut App) -> impl IntoElement {
+        ui! {
+            div @[flex_col, p_4] {
+                self.title,
+                ..self.children,
+            }
+        }
+    }
+}
+
+/// `#[derive(ParentComponent)]` forwards children to the `children` field
+#[test]
+fn test_derived_parent() {
+    let _ = ui! {
+        Card("Settings") {
+            "General",
+            Spacer {},
+            Card("Nested") { "Body" },
+        }
+    };
+}
+
+#[derive(IntoElement, StyledComponent, InteractiveComponent)]
+#[interactive(stateful)]
+struct Row {
+    #[base]
+    root: gpui::Stateful<gpui::Div>,
+}
+
+impl Row {
+    fn new() -> Self {
+        Self {
+            root: div().id("row"),
+        }
+    }
+}
+
+impl RenderOnce for Row {
+    fn render(self, _window: &mut Window, _cx: &mut App) -> impl IntoElement {
+        self.root
+    }
+}
+
+/// Delegation derives let style attributes and listeners reach the base
+#[test]
+fn test_delegated_traits() {
+    let _ = ui! {
+        Row @[flex, p_2, hover: |s| s.bg(gpui::red()), on:click: |_, _, _| {}] {}
+    };
+}
+
+#[derive(IntoElement, BaseComponent, Props)]
+struct Tag {
+    label: gpui::SharedString,
+    #[prop(skip, default = div())]
+    base: gpui::Div,
+}
+
+impl RenderOnce for Tag {
+    fn render(self, _window: &mut Window, _cx: &mut App) -> impl IntoElement {
+        ui! { self.base @[px_1] { self.label } }
+    }
+}
+
+/// `@base[...]` forwards style attributes to the wrapped element
+#[test]
+fn test_base_attributes() {
+    let _ = ui! {
+        Tag @[:label: "New"] @base[mt: 2, w: px(120.0), flex] {}
+    };
+}
+
+define_component! {
+    /// A presentational component declared next to its usage
+    struct Pill extends div {
+        label: gpui::SharedString,
+        #[prop(default = 1)]
+        count: usize,
+    }
+
+    render(self) {
+        ui! {
+            self.base @[flex, gap_1] {
+                self.label,
+                self.count.to_string(),
+            }
+        }
+    }
+}
+
+/// `define_component!` components take props, styles, listeners and children
+#[test]
+fn test_declared_component() {
+    let _ = ui! {
+        div {
+            Pill @[:label: "Inbox", :count: 3, p_2, on:mouse_down.left: |_, _, _| {}] {
+                "extra",
+            },
+            Pill @[:label: "Drafts"] @base[mt: 2] {},
+        }
+    };
+}
+
+/// A function component with an `Into` prop, a defaulted prop and `cx`
+#[component]
+fn inbox_badge(
+    label: gpui::SharedString,
+    #[prop(default = 1)] count: usize,
+    cx: &mut App,
+) -> impl IntoElement {
+    let _ = cx;
+    ui! {
+        div @[flex, gap_1] {
+            label,
+            count.to_string(),
+        }
+    }
+}
+
+/// `#[component]` functions are used like hand-written components
+#[test]
+fn test_function_component() {
+    let _ = ui! {
+        div {
+            InboxBadge @[:label: "Inbox", :count: 3] {},
+            InboxBadge @[:label: "Drafts"] {},
+        }
+    };
+}
+
+struct Counter {
+    count: usize,
+}
+
+impl Counter {
+    fn increment(&mut self, _: &ClickEvent, _: &mut Window, cx: &mut Context<Self>) {
+        self.count += 1;
+        cx.notify();
+    }
+}
+
+impl Render for Counter {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        ui! {
+            #![listener(cx)]
+            div @[id: "counter", on:click: self.increment] {
+                text: self.count,
+            }
+        }
+    }
+}
+
+/// `self.method` handlers wrapped in `cx.listener` by the `listener` header
+#[test]
+fn test_listener_header() {
+    fn assert_render<T: Render>() {}
+    assert_render::<Counter>();
+}
+
+struct Dialog {
+    focus_handle: FocusHandle,
+}
+
+impl Render for Dialog {
+    fn render(&mut self, _window: &mut Window, _cx: &mut Context<Self>) -> impl IntoElement {
+        ui! {
+            div @[focus: self.focus_handle, focus_trap, flex, flex_col] {
+                div @[tab_index: 0] { "Cancel" },
+                div @[tab_index: 1] { "Confirm" },
+            }
+        }
+    }
+}
+
+/// `focus:` tracks a focus handle, `focus_trap` keeps Tab inside the element
+#[test]
+fn test_focus_trap() {
+    fn assert_render<T: Render>() {}
+    assert_render::<Dialog>();
+}
+
+struct Greeting {
+    name: gpui::SharedString,
+}
+
+render! {
+    struct = Greeting,
+    |self| ui! {
+        div @[flex, gap_2] {
+            "Hello,",
+            text: self.name,
+        }
+    }
+}
+
+/// `render!` implements `Render` from a closure-like body
+#[test]
+fn test_render_macro() {
+    fn assert_render<T: Render>() {}
+    assert_render::<Greeting>();
+}
@@ -14,6 +14,55 @@ fn test_dynamic_text() {
     };
 }
 
+#[test]
+fn test_interpolated_text() {
+    let name = "Alice";
+    let count = 3;
+    let _ = ui! {
+        div {
+            "Hello, {name}!",
+            "{count:>3} items",
+            "{{not interpolated}}",
+        }
+    };
+}
+
+#[test]
+fn test_display_text() {
+    let count = 42;
+    let ratio = 0.5;
+    let _ = ui! {
+        div {
+            7,
+            text: count,
+            text: ratio * 100.0,
+            text(count + 1),
+        }
+    };
+}
+
+#[test]
+fn test_theme_tokens() {
+    struct Theme {
+        surface: gpui::Hsla,
+        border: gpui::Hsla,
+    }
+    struct Cx(Theme);
+    impl Cx {
+        const fn theme(&self) -> &Theme {
+            &self.0
+        }
+    }
+
+    let cx = Cx(Theme {
+        surface: gpui::white(),
+        border: gpui::black(),
+    });
+    let _ = ui! {
+        div @[bg: $surface, border: (1, $border)] @hover[bg: $border] {}
+    };
+}
+
 #[test]
 fn test_dynamic_attribute_value() {
     let width = 300.0;
@@ -309,3 +358,19 @@ fn test_nested_closures_with_params() {
         }
     };
 }
+
+#[test]
+fn test_attribute_values_evaluated_once_in_order() {
+    let log = std::cell::RefCell::new(Vec::new());
+    let next = |n: u16| {
+        log.borrow_mut().push(n);
+        px(f32::from(n))
+    };
+    let _ = ui! {
+        div @[w: next(1), h: next(2)] @hover[mt: next(3)] {
+            div @[w: next(4)] {},
+            div @[when: (next(5) > px(0.0), Styled::flex)] {},
+        }
+    };
+    assert_eq!(*log.borrow(), [1, 2, 3, 4, 5]);
+}
@@ -0,0 +1,7 @@
+use gpui_markup::ui;
+
+fn main() {
+    let _ = ui! {
+        anchored @[on:mouse_down: |_, _, _| {}] {}
+    };
+}
@@ -0,0 +1,23 @@
+use gpui_markup::ui;
+
+struct Badge;
+
+impl Badge {
+    fn new() -> Self {
+        Self
+    }
+
+    fn count(self, _: u32) -> Self {
+        self
+    }
+}
+
+fn main() {
+    // Should fail: errors on attribute calls point at the attribute
+    let _ = ui! {
+        Badge @[count] {}
+    };
+    let _ = ui! {
+        Badge @[count: "3"] {}
+    };
+}
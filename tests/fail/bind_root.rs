@@ -0,0 +1,6 @@
+use gpui::div;
+use gpui_markup::ui;
+
+fn main() {
+    let _ = ui! { div @[bind: root] {} };
+}
@@ -0,0 +1,9 @@
+use gpui::canvas;
+use gpui_markup::ui;
+
+fn main() {
+    // Should fail: paint is missing
+    let _ = ui! { canvas { prepaint: |bounds, _, _| bounds } };
+    // Should fail: paint takes four parameters
+    let _ = ui! { canvas { paint: |bounds, window, cx| {} } };
+}
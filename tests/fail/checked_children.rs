@@ -0,0 +1,15 @@
+use gpui::div;
+use gpui_markup::ui;
+
+fn main() {
+    let title = String::from("Inbox");
+    let count = 3;
+    // Should fail: `&String` and numbers aren't elements
+    let _ = ui! {
+        #![options(checked)]
+        div {
+            &title,
+            count,
+        }
+    };
+}
@@ -0,0 +1,16 @@
+use gpui_markup::ui;
+
+fn main() {
+    // Should fail: only `cfg` attributes are supported
+    let _ = ui! {
+        div {
+            #[allow(unused)]
+            div {},
+        }
+    };
+    // Should fail: the top-level element can't be gated
+    let _ = ui! {
+        #[cfg(debug_assertions)]
+        div {}
+    };
+}
@@ -0,0 +1,9 @@
+use gpui::{IntoElement, div};
+use gpui_markup::component;
+
+#[component]
+fn label(text: &str) -> impl IntoElement {
+    div().child(text.to_owned())
+}
+
+fn main() {}
@@ -0,0 +1,8 @@
+use gpui_markup::ui;
+
+fn main() {
+    let _ = ui! {
+        #![elements(div = my_ui::div -> gpui::Div)]
+        div {}
+    };
+}
@@ -0,0 +1,7 @@
+use gpui_markup::ui;
+
+fn main() {
+    let _ = ui! {
+        div @[w: px(10.0), flex, w: px(20.0)] {}
+    };
+}
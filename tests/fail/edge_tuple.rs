@@ -0,0 +1,7 @@
+use gpui_markup::ui;
+
+fn main() {
+    let _ = ui! {
+        div @[p: (px(1.0), px(2.0), px(3.0), px(4.0), px(5.0))] {}
+    };
+}
@@ -0,0 +1,5 @@
+use gpui_markup::ui;
+
+fn main() {
+    let _ = ui! { empty { "Nothing" } };
+}
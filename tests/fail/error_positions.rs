@@ -13,7 +13,7 @@ fn main() {
     // Should fail: `IntoElement` not implemented
     let _ = ui! {
         deferred {
-            1
+            true
         }
     };
 }
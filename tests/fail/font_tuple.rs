@@ -0,0 +1,7 @@
+use gpui_markup::ui;
+
+fn main() {
+    let _ = ui! {
+        div @[font: ("Zed Mono", 13.0, 500, 1.5)] {}
+    };
+}
@@ -0,0 +1,14 @@
+use gpui::prelude::*;
+use gpui::{div, px};
+use gpui_markup::ui;
+
+fn main() {
+    let _ = ui! {
+        #![deny(unknown_attribute)]
+        div @[widht: px(10.0)] {}
+    };
+    let _ = ui! {
+        #![allow(duplicate_attributes)]
+        div @[w: px(10.0), w: px(20.0)] {}
+    };
+}
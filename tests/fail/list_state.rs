@@ -0,0 +1,9 @@
+use gpui::list;
+use gpui_markup::ui;
+
+fn main() {
+    // Should fail: the state is missing
+    let _ = ui! { list { |_, _, _| "Item" } };
+    // Should fail: the item renderer takes three parameters
+    let _ = ui! { list(state) { |ix| "Item" } };
+}
@@ -0,0 +1,7 @@
+use gpui_markup::Markup;
+
+#[derive(Markup)]
+#[markup(builder = "new")]
+struct Toolbar;
+
+fn main() {}
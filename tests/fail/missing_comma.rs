@@ -0,0 +1,11 @@
+use gpui::div;
+use gpui_markup::ui;
+
+fn main() {
+    let _ = ui! {
+        div {
+            "Title"
+            div { "Body" }
+        }
+    };
+}
@@ -0,0 +1,13 @@
+use gpui::div;
+use gpui_markup::ui;
+
+fn main() {
+    let _ = ui! {
+        div {
+            div @[w: , flex] {},
+            "Title"
+            div {},
+            ..,
+        }
+    };
+}
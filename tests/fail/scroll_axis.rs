@@ -0,0 +1,7 @@
+use gpui_markup::ui;
+
+fn main() {
+    let _ = ui! {
+        div @[id: "list", scroll: (vertical, handle)] {}
+    };
+}
@@ -0,0 +1,7 @@
+use gpui_markup::ui;
+
+fn main() {
+    let _ = ui! {
+        div @[size: (px(1.0), px(2.0), px(3.0))] {}
+    };
+}
@@ -0,0 +1,9 @@
+use gpui_markup::ui;
+
+fn main() {
+    let _ = ui! {
+        div {
+            span @[italic] { div {} },
+        }
+    };
+}
@@ -0,0 +1,7 @@
+use gpui_markup::ui;
+
+fn main() {
+    let _ = ui! {
+        div { "Press ", span @[on_click: |_, _| {}] { "here" } }
+    };
+}
@@ -0,0 +1,13 @@
+use gpui::div;
+use gpui_markup::ui;
+
+fn main() {
+    let counts: Vec<u32> = vec![1, 2, 3];
+    // Should fail: spread items must be elements
+    let _ = ui! {
+        div {
+            "Counts",
+            ..counts,
+        }
+    };
+}
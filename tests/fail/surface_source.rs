@@ -0,0 +1,8 @@
+use gpui_markup::ui;
+
+fn main() {
+    // Should fail: surface source is missing
+    let _ = ui! { surface {} };
+    // Should fail: surface source is an element
+    let _ = ui! { surface { div {} } };
+}
@@ -0,0 +1,10 @@
+use gpui_markup::ui;
+
+fn main() {
+    // Should fail: `svg` doesn't implement `ParentElement`
+    let _ = ui! {
+        div {
+            svg @[path: "icons/check.svg"] { "Check" },
+        }
+    };
+}
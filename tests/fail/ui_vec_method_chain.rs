@@ -0,0 +1,8 @@
+use gpui_markup::ui_vec;
+
+fn main() {
+    let _ = ui_vec! {
+        "First",
+        .flex(),
+    };
+}
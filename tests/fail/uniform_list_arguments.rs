@@ -0,0 +1,9 @@
+use gpui::uniform_list;
+use gpui_markup::ui;
+
+fn main() {
+    // Should fail: the item count is missing
+    let _ = ui! { uniform_list("files") |range, _, _| {} };
+    // Should fail: the range renderer takes three parameters
+    let _ = ui! { uniform_list("files", 10) |range| {} };
+}
@@ -0,0 +1,10 @@
+use gpui::prelude::*;
+use gpui::{div, px};
+use gpui_markup::ui;
+
+fn main() {
+    let _ = ui! {
+        #![options(checked)]
+        div @[flex, widht: px(120.)] {}
+    };
+}
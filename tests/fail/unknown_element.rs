@@ -0,0 +1,10 @@
+use gpui::div;
+use gpui_markup::ui;
+
+fn main() {
+    let _ = ui! {
+        div {
+            dvi @[flex] {},
+        }
+    };
+}
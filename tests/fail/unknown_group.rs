@@ -0,0 +1,9 @@
+use gpui_markup::ui;
+
+fn main() {
+    let _ = ui! {
+        div @[group: "sidebar"] {
+            div @group_hover("sidebr")[visible] {},
+        }
+    };
+}
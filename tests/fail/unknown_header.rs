@@ -0,0 +1,8 @@
+use gpui_markup::ui;
+
+fn main() {
+    let _ = ui! {
+        #![listenr(cx)]
+        div {}
+    };
+}
@@ -0,0 +1,11 @@
+use gpui_markup::ui;
+
+fn main() {
+    let _ = ui! {
+        #![deny(unknown_keyword)]
+        div @[rounded: mdd] {}
+    };
+    let _ = ui! {
+        div @[shadow: 2xll] {}
+    };
+}
@@ -0,0 +1,7 @@
+use gpui_markup::ui;
+
+fn main() {
+    let _ = ui! {
+        div @[id: "row", on:click.doubel: |_, _, _| {}] {}
+    };
+}